@@ -0,0 +1,507 @@
+//! ## Python bindings
+//! Exposes pool construction from tick lists, exact input and exact output quoting, position
+//! amounts, price and tick conversions and path encoding to Python with `pyo3`. Token amounts,
+//! liquidity and sqrt prices are Python `int`s and human-readable prices are `decimal.Decimal`s.
+
+use alloy_primitives::{aliases::U24, Address, U160, U256};
+use num_bigint::BigUint;
+use pyo3::{
+    exceptions::{PyOverflowError, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+use uniswap_v3_sdk::prelude::{sdk_core::prelude::*, Error, *};
+
+/// The number of significant digits of returned prices, matching the default precision of the
+/// `decimal` module
+const DECIMAL_PRECISION: u8 = 28;
+
+fn value_error(e: impl ToString) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+fn to_int(value: U256) -> BigUint {
+    BigUint::from_bytes_le(&value.to_le_bytes::<32>())
+}
+
+fn to_u256(value: &BigUint) -> PyResult<U256> {
+    U256::try_from_le_slice(&value.to_bytes_le())
+        .ok_or_else(|| PyOverflowError::new_err("int too big to convert to uint256"))
+}
+
+fn to_u160(value: &BigUint) -> PyResult<U160> {
+    U160::try_from_le_slice(&value.to_bytes_le())
+        .ok_or_else(|| PyOverflowError::new_err("int too big to convert to uint160"))
+}
+
+fn parse_address(address: &str) -> PyResult<Address> {
+    address.parse().map_err(value_error)
+}
+
+/// A token known only by its address and decimals.
+fn token(chain_id: u64, address: Address, decimals: u8) -> Token {
+    Token::new(chain_id, address, decimals, None, None, 0, 0)
+}
+
+/// Converts a number or string to its fixed-point notation with the `decimal` module.
+fn fixed_point(price: &Bound<'_, PyAny>) -> PyResult<String> {
+    let py = price.py();
+    py.import("decimal")?
+        .getattr("Decimal")?
+        .call1((price.str()?,))?
+        .call_method1("__format__", ("f",))?
+        .extract()
+}
+
+fn to_decimal<'py>(py: Python<'py>, value: &str) -> PyResult<Bound<'py, PyAny>> {
+    py.import("decimal")?.getattr("Decimal")?.call1((value,))
+}
+
+/// Parses a human-readable price of token0 in token1 into a raw price.
+fn parse_price(price: &str, token0: Token, token1: Token) -> PyResult<Price<Token, Token>> {
+    let (integer, fraction) = price.split_once('.').unwrap_or((price, ""));
+    let fraction = fraction.trim_end_matches('0');
+    let digits = U256::from_str_radix(&format!("{integer}{fraction}"), 10)
+        .map_err(|e| value_error(format!("invalid price {price}: {e}")))?;
+    if digits.is_zero() {
+        return Err(PyValueError::new_err("the price must be positive"));
+    }
+    let ten = U256::from(10);
+    let denominator = ten.pow(U256::from(fraction.len())) * ten.pow(U256::from(token0.decimals));
+    let numerator = digits * ten.pow(U256::from(token1.decimals));
+    Ok(Price::new(
+        token0,
+        token1,
+        denominator.to_big_int(),
+        numerator.to_big_int(),
+    ))
+}
+
+/// A Uniswap V3 pool with its initialized ticks
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id of the pool
+/// * `token_a`, `decimals_a`: The address and decimals of one of the tokens in the pool
+/// * `token_b`, `decimals_b`: The address and decimals of the other token in the pool
+/// * `fee`: The fee in hundredths of a bips
+/// * `sqrt_price_x96`: The current sqrt price as a Q64.96
+/// * `liquidity`: The current in range liquidity
+/// * `ticks`: The initialized ticks as `(index, liquidity_gross, liquidity_net)` tuples, sorted by
+///   index
+/// * `tick_spacing`: The tick spacing of a custom fee tier, defaulting to that of `fee`
+#[pyclass(name = "Pool", module = "uniswap_v3_sdk", frozen)]
+#[derive(Clone, Debug)]
+struct PyPool(Pool<TickListDataProvider>);
+
+#[pymethods]
+impl PyPool {
+    #[new]
+    #[pyo3(signature = (
+        chain_id, token_a, decimals_a, token_b, decimals_b, fee, sqrt_price_x96, liquidity, ticks,
+        tick_spacing = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        chain_id: u64,
+        token_a: &str,
+        decimals_a: u8,
+        token_b: &str,
+        decimals_b: u8,
+        fee: u32,
+        sqrt_price_x96: BigUint,
+        liquidity: u128,
+        ticks: Vec<(i32, u128, i128)>,
+        tick_spacing: Option<i32>,
+    ) -> PyResult<Self> {
+        let fee = match tick_spacing {
+            Some(tick_spacing) if tick_spacing > 0 => FeeAmount::new_custom(fee, tick_spacing),
+            Some(tick_spacing) => {
+                return Err(value_error(format!("invalid tick spacing: {tick_spacing}")))
+            }
+            None => get_fee_amount(chain_id, U24::from(fee)).map_err(value_error)?,
+        };
+        let ticks = ticks
+            .into_iter()
+            .map(|(index, liquidity_gross, liquidity_net)| {
+                Tick::new(index, liquidity_gross, liquidity_net)
+            })
+            .collect();
+        Pool::new_with_tick_data_provider(
+            token(chain_id, parse_address(token_a)?, decimals_a),
+            token(chain_id, parse_address(token_b)?, decimals_b),
+            fee,
+            to_u160(&sqrt_price_x96)?,
+            liquidity,
+            TickListDataProvider::new(ticks, fee.tick_spacing().as_i32()),
+        )
+        .map(Self)
+        .map_err(value_error)
+    }
+
+    #[getter]
+    fn token0(&self) -> String {
+        self.0.token0.address.to_checksum(None)
+    }
+
+    #[getter]
+    fn token1(&self) -> String {
+        self.0.token1.address.to_checksum(None)
+    }
+
+    #[getter]
+    fn fee(&self) -> u32 {
+        Into::<U24>::into(self.0.fee).to()
+    }
+
+    #[getter]
+    fn tick_spacing(&self) -> i32 {
+        self.0.tick_spacing()
+    }
+
+    #[getter]
+    fn sqrt_price_x96(&self) -> BigUint {
+        to_int(U256::from(self.0.sqrt_ratio_x96))
+    }
+
+    #[getter]
+    fn liquidity(&self) -> u128 {
+        self.0.liquidity
+    }
+
+    #[getter]
+    fn tick_current(&self) -> i32 {
+        self.0.tick_current
+    }
+
+    /// The price of token0 in token1, adjusted for decimals
+    #[getter]
+    fn token0_price<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let price = self
+            .0
+            .token0_price()
+            .to_significant(DECIMAL_PRECISION, None)
+            .map_err(value_error)?;
+        to_decimal(py, &price)
+    }
+
+    /// Returns the output amount of swapping `amount_in` of `token_in`.
+    #[pyo3(signature = (token_in, amount_in, sqrt_price_limit_x96 = None))]
+    fn get_output_amount(
+        &self,
+        py: Python<'_>,
+        token_in: &str,
+        amount_in: BigUint,
+        sqrt_price_limit_x96: Option<BigUint>,
+    ) -> PyResult<BigUint> {
+        let limit = sqrt_price_limit_x96.as_ref().map(to_u160).transpose()?;
+        self.quote_batch(py, token_in, &[amount_in], limit, true)
+            .map(|mut amounts| amounts.remove(0))
+    }
+
+    /// Returns the input amount of `token_in` required to receive `amount_out` of the other token.
+    #[pyo3(signature = (token_out, amount_out, sqrt_price_limit_x96 = None))]
+    fn get_input_amount(
+        &self,
+        py: Python<'_>,
+        token_out: &str,
+        amount_out: BigUint,
+        sqrt_price_limit_x96: Option<BigUint>,
+    ) -> PyResult<BigUint> {
+        let limit = sqrt_price_limit_x96.as_ref().map(to_u160).transpose()?;
+        self.quote_batch(py, token_out, &[amount_out], limit, false)
+            .map(|mut amounts| amounts.remove(0))
+    }
+
+    /// Quotes every amount of `amounts_in` as an independent exact input swap against the current
+    /// pool state, releasing the GIL while quoting.
+    fn get_output_amounts(
+        &self,
+        py: Python<'_>,
+        token_in: &str,
+        amounts_in: Vec<BigUint>,
+    ) -> PyResult<Vec<BigUint>> {
+        self.quote_batch(py, token_in, &amounts_in, None, true)
+    }
+
+    /// Quotes every amount of `amounts_out` as an independent exact output swap against the
+    /// current pool state, releasing the GIL while quoting.
+    fn get_input_amounts(
+        &self,
+        py: Python<'_>,
+        token_out: &str,
+        amounts_out: Vec<BigUint>,
+    ) -> PyResult<Vec<BigUint>> {
+        self.quote_batch(py, token_out, &amounts_out, None, false)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Pool(token0={}, token1={}, fee={}, tick_current={})",
+            self.token0(),
+            self.token1(),
+            self.fee(),
+            self.0.tick_current
+        )
+    }
+}
+
+impl PyPool {
+    /// Returns the pool token at `address`.
+    fn token(&self, address: &str) -> PyResult<Token> {
+        let address = parse_address(address)?;
+        if address == self.0.token0.address {
+            Ok(self.0.token0.clone())
+        } else if address == self.0.token1.address {
+            Ok(self.0.token1.clone())
+        } else {
+            Err(value_error(Error::InvalidToken))
+        }
+    }
+
+    fn quote_batch(
+        &self,
+        py: Python<'_>,
+        token: &str,
+        amounts: &[BigUint],
+        sqrt_price_limit_x96: Option<U160>,
+        exact_input: bool,
+    ) -> PyResult<Vec<BigUint>> {
+        let token = self.token(token)?;
+        let amounts = amounts.iter().map(to_u256).collect::<PyResult<Vec<_>>>()?;
+        py.allow_threads(|| {
+            amounts
+                .into_iter()
+                .map(|amount| {
+                    let amount =
+                        CurrencyAmount::from_raw_amount(token.clone(), amount.to_big_int())?;
+                    let quote = if exact_input {
+                        self.0.get_output_amount(&amount, sqrt_price_limit_x96)?
+                    } else {
+                        self.0.get_input_amount(&amount, sqrt_price_limit_x96)?
+                    };
+                    Ok(to_int(U256::from_big_int(quote.quotient())))
+                })
+                .collect::<Result<Vec<_>, Error>>()
+        })
+        .map_err(value_error)
+    }
+}
+
+/// A liquidity position in a pool
+///
+/// ## Arguments
+///
+/// * `pool`: The pool of the position
+/// * `tick_lower`: The lower tick of the position
+/// * `tick_upper`: The upper tick of the position
+/// * `liquidity`: The liquidity of the position
+#[pyclass(name = "Position", module = "uniswap_v3_sdk", frozen)]
+#[derive(Clone, Debug)]
+struct PyPosition(Position<TickListDataProvider>);
+
+#[pymethods]
+impl PyPosition {
+    #[new]
+    fn new(pool: &PyPool, tick_lower: i32, tick_upper: i32, liquidity: u128) -> PyResult<Self> {
+        Position::try_new(pool.0.clone(), liquidity, tick_lower, tick_upper)
+            .map(Self)
+            .map_err(value_error)
+    }
+
+    /// Returns the position with the maximum liquidity for the given amounts of token0 and
+    /// token1.
+    #[staticmethod]
+    #[pyo3(signature = (pool, tick_lower, tick_upper, amount0, amount1, use_full_precision = true))]
+    fn from_amounts(
+        pool: &PyPool,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount0: BigUint,
+        amount1: BigUint,
+        use_full_precision: bool,
+    ) -> PyResult<Self> {
+        Position::from_amounts(
+            pool.0.clone(),
+            tick_lower,
+            tick_upper,
+            to_u256(&amount0)?,
+            to_u256(&amount1)?,
+            use_full_precision,
+        )
+        .map(Self)
+        .map_err(value_error)
+    }
+
+    #[getter]
+    fn pool(&self) -> PyPool {
+        PyPool(self.0.pool.clone())
+    }
+
+    #[getter]
+    fn tick_lower(&self) -> i32 {
+        self.0.tick_lower
+    }
+
+    #[getter]
+    fn tick_upper(&self) -> i32 {
+        self.0.tick_upper
+    }
+
+    #[getter]
+    fn liquidity(&self) -> u128 {
+        self.0.liquidity
+    }
+
+    /// The amount of token0 the position is worth at the current pool price, rounded down
+    #[getter]
+    fn amount0(&self) -> PyResult<BigUint> {
+        let amount0 = self.0.amount0().map_err(value_error)?;
+        Ok(to_int(U256::from_big_int(amount0.quotient())))
+    }
+
+    /// The amount of token1 the position is worth at the current pool price, rounded down
+    #[getter]
+    fn amount1(&self) -> PyResult<BigUint> {
+        let amount1 = self.0.amount1().map_err(value_error)?;
+        Ok(to_int(U256::from_big_int(amount1.quotient())))
+    }
+
+    /// Returns the `(amount0, amount1)` required to mint the liquidity of the position at the
+    /// current pool price, rounded up.
+    fn mint_amounts(&self) -> PyResult<(BigUint, BigUint)> {
+        let MintAmounts { amount0, amount1 } = self.0.mint_amounts().map_err(value_error)?;
+        Ok((to_int(amount0), to_int(amount1)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Position(tick_lower={}, tick_upper={}, liquidity={})",
+            self.0.tick_lower, self.0.tick_upper, self.0.liquidity
+        )
+    }
+}
+
+/// Returns the sqrt price as a Q64.96 at `tick`.
+#[pyfunction(name = "get_sqrt_ratio_at_tick")]
+fn py_get_sqrt_ratio_at_tick(tick: i32) -> PyResult<BigUint> {
+    if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+        return Err(PyValueError::new_err("TICK_BOUND"));
+    }
+    get_sqrt_ratio_at_tick(tick.to_i24())
+        .map(|sqrt_ratio_x96| to_int(U256::from(sqrt_ratio_x96)))
+        .map_err(value_error)
+}
+
+/// Returns the greatest tick whose sqrt price is at most `sqrt_ratio_x96`.
+#[pyfunction(name = "get_tick_at_sqrt_ratio")]
+fn py_get_tick_at_sqrt_ratio(sqrt_ratio_x96: BigUint) -> PyResult<i32> {
+    get_tick_at_sqrt_ratio(to_u160(&sqrt_ratio_x96)?)
+        .map(|tick| tick.as_i32())
+        .map_err(value_error)
+}
+
+/// Returns the closest tick to `tick` that is a multiple of `tick_spacing`.
+#[pyfunction(name = "nearest_usable_tick")]
+fn py_nearest_usable_tick(tick: i32, tick_spacing: i32) -> PyResult<i32> {
+    if tick_spacing <= 0 {
+        return Err(PyValueError::new_err("TICK_SPACING"));
+    }
+    if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+        return Err(PyValueError::new_err("TICK_BOUND"));
+    }
+    Ok(nearest_usable_tick(tick, tick_spacing))
+}
+
+/// Returns the price of token0 in token1 at `tick` as a `Decimal`, adjusted for the decimals of
+/// the tokens.
+#[pyfunction(name = "tick_to_price")]
+#[pyo3(signature = (tick, decimals0 = 18, decimals1 = 18))]
+fn py_tick_to_price(
+    py: Python<'_>,
+    tick: i32,
+    decimals0: u8,
+    decimals1: u8,
+) -> PyResult<Bound<'_, PyAny>> {
+    let price = tick_to_price(
+        token(1, Address::with_last_byte(1), decimals0),
+        token(1, Address::with_last_byte(2), decimals1),
+        tick.to_i24(),
+    )
+    .and_then(|price| Ok(price.to_significant(DECIMAL_PRECISION, None)?))
+    .map_err(value_error)?;
+    to_decimal(py, &price)
+}
+
+/// Returns the tick whose price of token0 in token1 is closest to and at most `price`, which is a
+/// `Decimal`, `int`, `float` or `str` adjusted for the decimals of the tokens.
+#[pyfunction(name = "price_to_closest_tick")]
+#[pyo3(signature = (price, decimals0 = 18, decimals1 = 18))]
+fn py_price_to_closest_tick(
+    price: &Bound<'_, PyAny>,
+    decimals0: u8,
+    decimals1: u8,
+) -> PyResult<i32> {
+    let price = parse_price(
+        &fixed_point(price)?,
+        token(1, Address::with_last_byte(1), decimals0),
+        token(1, Address::with_last_byte(2), decimals1),
+    )?;
+    price_to_closest_tick(&price)
+        .map(|tick| tick.as_i32())
+        .map_err(value_error)
+}
+
+/// Encodes the path of a multi-hop swap through `tokens` with one fee between every two tokens,
+/// reversed if `exact_output`.
+#[pyfunction]
+#[pyo3(signature = (tokens, fees, exact_output = false))]
+fn encode_path<'py>(
+    py: Python<'py>,
+    tokens: Vec<String>,
+    fees: Vec<u32>,
+    exact_output: bool,
+) -> PyResult<Bound<'py, PyBytes>> {
+    if tokens.len() < 2 || fees.len() != tokens.len() - 1 {
+        return Err(PyValueError::new_err(
+            "a path needs one fee between every two tokens",
+        ));
+    }
+    let tokens = tokens
+        .iter()
+        .map(|address| Ok(token(1, parse_address(address)?, 18)))
+        .collect::<PyResult<Vec<_>>>()?;
+    let pools = tokens
+        .windows(2)
+        .zip(fees)
+        .map(|(pair, fee)| {
+            Pool::new(
+                pair[0].clone(),
+                pair[1].clone(),
+                // only the fee is encoded in the path
+                FeeAmount::new_custom(fee, 0),
+                U160::from(Q96),
+                0,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(value_error)?;
+    let route = Route::new(pools, tokens[0].clone(), tokens[tokens.len() - 1].clone());
+    Ok(PyBytes::new(
+        py,
+        &encode_route_to_path(&route, exact_output),
+    ))
+}
+
+#[pymodule(name = "uniswap_v3_sdk")]
+fn py_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyPool>()?;
+    m.add_class::<PyPosition>()?;
+    m.add_function(wrap_pyfunction!(py_get_sqrt_ratio_at_tick, m)?)?;
+    m.add_function(wrap_pyfunction!(py_get_tick_at_sqrt_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(py_nearest_usable_tick, m)?)?;
+    m.add_function(wrap_pyfunction!(py_tick_to_price, m)?)?;
+    m.add_function(wrap_pyfunction!(py_price_to_closest_tick, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_path, m)?)?;
+    Ok(())
+}
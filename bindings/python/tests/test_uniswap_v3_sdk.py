@@ -0,0 +1,129 @@
+from concurrent.futures import ThreadPoolExecutor
+from decimal import Decimal
+
+import pytest
+
+from uniswap_v3_sdk import (
+    Pool,
+    Position,
+    encode_path,
+    get_sqrt_ratio_at_tick,
+    get_tick_at_sqrt_ratio,
+    nearest_usable_tick,
+    price_to_closest_tick,
+    tick_to_price,
+)
+
+TOKEN0 = "0x0000000000000000000000000000000000000001"
+TOKEN1 = "0x0000000000000000000000000000000000000002"
+TOKEN2 = "0x0000000000000000000000000000000000000003"
+Q96 = 2**96
+LIQUIDITY = 10**6
+MIN_TICK = nearest_usable_tick(-887272, 60)
+MAX_TICK = nearest_usable_tick(887272, 60)
+
+
+def make_pool():
+    return Pool(
+        1,
+        TOKEN1,
+        18,
+        TOKEN0,
+        18,
+        3000,
+        Q96,
+        LIQUIDITY,
+        [(MIN_TICK, LIQUIDITY, LIQUIDITY), (MAX_TICK, LIQUIDITY, -LIQUIDITY)],
+    )
+
+
+def test_pool():
+    pool = make_pool()
+    assert pool.token0 == TOKEN0
+    assert pool.token1 == TOKEN1
+    assert pool.fee == 3000
+    assert pool.tick_spacing == 60
+    assert pool.sqrt_price_x96 == Q96
+    assert pool.liquidity == LIQUIDITY
+    assert pool.tick_current == 0
+    assert pool.token0_price == Decimal(1)
+
+
+def test_quote():
+    pool = make_pool()
+    assert pool.get_output_amount(TOKEN0, 100) == 98
+    assert pool.get_input_amount(TOKEN1, 98) == 100
+    with pytest.raises(ValueError):
+        pool.get_output_amount(TOKEN2, 100)
+    with pytest.raises(OverflowError):
+        pool.get_output_amount(TOKEN0, -1)
+
+
+def test_batch_quote():
+    pool = make_pool()
+    amounts = list(range(100, 1100, 100))
+    expected = [pool.get_output_amount(TOKEN0, amount) for amount in amounts]
+    assert pool.get_output_amounts(TOKEN0, amounts) == expected
+    assert pool.get_input_amounts(TOKEN1, expected) == [
+        pool.get_input_amount(TOKEN1, amount) for amount in expected
+    ]
+    with ThreadPoolExecutor(4) as executor:
+        results = executor.map(
+            lambda _: pool.get_output_amounts(TOKEN0, amounts), range(8)
+        )
+        assert all(result == expected for result in results)
+
+
+def test_position():
+    pool = make_pool()
+    position = Position(pool, -120, 120, LIQUIDITY)
+    assert position.amount0 == 5981
+    assert position.amount1 == 5981
+    assert position.mint_amounts() == (5982, 5982)
+    from_amounts = Position.from_amounts(pool, -120, 120, 5982, 5982)
+    assert from_amounts.liquidity >= LIQUIDITY
+    with pytest.raises(ValueError):
+        Position(pool, 120, -120, LIQUIDITY)
+    # misaligned with the tick spacing of 60 and out of bounds
+    with pytest.raises(ValueError):
+        Position(pool, -100, 120, LIQUIDITY)
+    with pytest.raises(ValueError):
+        Position(pool, MIN_TICK - 60, 120, LIQUIDITY)
+    with pytest.raises(ValueError):
+        Position.from_amounts(pool, -120, 100, 5982, 5982)
+
+
+def test_tick_math():
+    assert get_sqrt_ratio_at_tick(0) == Q96
+    assert get_tick_at_sqrt_ratio(Q96) == 0
+    assert get_tick_at_sqrt_ratio(get_sqrt_ratio_at_tick(-887272)) == -887272
+    assert nearest_usable_tick(-887272, 60) == -887220
+    with pytest.raises(ValueError):
+        get_sqrt_ratio_at_tick(1 << 23)
+    with pytest.raises(ValueError):
+        nearest_usable_tick(887273, 60)
+    with pytest.raises(ValueError):
+        nearest_usable_tick(0, 0)
+
+
+def test_price_tick_conversions():
+    assert tick_to_price(0) == Decimal(1)
+    price = tick_to_price(-201120, 18, 6)
+    assert Decimal(1800) < price < Decimal(1900)
+    assert price_to_closest_tick(price, 18, 6) == -201120
+    assert price_to_closest_tick("1", 18, 18) == 0
+    assert price_to_closest_tick(Decimal("1.00015"), 18, 18) == 1
+    with pytest.raises(ValueError):
+        price_to_closest_tick(0)
+
+
+def test_encode_path():
+    path = encode_path([TOKEN0, TOKEN1, TOKEN2], [500, 3000])
+    assert path.hex() == (
+        TOKEN0[2:] + "0001f4" + TOKEN1[2:] + "000bb8" + TOKEN2[2:]
+    )
+    assert encode_path([TOKEN0, TOKEN1, TOKEN2], [500, 3000], exact_output=True).hex() == (
+        TOKEN2[2:] + "000bb8" + TOKEN1[2:] + "0001f4" + TOKEN0[2:]
+    )
+    with pytest.raises(ValueError):
+        encode_path([TOKEN0, TOKEN1], [])
@@ -70,6 +70,7 @@ async fn main() {
             npm,
             amount0,
             &provider,
+            None,
         )
         .await
         .unwrap();
@@ -80,6 +81,7 @@ async fn main() {
                 npm,
                 amount1,
                 &provider,
+                None,
             )
             .await
             .unwrap(),
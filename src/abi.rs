@@ -92,6 +92,7 @@ sol! {
 
     interface IERC721Permit {
         #[derive(Debug, Default, PartialEq, Eq)]
+        #[cfg_attr(feature = "extensions", derive(serde::Serialize, serde::Deserialize))]
         struct Permit {
             address spender;
             uint256 tokenId;
@@ -116,6 +117,7 @@ sol! {
 
     interface IERC20Permit {
         #[derive(Debug, Default, PartialEq, Eq)]
+        #[cfg_attr(feature = "extensions", derive(serde::Serialize, serde::Deserialize))]
         struct Permit {
             address owner;
             address spender;
@@ -127,6 +129,7 @@ sol! {
 
     interface IDaiPermit {
         #[derive(Debug, Default, PartialEq, Eq)]
+        #[cfg_attr(feature = "extensions", derive(serde::Serialize, serde::Deserialize))]
         struct Permit {
             address holder;
             address spender;
@@ -188,6 +191,24 @@ sol! {
             address to,
             uint256 amountRequested
         ) external returns (uint256 reward);
+
+        function createIncentive(IncentiveKey memory key, uint256 reward) external;
+
+        function endIncentive(IncentiveKey memory key) external returns (uint256 refund);
+
+        function transferDeposit(uint256 tokenId, address to) external;
+
+        function getRewardInfo(IncentiveKey memory key, uint256 tokenId)
+            external
+            returns (uint256 reward, uint160 secondsInsideX128);
+    }
+}
+
+sol! {
+    interface IUniswapV3Factory {
+        function createPool(address tokenA, address tokenB, uint24 fee) external returns (address pool);
+
+        function enableFeeAmount(uint24 fee, int24 tickSpacing) external;
     }
 }
 
@@ -322,3 +343,108 @@ sol! {
         function exactOutput(ExactOutputParams calldata params) external payable returns (uint256 amountIn);
     }
 }
+
+sol! {
+    interface ISwapRouter {
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct ExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 deadline;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function exactInputSingle(ExactInputSingleParams calldata params) external payable returns (uint256 amountOut);
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct ExactInputParams {
+            bytes path;
+            address recipient;
+            uint256 deadline;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+        }
+
+        function exactInput(ExactInputParams calldata params) external payable returns (uint256 amountOut);
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct ExactOutputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 deadline;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        function exactOutputSingle(ExactOutputSingleParams calldata params) external payable returns (uint256 amountIn);
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct ExactOutputParams {
+            bytes path;
+            address recipient;
+            uint256 deadline;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+        }
+
+        function exactOutput(ExactOutputParams calldata params) external payable returns (uint256 amountIn);
+    }
+}
+
+sol! {
+    interface IPeripheryPaymentsExtended {
+        function unwrapWETH9(uint256 amountMinimum) external payable;
+
+        function wrapETH(uint256 value) external payable;
+
+        function sweepToken(address token, uint256 amountMinimum) external payable;
+
+        function pull(address token, uint256 value) external payable;
+    }
+}
+
+sol! {
+    interface IApproveAndCall {
+        function approveMax(address token) external payable;
+
+        function approveMaxMinusOne(address token) external payable;
+
+        function approveZeroThenMax(address token) external payable;
+
+        function approveZeroThenMaxMinusOne(address token) external payable;
+
+        function callPositionManager(bytes memory data) external payable returns (bytes memory result);
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct MintParams {
+            address token0;
+            address token1;
+            uint24 fee;
+            int24 tickLower;
+            int24 tickUpper;
+            uint256 amount0Min;
+            uint256 amount1Min;
+            address recipient;
+        }
+
+        function mint(MintParams calldata params) external payable returns (bytes memory result);
+
+        #[derive(Debug, Default, PartialEq, Eq)]
+        struct IncreaseLiquidityParams {
+            address token0;
+            address token1;
+            uint256 tokenId;
+            uint256 amount0Min;
+            uint256 amount1Min;
+        }
+
+        function increaseLiquidity(IncreaseLiquidityParams calldata params) external payable returns (bytes memory result);
+    }
+}
@@ -0,0 +1,234 @@
+use crate::prelude::{Error, *};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::SolCall;
+use uniswap_sdk_core::prelude::*;
+
+/// The approval the router must grant the `NonfungiblePositionManager` to spend a token, as
+/// returned by `getApprovalType` on
+/// [`SwapRouter02`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/base/ApproveAndCall.sol).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ApprovalType {
+    #[default]
+    NotRequired,
+    Max,
+    MaxMinusOne,
+    ZeroThenMax,
+    ZeroThenMaxMinusOne,
+}
+
+/// Options for adding liquidity through the router, without the fields handled by the router.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CondensedAddLiquidityOptions {
+    /// Mint a new position to the recipient
+    Mint {
+        /// The account that should receive the minted NFT.
+        recipient: Address,
+    },
+    /// Increase the liquidity of an existing position
+    Increase {
+        /// The ID of the position to increase liquidity for.
+        token_id: U256,
+    },
+}
+
+/// Encodes the approval of `token` for the `NonfungiblePositionManager`.
+///
+/// ## Arguments
+///
+/// * `token`: The token to approve
+/// * `approval_type`: The type of approval, [`Error::ApprovalNotRequired`] is returned for
+///   [`ApprovalType::NotRequired`]
+#[inline]
+pub fn encode_approve(token: Address, approval_type: ApprovalType) -> Result<Bytes, Error> {
+    Ok(match approval_type {
+        ApprovalType::Max => IApproveAndCall::approveMaxCall { token }.abi_encode(),
+        ApprovalType::MaxMinusOne => IApproveAndCall::approveMaxMinusOneCall { token }.abi_encode(),
+        ApprovalType::ZeroThenMax => IApproveAndCall::approveZeroThenMaxCall { token }.abi_encode(),
+        ApprovalType::ZeroThenMaxMinusOne => {
+            IApproveAndCall::approveZeroThenMaxMinusOneCall { token }.abi_encode()
+        }
+        ApprovalType::NotRequired => return Err(Error::ApprovalNotRequired),
+    }
+    .into())
+}
+
+/// Encodes a call forwarding the given calldata to the `NonfungiblePositionManager`.
+///
+/// ## Arguments
+///
+/// * `calldatas`: The calls to forward, wrapped in a multicall if there is more than one
+#[inline]
+#[must_use]
+pub fn encode_call_position_manager(calldatas: Vec<Bytes>) -> Bytes {
+    assert!(!calldatas.is_empty(), "NULL_CALLDATA");
+    IApproveAndCall::callPositionManagerCall {
+        data: encode_multicall(calldatas),
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Encodes adding liquidity to a position with the router's token balances.
+///
+/// ## Arguments
+///
+/// * `position`: The position to add liquidity for
+/// * `minimal_position`: The position resulting from the worst acceptable swap outcome, whose
+///   amounts bound the minimum amounts when slippage alone would be too strict, e.g. for range
+///   orders
+/// * `add_liquidity_options`: Whether to mint a new position or increase an existing one
+/// * `slippage_tolerance`: How much the pool price is allowed to move
+#[inline]
+pub fn encode_add_liquidity<TP: TickDataProvider>(
+    position: &mut Position<TP>,
+    minimal_position: &mut Position<TP>,
+    add_liquidity_options: CondensedAddLiquidityOptions,
+    slippage_tolerance: &Percent,
+) -> Result<Bytes, Error> {
+    let MintAmounts {
+        amount0: mut amount0_min,
+        amount1: mut amount1_min,
+    } = position.mint_amounts_with_slippage(slippage_tolerance)?;
+    let minimal_amount0 = U256::from_big_int(minimal_position.amount0_cached()?.quotient());
+    let minimal_amount1 = U256::from_big_int(minimal_position.amount1_cached()?.quotient());
+    amount0_min = amount0_min.min(minimal_amount0);
+    amount1_min = amount1_min.min(minimal_amount1);
+    Ok(match add_liquidity_options {
+        CondensedAddLiquidityOptions::Mint { recipient } => IApproveAndCall::mintCall {
+            params: IApproveAndCall::MintParams {
+                token0: position.pool.token0.address(),
+                token1: position.pool.token1.address(),
+                fee: position.pool.fee.into(),
+                tickLower: position.tick_lower.to_i24(),
+                tickUpper: position.tick_upper.to_i24(),
+                amount0Min: amount0_min,
+                amount1Min: amount1_min,
+                recipient,
+            },
+        }
+        .abi_encode(),
+        CondensedAddLiquidityOptions::Increase { token_id } => {
+            IApproveAndCall::increaseLiquidityCall {
+                params: IApproveAndCall::IncreaseLiquidityParams {
+                    token0: position.pool.token0.address(),
+                    token1: position.pool.token1.address(),
+                    tokenId: token_id,
+                    amount0Min: amount0_min,
+                    amount1Min: amount1_min,
+                },
+            }
+            .abi_encode()
+        }
+    }
+    .into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, hex, uint};
+
+    const TOKEN: Address = address!("0000000000000000000000000000000000000001");
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+
+    #[test]
+    fn test_encode_approve() {
+        assert_eq!(
+            encode_approve(TOKEN, ApprovalType::Max).unwrap().to_vec(),
+            hex!("571ac8b00000000000000000000000000000000000000000000000000000000000000001")
+        );
+        assert_eq!(
+            encode_approve(TOKEN, ApprovalType::MaxMinusOne)
+                .unwrap()
+                .to_vec(),
+            hex!("cab372ce0000000000000000000000000000000000000000000000000000000000000001")
+        );
+        assert_eq!(
+            encode_approve(TOKEN, ApprovalType::ZeroThenMax)
+                .unwrap()
+                .to_vec(),
+            hex!("639d71a90000000000000000000000000000000000000000000000000000000000000001")
+        );
+        assert_eq!(
+            encode_approve(TOKEN, ApprovalType::ZeroThenMaxMinusOne)
+                .unwrap()
+                .to_vec(),
+            hex!("ab3fdd500000000000000000000000000000000000000000000000000000000000000001")
+        );
+    }
+
+    #[test]
+    fn test_encode_approve_not_required() {
+        assert!(matches!(
+            encode_approve(TOKEN, ApprovalType::NotRequired),
+            Err(Error::ApprovalNotRequired)
+        ));
+    }
+
+    #[test]
+    fn test_encode_call_position_manager() {
+        let calldata = encode_call_position_manager(vec![INonfungiblePositionManager::burnCall {
+            tokenId: uint!(1_U256),
+        }
+        .abi_encode()
+        .into()]);
+        let decoded =
+            IApproveAndCall::callPositionManagerCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(
+            INonfungiblePositionManager::burnCall::abi_decode(&decoded.data, true)
+                .unwrap()
+                .tokenId,
+            uint!(1_U256)
+        );
+    }
+
+    #[test]
+    fn test_encode_add_liquidity_mint() {
+        let mut position = Position::new(POOL_0_1.clone(), 1_000_000, -60, 60);
+        let mut minimal_position = Position::new(POOL_0_1.clone(), 0, -60, 60);
+        let calldata = encode_add_liquidity(
+            &mut position,
+            &mut minimal_position,
+            CondensedAddLiquidityOptions::Mint {
+                recipient: RECIPIENT,
+            },
+            &Percent::new(1, 100),
+        )
+        .unwrap();
+        let params = IApproveAndCall::mintCall::abi_decode(&calldata, true)
+            .unwrap()
+            .params;
+        assert_eq!(params.token0, TOKEN0.address());
+        assert_eq!(params.tickLower.as_i32(), -60);
+        assert_eq!(params.tickUpper.as_i32(), 60);
+        // bounded by the minimal position with zero liquidity
+        assert_eq!(params.amount0Min, U256::ZERO);
+        assert_eq!(params.amount1Min, U256::ZERO);
+        assert_eq!(params.recipient, RECIPIENT);
+    }
+
+    #[test]
+    fn test_encode_add_liquidity_increase() {
+        let mut position = Position::new(POOL_0_1.clone(), 1_000_000, -60, 60);
+        let mut minimal_position = position.clone();
+        let calldata = encode_add_liquidity(
+            &mut position,
+            &mut minimal_position,
+            CondensedAddLiquidityOptions::Increase {
+                token_id: uint!(1_U256),
+            },
+            &Percent::new(1, 100),
+        )
+        .unwrap();
+        let params = IApproveAndCall::increaseLiquidityCall::abi_decode(&calldata, true)
+            .unwrap()
+            .params;
+        let MintAmounts { amount0, amount1 } = position
+            .mint_amounts_with_slippage(&Percent::new(1, 100))
+            .unwrap();
+        assert_eq!(params.tokenId, uint!(1_U256));
+        assert_eq!(params.amount0Min, amount0);
+        assert_eq!(params.amount1Min, amount1);
+    }
+}
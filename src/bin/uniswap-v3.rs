@@ -0,0 +1,959 @@
+//! A command-line tool for common Uniswap V3 SDK operations, printing its results as JSON.
+//!
+//! Run `uniswap-v3 help` for the list of commands. Pool and position quotes read the pool state
+//! from a local snapshot file, e.g.
+//!
+//! ```json
+//! {
+//!   "chain_id": 1,
+//!   "token0": { "address": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "decimals": 6 },
+//!   "token1": { "address": "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2", "decimals": 18 },
+//!   "fee": 500,
+//!   "sqrt_price_x96": "1350174849792634181862360983626536",
+//!   "liquidity": "10000000000000000000",
+//!   "ticks": [
+//!     { "index": -887270, "liquidity_gross": "10000000000000000000", "liquidity_net": "10000000000000000000" },
+//!     { "index": 887270, "liquidity_gross": "10000000000000000000", "liquidity_net": "-10000000000000000000" }
+//!   ]
+//! }
+//! ```
+
+use alloy::{
+    dyn_abi::{DynSolValue, JsonAbiExt},
+    json_abi::Function,
+};
+use alloy_primitives::{aliases::U24, hex, Address, Bytes, B256, I128, U128, U160, U256};
+use alloy_sol_types::SolCall;
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{fs, path::Path, str::FromStr};
+use uniswap_sdk_core::prelude::*;
+use uniswap_v3_sdk::prelude::{Error, *};
+
+type Result<T, E = Box<dyn std::error::Error>> = core::result::Result<T, E>;
+
+/// The signatures of the router and position manager functions recognized by `decode`.
+const SIGNATURES: &[&str] = &[
+    IMulticall::multicallCall::SIGNATURE,
+    "multicall(uint256,bytes[])",
+    "multicall(bytes32,bytes[])",
+    IV3SwapRouter::exactInputSingleCall::SIGNATURE,
+    IV3SwapRouter::exactInputCall::SIGNATURE,
+    IV3SwapRouter::exactOutputSingleCall::SIGNATURE,
+    IV3SwapRouter::exactOutputCall::SIGNATURE,
+    ISwapRouter::exactInputSingleCall::SIGNATURE,
+    ISwapRouter::exactInputCall::SIGNATURE,
+    ISwapRouter::exactOutputSingleCall::SIGNATURE,
+    ISwapRouter::exactOutputCall::SIGNATURE,
+    INonfungiblePositionManager::createAndInitializePoolIfNecessaryCall::SIGNATURE,
+    INonfungiblePositionManager::mintCall::SIGNATURE,
+    INonfungiblePositionManager::increaseLiquidityCall::SIGNATURE,
+    INonfungiblePositionManager::decreaseLiquidityCall::SIGNATURE,
+    INonfungiblePositionManager::collectCall::SIGNATURE,
+    INonfungiblePositionManager::burnCall::SIGNATURE,
+    IPeripheryPaymentsWithFee::unwrapWETH9Call::SIGNATURE,
+    IPeripheryPaymentsWithFee::refundETHCall::SIGNATURE,
+    IPeripheryPaymentsWithFee::sweepTokenCall::SIGNATURE,
+    IPeripheryPaymentsWithFee::unwrapWETH9WithFeeCall::SIGNATURE,
+    IPeripheryPaymentsWithFee::sweepTokenWithFeeCall::SIGNATURE,
+    IPeripheryPaymentsExtended::unwrapWETH9Call::SIGNATURE,
+    IPeripheryPaymentsExtended::wrapETHCall::SIGNATURE,
+    IPeripheryPaymentsExtended::sweepTokenCall::SIGNATURE,
+    IPeripheryPaymentsExtended::pullCall::SIGNATURE,
+    ISelfPermit::selfPermitCall::SIGNATURE,
+    ISelfPermit::selfPermitAllowedCall::SIGNATURE,
+];
+
+#[derive(Debug, Deserialize)]
+struct TokenSnapshot {
+    address: Address,
+    decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickSnapshot {
+    index: i32,
+    liquidity_gross: U128,
+    liquidity_net: I128,
+}
+
+/// The state of a pool as read from a snapshot file
+#[derive(Debug, Deserialize)]
+struct PoolSnapshot {
+    #[serde(default = "mainnet")]
+    chain_id: u64,
+    token0: TokenSnapshot,
+    token1: TokenSnapshot,
+    fee: u32,
+    /// The tick spacing of a fee tier which is not enabled on the factory by default
+    tick_spacing: Option<i32>,
+    sqrt_price_x96: U160,
+    liquidity: U128,
+    ticks: Vec<TickSnapshot>,
+}
+
+const fn mainnet() -> u64 {
+    1
+}
+
+impl PoolSnapshot {
+    fn read(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn into_pool(self) -> Result<Pool<TickListDataProvider>> {
+        let fee = match self.tick_spacing {
+            Some(tick_spacing) => FeeAmount::new_custom(self.fee, tick_spacing),
+            None => get_fee_amount(self.chain_id, U24::from(self.fee))?,
+        };
+        let token = |token: &TokenSnapshot| {
+            Token::new(
+                self.chain_id,
+                token.address,
+                token.decimals,
+                None,
+                None,
+                0,
+                0,
+            )
+        };
+        let ticks = self
+            .ticks
+            .iter()
+            .map(|tick| {
+                Ok(Tick::new(
+                    tick.index,
+                    tick.liquidity_gross.to(),
+                    tick.liquidity_net.try_into()?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Pool::new_with_tick_data_provider(
+            token(&self.token0),
+            token(&self.token1),
+            fee,
+            self.sqrt_price_x96,
+            self.liquidity.to(),
+            TickListDataProvider::new(ticks, fee.tick_spacing().as_i32()),
+        )?)
+    }
+}
+
+fn arg(name: &'static str, help: &'static str) -> Arg {
+    Arg::new(name).long(name).help(help)
+}
+
+fn cli() -> Command {
+    let snapshot = arg("snapshot", "The pool snapshot file").required(true);
+    let slippage = arg("slippage-bips", "The slippage tolerance in bips").default_value("50");
+    let deadline = arg("deadline", "The deadline in epoch seconds").required(true);
+    let decimals = [
+        arg("decimals0", "The decimals of token0").default_value("18"),
+        arg("decimals1", "The decimals of token1").default_value("18"),
+    ];
+    Command::new("uniswap-v3")
+        .about("Common Uniswap V3 SDK operations with JSON output")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("pool-address")
+                .about("Computes the address of a pool")
+                .arg(arg("token-a", "One of the tokens of the pool").required(true))
+                .arg(arg("token-b", "The other token of the pool").required(true))
+                .arg(arg("fee", "The fee in hundredths of bips").required(true))
+                .arg(arg("chain-id", "The chain id").default_value("1"))
+                .arg(arg(
+                    "factory",
+                    "Overrides the factory of the deployment on the chain",
+                ))
+                .arg(arg(
+                    "init-code-hash",
+                    "Overrides the pool init code hash of the deployment on the chain",
+                )),
+        )
+        .subcommand(
+            Command::new("convert")
+                .about("Converts between tick, price of token0 in token1 and sqrtPriceX96")
+                .arg(arg("tick", "The tick"))
+                .arg(arg("price", "The decimal price of token0 in token1"))
+                .arg(arg("sqrt-price-x96", "The sqrt price as a Q64.96"))
+                .group(
+                    clap::ArgGroup::new("input")
+                        .args(["tick", "price", "sqrt-price-x96"])
+                        .required(true),
+                )
+                .args(decimals),
+        )
+        .subcommand(
+            Command::new("path")
+                .about("Encodes or decodes a swap path")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("encode")
+                        .arg(
+                            arg("tokens", "The comma separated tokens in swap order")
+                                .required(true)
+                                .value_delimiter(','),
+                        )
+                        .arg(
+                            arg("fees", "The comma separated fees of the hops")
+                                .required(true)
+                                .value_delimiter(','),
+                        )
+                        .arg(
+                            arg(
+                                "exact-output",
+                                "Encodes the path in reverse for exact output",
+                            )
+                            .action(ArgAction::SetTrue),
+                        ),
+                )
+                .subcommand(
+                    Command::new("decode").arg(arg("path", "The hex encoded path").required(true)),
+                ),
+        )
+        .subcommand(
+            Command::new("decode")
+                .about("Decodes swap router and position manager calldata")
+                .arg(arg("calldata", "The hex encoded calldata").required(true)),
+        )
+        .subcommand(with_swap_args(
+            Command::new("quote")
+                .about("Quotes a swap against a pool snapshot")
+                .arg(snapshot.clone()),
+        ))
+        .subcommand(with_position_args(
+            Command::new("position")
+                .about("Computes the amounts of a position against a pool snapshot")
+                .arg(snapshot.clone()),
+        ))
+        .subcommand(
+            Command::new("encode")
+                .about("Encodes swap router and position manager calldata against a pool snapshot")
+                .subcommand_required(true)
+                .subcommand(with_swap_args(
+                    Command::new("swap")
+                        .about("Encodes a swap through the pool on SwapRouter02")
+                        .arg(snapshot.clone())
+                        .arg(arg("recipient", "The account receiving the output").required(true))
+                        .arg(slippage.clone()),
+                ))
+                .subcommand(with_position_args(
+                    Command::new("add")
+                        .about("Encodes minting a position, or increasing it with --token-id")
+                        .arg(snapshot.clone())
+                        .arg(arg(
+                            "recipient",
+                            "The account receiving the minted position",
+                        ))
+                        .arg(arg("token-id", "The id of the position to increase"))
+                        .group(
+                            clap::ArgGroup::new("target")
+                                .args(["recipient", "token-id"])
+                                .required(true),
+                        )
+                        .arg(
+                            arg(
+                                "create-pool",
+                                "Creates and initializes the pool if necessary",
+                            )
+                            .action(ArgAction::SetTrue)
+                            .conflicts_with("token-id"),
+                        )
+                        .arg(slippage.clone())
+                        .arg(deadline.clone()),
+                ))
+                .subcommand(with_position_args(
+                    Command::new("remove")
+                        .about("Encodes exiting a position and collecting its tokens")
+                        .arg(snapshot)
+                        .arg(arg("token-id", "The id of the position").required(true))
+                        .arg(arg("recipient", "The account receiving the tokens").required(true))
+                        .arg(
+                            arg("percentage-bips", "The percentage of the liquidity to exit")
+                                .default_value("10000"),
+                        )
+                        .arg(
+                            arg(
+                                "burn",
+                                "Burns the position when exiting all of its liquidity",
+                            )
+                            .action(ArgAction::SetTrue),
+                        )
+                        .arg(slippage)
+                        .arg(deadline),
+                )),
+        )
+}
+
+/// Adds the arguments of a swap against a pool snapshot.
+fn with_swap_args(command: Command) -> Command {
+    command
+        .arg(arg("token-in", "The input token").required(true))
+        .arg(arg(
+            "amount-in",
+            "The raw input amount of an exact input swap",
+        ))
+        .arg(arg(
+            "amount-out",
+            "The raw output amount of an exact output swap",
+        ))
+        .group(
+            clap::ArgGroup::new("amount")
+                .args(["amount-in", "amount-out"])
+                .required(true),
+        )
+}
+
+/// Adds the arguments of a position against a pool snapshot.
+fn with_position_args(command: Command) -> Command {
+    command
+        .arg(
+            arg("tick-lower", "The lower tick")
+                .required(true)
+                .allow_negative_numbers(true),
+        )
+        .arg(
+            arg("tick-upper", "The upper tick")
+                .required(true)
+                .allow_negative_numbers(true),
+        )
+        .arg(arg("liquidity", "The liquidity of the position"))
+        .arg(arg("amount0", "The raw amount of token0 to deposit").requires("amount1"))
+        .arg(arg("amount1", "The raw amount of token1 to deposit").requires("amount0"))
+        .group(
+            clap::ArgGroup::new("size")
+                .args(["liquidity", "amount0"])
+                .required(true),
+        )
+}
+
+/// Parses the value of an argument, if given.
+fn parse<T>(matches: &ArgMatches, name: &str) -> Result<Option<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    matches
+        .get_one::<String>(name)
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|e| format!("invalid --{name} {value}: {e}").into())
+        })
+        .transpose()
+}
+
+/// Parses the value of a required argument.
+fn required<T>(matches: &ArgMatches, name: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    parse(matches, name)?.ok_or_else(|| format!("missing --{name}").into())
+}
+
+/// Parses the values of a comma separated argument.
+fn parse_list<T>(matches: &ArgMatches, name: &str) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    matches
+        .get_many::<String>(name)
+        .unwrap_or_default()
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|e| format!("invalid --{name} {value}: {e}").into())
+        })
+        .collect()
+}
+
+fn placeholder_token(chain_id: u64, address: Address, decimals: u8) -> Token {
+    Token::new(chain_id, address, decimals, None, None, 0, 0)
+}
+
+fn amount_json(amount: &CurrencyAmount<Token>) -> Value {
+    json!({
+        "token": amount.currency.address(),
+        "raw": amount.quotient().to_string(),
+        "amount": amount.to_exact(),
+    })
+}
+
+fn pool_address(matches: &ArgMatches) -> Result<Value> {
+    let token_a: Address = required(matches, "token-a")?;
+    let token_b: Address = required(matches, "token-b")?;
+    if token_a == token_b {
+        return Err("the tokens of a pool must differ".into());
+    }
+    let fee = U24::from(required::<u32>(matches, "fee")?);
+    let chain_id = required(matches, "chain-id")?;
+    let deployment = get_deployment(chain_id);
+    let (factory, fee) = match parse(matches, "factory")? {
+        // the fee tiers of a factory other than the deployment's are unknown, and only the fee
+        // determines the pool address
+        Some(factory) => (factory, FeeAmount::new_custom(fee.to(), 0)),
+        None => (
+            deployment
+                .as_ref()
+                .ok_or_else(|| format!("no deployment on chain {chain_id}, pass --factory"))?
+                .factory,
+            get_fee_amount(chain_id, fee)?,
+        ),
+    };
+    let init_code_hash: B256 = match parse(matches, "init-code-hash")? {
+        Some(init_code_hash) => init_code_hash,
+        None => deployment.map_or(POOL_INIT_CODE_HASH, |deployment| {
+            deployment.pool_init_code_hash
+        }),
+    };
+    let (token0, token1) = if token_a < token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+    Ok(json!({
+        "address": compute_pool_address(
+            factory,
+            token_a,
+            token_b,
+            fee,
+            Some(init_code_hash),
+            Some(chain_id),
+        ),
+        "token0": token0,
+        "token1": token1,
+        "fee": Into::<U24>::into(fee).to::<u32>(),
+        "factory": factory,
+    }))
+}
+
+/// Parses a decimal price of token0 in token1 into a raw price.
+fn parse_price(price: &str, token0: &Token, token1: &Token) -> Result<Price<Token, Token>> {
+    let (integer, fraction) = price.split_once('.').unwrap_or((price, ""));
+    let digits = U256::from_str_radix(&format!("{integer}{fraction}"), 10)
+        .map_err(|e| format!("invalid --price {price}: {e}"))?;
+    if digits.is_zero() {
+        return Err("the price must be positive".into());
+    }
+    let ten = U256::from(10);
+    Ok(Price::new(
+        token0.clone(),
+        token1.clone(),
+        (ten.pow(U256::from(fraction.len())) * ten.pow(U256::from(token0.decimals))).to_big_int(),
+        (digits * ten.pow(U256::from(token1.decimals))).to_big_int(),
+    ))
+}
+
+fn convert(matches: &ArgMatches) -> Result<Value> {
+    let token0 = placeholder_token(
+        1,
+        Address::with_last_byte(1),
+        required(matches, "decimals0")?,
+    );
+    let token1 = placeholder_token(
+        1,
+        Address::with_last_byte(2),
+        required(matches, "decimals1")?,
+    );
+    let (tick, sqrt_price_x96) = if let Some(tick) = parse::<i32>(matches, "tick")? {
+        (tick, get_sqrt_ratio_at_tick(tick.to_i24())?)
+    } else if let Some(sqrt_price_x96) = parse::<U160>(matches, "sqrt-price-x96")? {
+        (
+            get_tick_at_sqrt_ratio(sqrt_price_x96)?.as_i32(),
+            sqrt_price_x96,
+        )
+    } else {
+        let price = parse_price(&required::<String>(matches, "price")?, &token0, &token1)?;
+        let tick = price_to_closest_tick(&price)?;
+        (tick.as_i32(), get_sqrt_ratio_at_tick(tick)?)
+    };
+    let sqrt_price_x96_squared = U256::from(sqrt_price_x96).to_big_int().pow(2);
+    let price = Price::new(token0, token1, Q192.to_big_int(), sqrt_price_x96_squared);
+    Ok(json!({
+        "tick": tick,
+        "sqrt_price_x96": sqrt_price_x96.to_string(),
+        "price": price.to_significant(18, None)?,
+        "inverse_price": price.invert().to_significant(18, None)?,
+    }))
+}
+
+fn path(matches: &ArgMatches) -> Result<Value> {
+    match matches.subcommand() {
+        Some(("encode", matches)) => {
+            let tokens = parse_list::<Address>(matches, "tokens")?
+                .into_iter()
+                .map(|address| placeholder_token(1, address, 18))
+                .collect::<Vec<_>>();
+            let fees = parse_list::<u32>(matches, "fees")?;
+            if tokens.len() < 2 || fees.len() != tokens.len() - 1 {
+                return Err("a path needs one fee between every two tokens".into());
+            }
+            let pools = tokens
+                .windows(2)
+                .zip(fees)
+                .map(|(pair, fee)| {
+                    Pool::new(
+                        pair[0].clone(),
+                        pair[1].clone(),
+                        // only the fee is encoded in the path
+                        FeeAmount::new_custom(fee, 0),
+                        U160::from(Q96),
+                        0,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let route = Route::new(pools, tokens[0].clone(), tokens[tokens.len() - 1].clone());
+            let path = encode_route_to_path(&route, matches.get_flag("exact-output"));
+            Ok(json!({ "path": path }))
+        }
+        Some(("decode", matches)) => {
+            let (tokens, fees) = decode_path(&required::<Bytes>(matches, "path")?)?;
+            let fees = fees.iter().map(U24::to::<u32>).collect::<Vec<_>>();
+            Ok(json!({ "tokens": tokens, "fees": fees }))
+        }
+        _ => unreachable!("subcommand_required"),
+    }
+}
+
+/// Converts a decoded ABI value to JSON, with integers as decimal strings.
+fn abi_value_json(value: &DynSolValue) -> Value {
+    match value {
+        DynSolValue::Bool(value) => json!(value),
+        DynSolValue::Int(value, _) => json!(value.to_string()),
+        DynSolValue::Uint(value, _) => json!(value.to_string()),
+        DynSolValue::Address(value) => json!(value),
+        DynSolValue::FixedBytes(value, size) => json!(hex::encode_prefixed(&value[..*size])),
+        DynSolValue::Bytes(value) => json!(hex::encode_prefixed(value)),
+        DynSolValue::String(value) => json!(value),
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values) => Value::Array(values.iter().map(abi_value_json).collect()),
+        value => json!(format!("{value:?}")),
+    }
+}
+
+/// Decodes a call by its selector, recursing into the calls of a multicall.
+fn decode_call(calldata: &[u8]) -> Result<Value> {
+    if calldata.starts_with(&IMulticall::multicallCall::SELECTOR) {
+        let calls = decode_multicall::<Bytes, _>(calldata)?
+            .iter()
+            .map(|call| decode_call(call))
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(json!({
+            "function": IMulticall::multicallCall::SIGNATURE,
+            "args": [calls],
+        }));
+    }
+    let selector = calldata
+        .get(..4)
+        .ok_or("calldata is shorter than a selector")?;
+    for signature in SIGNATURES {
+        let function = Function::parse(signature)?;
+        if function.selector() != selector {
+            continue;
+        }
+        let inputs = function.abi_decode_input(&calldata[4..], true)?;
+        let mut args = inputs.iter().map(abi_value_json).collect::<Vec<_>>();
+        // the multicalls with a deadline or previous block hash end with the calls
+        if let (true, Some(DynSolValue::Array(calls))) =
+            (function.name == "multicall", inputs.last())
+        {
+            args.pop();
+            args.push(Value::Array(
+                calls
+                    .iter()
+                    .filter_map(DynSolValue::as_bytes)
+                    .map(decode_call)
+                    .collect::<Result<_>>()?,
+            ));
+        }
+        return Ok(json!({ "function": signature, "args": args }));
+    }
+    Err(format!("unknown selector {}", hex::encode_prefixed(selector)).into())
+}
+
+/// Reads a pool snapshot and the input token, output token and specified raw amount of a swap
+/// against it.
+fn read_swap(
+    matches: &ArgMatches,
+) -> Result<(Pool<TickListDataProvider>, Token, Token, TradeType, U256)> {
+    let pool = PoolSnapshot::read(required::<String>(matches, "snapshot")?)?.into_pool()?;
+    let token_in: Address = required(matches, "token-in")?;
+    let (token_in, token_out) = if token_in == pool.token0.address {
+        (pool.token0.clone(), pool.token1.clone())
+    } else if token_in == pool.token1.address {
+        (pool.token1.clone(), pool.token0.clone())
+    } else {
+        return Err(Error::InvalidToken.into());
+    };
+    let (trade_type, amount) = match parse::<U256>(matches, "amount-in")? {
+        Some(amount_in) => (TradeType::ExactInput, amount_in),
+        None => (TradeType::ExactOutput, required(matches, "amount-out")?),
+    };
+    Ok((pool, token_in, token_out, trade_type, amount))
+}
+
+fn quote(matches: &ArgMatches) -> Result<Value> {
+    let (pool, token_in, token_out, trade_type, amount) = read_swap(matches)?;
+    let (amount_in, amount_out) = if trade_type == TradeType::ExactInput {
+        let amount_in = CurrencyAmount::from_raw_amount(token_in, amount.to_big_int())?;
+        let amount_out = pool.get_output_amount(&amount_in, None)?;
+        (amount_in, amount_out)
+    } else {
+        let amount_out = CurrencyAmount::from_raw_amount(token_out, amount.to_big_int())?;
+        let amount_in = pool.get_input_amount(&amount_out, None)?;
+        (amount_in, amount_out)
+    };
+    Ok(json!({
+        "amount_in": amount_json(&amount_in),
+        "amount_out": amount_json(&amount_out),
+    }))
+}
+
+/// Reads a pool snapshot and a position in it, sized by liquidity or by amounts.
+fn read_position(matches: &ArgMatches) -> Result<Position<TickListDataProvider>> {
+    let pool = PoolSnapshot::read(required::<String>(matches, "snapshot")?)?.into_pool()?;
+    let tick_lower: i32 = required(matches, "tick-lower")?;
+    let tick_upper: i32 = required(matches, "tick-upper")?;
+    let tick_spacing = pool.tick_spacing();
+    if tick_lower >= tick_upper || tick_lower % tick_spacing != 0 || tick_upper % tick_spacing != 0
+    {
+        return Err(format!("invalid tick range with tick spacing {tick_spacing}").into());
+    }
+    Ok(
+        if let Some(liquidity) = parse::<u128>(matches, "liquidity")? {
+            Position::new(pool, liquidity, tick_lower, tick_upper)
+        } else {
+            Position::from_amounts(
+                pool,
+                tick_lower,
+                tick_upper,
+                required(matches, "amount0")?,
+                required(matches, "amount1")?,
+                true,
+            )?
+        },
+    )
+}
+
+fn position(matches: &ArgMatches) -> Result<Value> {
+    let position = read_position(matches)?;
+    let MintAmounts { amount0, amount1 } = position.mint_amounts()?;
+    Ok(json!({
+        "liquidity": position.liquidity.to_string(),
+        "amount0": amount_json(&position.amount0()?),
+        "amount1": amount_json(&position.amount1()?),
+        "mint_amount0": amount0.to_string(),
+        "mint_amount1": amount1.to_string(),
+    }))
+}
+
+/// Parses a percentage in bips.
+fn parse_bips(matches: &ArgMatches, name: &str) -> Result<Percent> {
+    Ok(Percent::new(required::<u32>(matches, name)?, 10_000))
+}
+
+/// Formats method parameters, with the periphery contract of the deployment on the chain they are
+/// encoded for, if any.
+fn method_parameters_json(parameters: &MethodParameters, chain_id: u64) -> Value {
+    json!({
+        "to": parameters.call_target(chain_id).ok(),
+        "calldata": parameters.calldata,
+        "value": parameters.value.to_string(),
+    })
+}
+
+fn encode(matches: &ArgMatches) -> Result<Value> {
+    match matches.subcommand() {
+        Some(("swap", matches)) => {
+            let (pool, token_in, token_out, trade_type, amount) = read_swap(matches)?;
+            let chain_id = pool.chain_id();
+            let token = if trade_type == TradeType::ExactInput {
+                token_in.clone()
+            } else {
+                token_out.clone()
+            };
+            let amount = CurrencyAmount::from_raw_amount(token, amount.to_big_int())?;
+            let route = Route::new(vec![pool], token_in, token_out);
+            let trade = Trade::from_route(route, amount, trade_type)?;
+            let parameters = swap_call_parameters(
+                &mut [trade],
+                SwapOptions {
+                    slippage_tolerance: parse_bips(matches, "slippage-bips")?,
+                    recipient: required(matches, "recipient")?,
+                    ..Default::default()
+                },
+            )?;
+            Ok(method_parameters_json(&parameters, chain_id))
+        }
+        Some(("add", matches)) => {
+            let mut position = read_position(matches)?;
+            let chain_id = position.pool.chain_id();
+            let specific_opts = match parse(matches, "token-id")? {
+                Some(token_id) => {
+                    AddLiquiditySpecificOptions::Increase(IncreaseSpecificOptions { token_id })
+                }
+                None => AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+                    recipient: required(matches, "recipient")?,
+                    create_pool: matches.get_flag("create-pool"),
+                }),
+            };
+            let parameters = add_call_parameters(
+                &mut position,
+                AddLiquidityOptions {
+                    slippage_tolerance: parse_bips(matches, "slippage-bips")?,
+                    deadline: required(matches, "deadline")?,
+                    use_native: None,
+                    token0_permit: None,
+                    token1_permit: None,
+                    specific_opts,
+                },
+            )?;
+            Ok(method_parameters_json(&parameters, chain_id))
+        }
+        Some(("remove", matches)) => {
+            let position = read_position(matches)?;
+            let chain_id = position.pool.chain_id();
+            let token_id = required(matches, "token-id")?;
+            // the collect call takes all tokens owed, so the expected fees only matter for ether
+            let collect_options = CollectOptions {
+                token_id,
+                expected_currency_owed0: CurrencyAmount::from_raw_amount(
+                    position.pool.token0.clone(),
+                    0,
+                )?,
+                expected_currency_owed1: CurrencyAmount::from_raw_amount(
+                    position.pool.token1.clone(),
+                    0,
+                )?,
+                recipient: required(matches, "recipient")?,
+            };
+            let parameters = remove_call_parameters(
+                &position,
+                RemoveLiquidityOptions {
+                    token_id,
+                    liquidity_percentage: parse_bips(matches, "percentage-bips")?,
+                    slippage_tolerance: parse_bips(matches, "slippage-bips")?,
+                    deadline: required(matches, "deadline")?,
+                    burn_token: matches.get_flag("burn"),
+                    permit: None,
+                    collect_options,
+                },
+            )?;
+            Ok(method_parameters_json(&parameters, chain_id))
+        }
+        _ => unreachable!("subcommand_required"),
+    }
+}
+
+fn run(matches: &ArgMatches) -> Result<Value> {
+    match matches.subcommand() {
+        Some(("pool-address", matches)) => pool_address(matches),
+        Some(("convert", matches)) => convert(matches),
+        Some(("path", matches)) => path(matches),
+        Some(("decode", matches)) => decode_call(&required::<Bytes>(matches, "calldata")?),
+        Some(("quote", matches)) => quote(matches),
+        Some(("position", matches)) => position(matches),
+        Some(("encode", matches)) => encode(matches),
+        _ => unreachable!("subcommand_required"),
+    }
+}
+
+fn main() {
+    match run(&cli().get_matches()) {
+        Ok(value) => println!("{value:#}"),
+        Err(e) => {
+            eprintln!("{}", json!({ "error": e.to_string() }));
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    const USDC: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+    fn matches(args: &[&str]) -> ArgMatches {
+        cli().get_matches_from(core::iter::once("uniswap-v3").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_path_round_trip() {
+        let tokens = format!("{USDC},{WETH}");
+        let encoded = run(&matches(&[
+            "path", "encode", "--tokens", &tokens, "--fees", "500",
+        ]))
+        .unwrap();
+        let decoded = run(&matches(&[
+            "path",
+            "decode",
+            "--path",
+            encoded["path"].as_str().unwrap(),
+        ]))
+        .unwrap();
+        assert_eq!(decoded, json!({ "tokens": [USDC, WETH], "fees": [500] }));
+        assert!(decode_path(&[0; 44]).is_err());
+    }
+
+    #[test]
+    fn test_convert_price() {
+        let converted = run(&matches(&[
+            "convert",
+            "--price",
+            "1.00015",
+            "--decimals0",
+            "6",
+            "--decimals1",
+            "6",
+        ]))
+        .unwrap();
+        assert_eq!(converted["tick"], 1);
+    }
+
+    #[test]
+    fn test_decode_multicall() {
+        let calldata = encode_multicall(vec![
+            IV3SwapRouter::exactInputSingleCall {
+                params: IV3SwapRouter::ExactInputSingleParams {
+                    tokenIn: USDC,
+                    tokenOut: WETH,
+                    fee: U24::from(500),
+                    amountIn: U256::from(1000),
+                    ..Default::default()
+                },
+            }
+            .abi_encode()
+            .into(),
+            encode_refund_eth(),
+        ]);
+        let decoded = decode_call(&calldata).unwrap();
+        assert_eq!(decoded["function"], "multicall(bytes[])");
+        let calls = &decoded["args"][0];
+        assert_eq!(
+            calls[0]["function"],
+            IV3SwapRouter::exactInputSingleCall::SIGNATURE
+        );
+        assert_eq!(calls[0]["args"][0][2], "500");
+        assert_eq!(calls[0]["args"][0][4], "1000");
+        assert_eq!(calls[1], json!({ "function": "refundETH()", "args": [] }));
+    }
+
+    /// Writes a full range USDC/WETH 0.05% pool snapshot to a temporary file named after the test.
+    fn snapshot(test: &str) -> String {
+        let path = std::env::temp_dir().join(format!("{test}-{}.json", std::process::id()));
+        let snapshot = json!({
+            "token0": { "address": USDC, "decimals": 6 },
+            "token1": { "address": WETH, "decimals": 18 },
+            "fee": 500,
+            "sqrt_price_x96": "1350174849792634181862360983626536",
+            "liquidity": "10000000000000000000",
+            "ticks": [
+                { "index": -887270, "liquidity_gross": "10000000000000000000", "liquidity_net": "10000000000000000000" },
+                { "index": 887270, "liquidity_gross": "10000000000000000000", "liquidity_net": "-10000000000000000000" }
+            ]
+        });
+        fs::write(&path, snapshot.to_string()).unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_encode_swap() {
+        let snapshot = snapshot("test_encode_swap");
+        let recipient = Address::with_last_byte(1).to_string();
+        let encoded = run(&matches(&[
+            "encode",
+            "swap",
+            "--snapshot",
+            &snapshot,
+            "--token-in",
+            &USDC.to_string(),
+            "--amount-in",
+            "1000000",
+            "--recipient",
+            &recipient,
+        ]))
+        .unwrap();
+        assert_eq!(encoded["to"], json!(SWAP_ROUTER_02_ADDRESSES[&1]));
+        assert_eq!(encoded["value"], "0");
+        let decoded =
+            decode_call(&Bytes::from_str(encoded["calldata"].as_str().unwrap()).unwrap()).unwrap();
+        assert_eq!(
+            decoded["function"],
+            IV3SwapRouter::exactInputSingleCall::SIGNATURE
+        );
+        assert_eq!(decoded["args"][0][0], json!(USDC));
+        assert_eq!(decoded["args"][0][4], "1000000");
+    }
+
+    #[test]
+    fn test_encode_add_and_remove() {
+        let snapshot = snapshot("test_encode_add_and_remove");
+        let recipient = Address::with_last_byte(1).to_string();
+        let position = [
+            "--snapshot",
+            &snapshot,
+            "--tick-lower",
+            "-887270",
+            "--tick-upper",
+            "887270",
+            "--liquidity",
+            "1000000000000",
+            "--deadline",
+            "123",
+        ];
+        let encoded = run(&matches(
+            &[&["encode", "add", "--recipient", &recipient], &position[..]].concat(),
+        ))
+        .unwrap();
+        let decoded =
+            decode_call(&Bytes::from_str(encoded["calldata"].as_str().unwrap()).unwrap()).unwrap();
+        assert_eq!(
+            decoded["function"],
+            INonfungiblePositionManager::mintCall::SIGNATURE
+        );
+
+        let encoded = run(&matches(
+            &[
+                &[
+                    "encode",
+                    "remove",
+                    "--token-id",
+                    "1",
+                    "--recipient",
+                    &recipient,
+                    "--burn",
+                ],
+                &position[..],
+            ]
+            .concat(),
+        ))
+        .unwrap();
+        assert_eq!(
+            encoded["to"],
+            json!(NONFUNGIBLE_POSITION_MANAGER_ADDRESSES[&1])
+        );
+        let decoded =
+            decode_call(&Bytes::from_str(encoded["calldata"].as_str().unwrap()).unwrap()).unwrap();
+        let functions = decoded["args"][0]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|call| call["function"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            functions,
+            [
+                INonfungiblePositionManager::decreaseLiquidityCall::SIGNATURE,
+                INonfungiblePositionManager::collectCall::SIGNATURE,
+                INonfungiblePositionManager::burnCall::SIGNATURE,
+            ]
+        );
+    }
+}
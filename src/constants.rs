@@ -1,3 +1,4 @@
+use crate::{error::Error, factory::find_registered_fee_amount};
 use alloy_primitives::{
     address,
     aliases::{I24, U24},
@@ -9,6 +10,16 @@ pub const FACTORY_ADDRESS: Address = address!("1F98431c8aD98523631AE4a59f267346e
 pub const POOL_INIT_CODE_HASH: B256 =
     b256!("e34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54");
 
+/// The bytecode hash of the pool on zkSync Era, which uses a different `CREATE2` derivation.
+pub const ZKSYNC_POOL_INIT_CODE_HASH: B256 =
+    b256!("010013f177ea1fcbc4520f9a3ca7cd2d1d77959e05aa66484027cb38e712aeed");
+
+/// Used by `SwapRouter02` as a recipient to indicate `msg.sender`.
+pub const MSG_SENDER: Address = address!("0000000000000000000000000000000000000001");
+
+/// Used by `SwapRouter02` as a recipient to indicate the router itself.
+pub const ADDRESS_THIS: Address = address!("0000000000000000000000000000000000000002");
+
 /// The default factory enabled fee amounts, denominated in hundredths of bips.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u32)]
@@ -47,19 +58,13 @@ impl FeeAmount {
     }
 }
 
-impl From<u32> for FeeAmount {
+/// Resolves a fee like the conversion from [`U24`], failing for fees which do not fit in 24 bits.
+impl TryFrom<u32> for FeeAmount {
+    type Error = Error;
+
     #[inline]
-    fn from(fee: u32) -> Self {
-        match fee {
-            100 => Self::LOWEST,
-            200 => Self::LOW_200,
-            300 => Self::LOW_300,
-            400 => Self::LOW_400,
-            500 => Self::LOW,
-            3000 => Self::MEDIUM,
-            10000 => Self::HIGH,
-            fee => Self::CUSTOM { fee, tick_spacing: (fee / 50) as i32 },
-        }
+    fn try_from(fee: u32) -> Result<Self, Error> {
+        Self::try_from(U24::saturating_from(fee))
     }
 }
 
@@ -74,7 +79,10 @@ impl From<i32> for FeeAmount {
             10 => Self::LOW,
             60 => Self::MEDIUM,
             200 => Self::HIGH,
-            tick_spacing => Self::CUSTOM { fee: (tick_spacing * 50) as u32, tick_spacing },
+            tick_spacing => Self::CUSTOM {
+                fee: (tick_spacing * 50) as u32,
+                tick_spacing,
+            },
         }
     }
 }
@@ -95,19 +103,25 @@ impl From<FeeAmount> for U24 {
     }
 }
 
-impl From<U24> for FeeAmount {
+/// Resolves a fee to one of the fee tiers with a known tick spacing, or else to a fee tier
+/// registered with [`register_fee_tier`](crate::factory::register_fee_tier) with the same tick
+/// spacing on every chain, failing with [`Error::UnknownFeeTier`] otherwise. Use
+/// [`get_fee_amount`](crate::factory::get_fee_amount) to resolve the fee tier enabled on the
+/// factory of a chain instead.
+impl TryFrom<U24> for FeeAmount {
+    type Error = Error;
+
     #[inline]
-    fn from(fee: U24) -> Self {
-        let fee_value = fee.into_limbs()[0] as u32;
-        match fee_value {
-            100 => Self::LOWEST,
-            200 => Self::LOW_200,
-            300 => Self::LOW_300,
-            400 => Self::LOW_400,
-            500 => Self::LOW,
-            3000 => Self::MEDIUM,
-            10000 => Self::HIGH,
-            fee => Self::CUSTOM { fee, tick_spacing: (fee / 50) as i32 },
+    fn try_from(fee: U24) -> Result<Self, Error> {
+        match fee.to::<u32>() {
+            100 => Ok(Self::LOWEST),
+            200 => Ok(Self::LOW_200),
+            300 => Ok(Self::LOW_300),
+            400 => Ok(Self::LOW_400),
+            500 => Ok(Self::LOW),
+            3000 => Ok(Self::MEDIUM),
+            10000 => Ok(Self::HIGH),
+            _ => find_registered_fee_amount(fee).ok_or(Error::UnknownFeeTier(fee)),
         }
     }
 }
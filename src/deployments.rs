@@ -0,0 +1,214 @@
+use crate::{
+    constants::{FeeAmount, POOL_INIT_CODE_HASH, ZKSYNC_POOL_INIT_CODE_HASH},
+    error::Error,
+};
+use alloc::borrow::Cow;
+use alloy_primitives::{address, Address, ChainId, B256};
+use uniswap_sdk_core::prelude::{
+    ChainId as Chain, CHAIN_TO_ADDRESSES_MAP, SWAP_ROUTER_02_ADDRESSES,
+};
+
+/// The `UniswapV3Staker` address on the chains it is deployed to.
+const STAKER_ADDRESS: Address = address!("e34139463bA50bD61336E0c446Bd8C0867c6fE65");
+
+/// The fee tiers enabled on the factory of the built-in deployments, 0.05%, 0.3% and 1% by the
+/// factory constructor and 0.01% by governance.
+const UNISWAP_FEE_TIERS: [FeeAmount; 4] = [
+    FeeAmount::LOWEST,
+    FeeAmount::LOW,
+    FeeAmount::MEDIUM,
+    FeeAmount::HIGH,
+];
+
+/// The core and periphery contracts of a Uniswap V3 deployment on a chain.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Deployment {
+    /// The `UniswapV3Factory` address, used as the deployer of pools
+    pub factory: Address,
+    /// The hash of the pool init code, used to compute pool addresses
+    pub pool_init_code_hash: B256,
+    /// The fee tiers enabled on the factory with their tick spacings, borrowed for the built-in
+    /// deployments and owned for deployments registered at runtime
+    pub fee_tiers: Cow<'static, [FeeAmount]>,
+    /// The `NonfungiblePositionManager` address
+    pub nonfungible_position_manager: Address,
+    /// The `SwapRouter02` address, if deployed
+    pub swap_router02: Option<Address>,
+    /// The v1 `Quoter` address
+    pub quoter: Address,
+    /// The `QuoterV2` address
+    pub quoter_v2: Address,
+    /// The `UniswapV3Staker` address, if deployed
+    pub staker: Option<Address>,
+    /// The `TickLens` address, if deployed
+    pub tick_lens: Option<Address>,
+    /// The `UniswapInterfaceMulticall` address
+    pub multicall: Address,
+}
+
+/// Returns the built-in Uniswap V3 deployment on a chain, if any.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+#[inline]
+#[must_use]
+pub fn default_deployment(chain_id: ChainId) -> Option<Deployment> {
+    let addresses = CHAIN_TO_ADDRESSES_MAP.get(&chain_id)?;
+    let staker = [
+        Chain::MAINNET,
+        Chain::OPTIMISM,
+        Chain::ARBITRUM_ONE,
+        Chain::POLYGON,
+    ]
+    .into_iter()
+    .any(|chain| chain as ChainId == chain_id)
+    .then_some(STAKER_ADDRESS);
+    Some(Deployment {
+        factory: addresses.v3_core_factory,
+        pool_init_code_hash: if chain_id == Chain::ZKSYNC as ChainId {
+            ZKSYNC_POOL_INIT_CODE_HASH
+        } else {
+            POOL_INIT_CODE_HASH
+        },
+        fee_tiers: Cow::Borrowed(&UNISWAP_FEE_TIERS),
+        nonfungible_position_manager: addresses.nonfungible_position_manager,
+        swap_router02: SWAP_ROUTER_02_ADDRESSES.get(&chain_id).copied(),
+        quoter: addresses.quoter,
+        quoter_v2: addresses.quoter_v2,
+        staker,
+        tick_lens: addresses.tick_lens,
+        multicall: addresses.multicall,
+    })
+}
+
+#[cfg(feature = "std")]
+static REGISTRY: std::sync::RwLock<alloc::collections::BTreeMap<ChainId, Deployment>> =
+    std::sync::RwLock::new(alloc::collections::BTreeMap::new());
+
+/// Registers a deployment for a chain, taking precedence over the built-in deployment.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `deployment`: The deployment on the chain
+#[cfg(feature = "std")]
+#[inline]
+pub fn register_deployment(chain_id: ChainId, deployment: Deployment) {
+    REGISTRY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(chain_id, deployment);
+}
+
+/// Returns the Uniswap V3 deployment on a chain, preferring a registered deployment over the
+/// built-in one.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+#[inline]
+#[must_use]
+pub fn get_deployment(chain_id: ChainId) -> Option<Deployment> {
+    #[cfg(feature = "std")]
+    if let Some(deployment) = REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get(&chain_id)
+    {
+        return Some(deployment.clone());
+    }
+    default_deployment(chain_id)
+}
+
+/// Returns the address if given, or else the address of a contract of the [`Deployment`] on a
+/// chain.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `address`: The address overriding the deployment
+/// * `contract`: Selects the contract address from the deployment
+///
+/// ## Returns
+///
+/// The address, or [`Error::UnknownDeployment`] if the chain has no deployment of the contract
+#[inline]
+pub fn resolve_deployment_address(
+    chain_id: ChainId,
+    address: Option<Address>,
+    contract: impl FnOnce(&Deployment) -> Option<Address>,
+) -> Result<Address, Error> {
+    match address {
+        Some(address) => Ok(address),
+        None => get_deployment(chain_id)
+            .as_ref()
+            .and_then(contract)
+            .ok_or(Error::UnknownDeployment(chain_id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::FACTORY_ADDRESS;
+
+    #[test]
+    fn test_default_deployments() {
+        let mainnet = get_deployment(1).unwrap();
+        assert_eq!(mainnet.factory, FACTORY_ADDRESS);
+        assert_eq!(mainnet.pool_init_code_hash, POOL_INIT_CODE_HASH);
+        assert_eq!(
+            mainnet.nonfungible_position_manager,
+            address!("C36442b4a4522E871399CD717aBDD847Ab11FE88")
+        );
+        assert_eq!(mainnet.staker, Some(STAKER_ADDRESS));
+
+        let base = get_deployment(Chain::BASE as ChainId).unwrap();
+        assert_eq!(
+            base.factory,
+            address!("33128a8fC17869897dcE68Ed026d694621f6FDfD")
+        );
+        assert_eq!(base.staker, None);
+
+        let zksync = get_deployment(Chain::ZKSYNC as ChainId).unwrap();
+        assert_eq!(zksync.pool_init_code_hash, ZKSYNC_POOL_INIT_CODE_HASH);
+
+        assert_eq!(get_deployment(ChainId::MAX), None);
+    }
+
+    #[test]
+    fn test_resolve_deployment_address() {
+        let npm = address!("C36442b4a4522E871399CD717aBDD847Ab11FE88");
+        assert_eq!(
+            resolve_deployment_address(1, None, |deployment| Some(
+                deployment.nonfungible_position_manager
+            ))
+            .unwrap(),
+            npm
+        );
+        assert_eq!(
+            resolve_deployment_address(ChainId::MAX, Some(npm), |deployment| Some(
+                deployment.factory
+            ))
+            .unwrap(),
+            npm
+        );
+        assert!(matches!(
+            resolve_deployment_address(ChainId::MAX, None, |deployment| Some(deployment.factory)),
+            Err(Error::UnknownDeployment(ChainId::MAX))
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_register_deployment() {
+        const CHAIN_ID: ChainId = ChainId::MAX - 1;
+        let deployment = Deployment {
+            factory: address!("1111111111111111111111111111111111111111"),
+            ..default_deployment(1).unwrap()
+        };
+        register_deployment(CHAIN_ID, deployment.clone());
+        assert_eq!(get_deployment(CHAIN_ID), Some(deployment));
+    }
+}
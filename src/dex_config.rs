@@ -0,0 +1,246 @@
+use crate::prelude::*;
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{address, aliases::U24, b256, Address, ChainId, B256};
+
+/// The `PancakeV3Factory` address, identical on the chains PancakeSwap V3 is deployed to.
+const PANCAKESWAP_FACTORY_ADDRESS: Address = address!("0BFbCF9fa4f9C56B0F40a671Ad40E0805A091865");
+/// The `PancakeV3PoolDeployer` address, which deploys the pools with `CREATE2`.
+const PANCAKESWAP_DEPLOYER_ADDRESS: Address = address!("41ff9AA7e16B8B1a8a8dc4f0eFacd93D02d071c9");
+const PANCAKESWAP_POOL_INIT_CODE_HASH: B256 =
+    b256!("6ce8eb472fa82df5469c6ab6d485f17c3ad13c8cd7af59b3d4a8026c5ce0f7e2");
+/// The PancakeSwap `SmartRouter` address, which has the `SwapRouter02` interface.
+const PANCAKESWAP_SMART_ROUTER_ADDRESS: Address =
+    address!("13f4EA83D0bd40E75C8222255bc855a974568Dd4");
+
+/// The SushiSwap V3 factory addresses, which also deploy the pools with `CREATE2`.
+const SUSHISWAP_FACTORY_ADDRESSES: [(ChainId, Address); 2] = [
+    (1, address!("bACEB8eC6b9355Dfc0269C18bac9d6E2Bdc29C4F")),
+    (42161, address!("1af415a1EbA07a4986a52B6f2e7dE7003D82231e")),
+];
+
+/// The interface of the swap router of a DEX
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SwapRouterFlavor {
+    /// `SwapRouter02`, whose swap parameters have no deadline and which checks the deadline in
+    /// `multicall` instead
+    #[default]
+    SwapRouter02,
+    /// The original `SwapRouter` of the V3 periphery, whose swap parameters include the deadline
+    SwapRouter,
+}
+
+/// The parameters in which a Uniswap V3 fork differs from Uniswap V3.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DexConfig {
+    /// The chain the DEX is deployed on
+    pub chain_id: ChainId,
+    /// The factory address, on which pools are created and fee tiers are enabled
+    pub factory: Address,
+    /// The address that deploys the pools with `CREATE2`, which is the factory on Uniswap V3 but
+    /// a separate pool deployer on some forks
+    pub deployer: Address,
+    /// The hash of the pool init code, used to compute pool addresses
+    pub pool_init_code_hash: B256,
+    /// The enabled fee tiers with their tick spacings
+    pub fee_tiers: Vec<FeeAmount>,
+    /// The swap router address
+    pub swap_router: Address,
+    /// The interface of the swap router
+    pub swap_router_flavor: SwapRouterFlavor,
+}
+
+impl DexConfig {
+    /// Returns the configuration of Uniswap V3 on a chain, or `None` if the chain has no
+    /// registered deployment with a `SwapRouter02`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    #[inline]
+    #[must_use]
+    pub fn uniswap(chain_id: ChainId) -> Option<Self> {
+        let deployment = get_deployment(chain_id)?;
+        Some(Self {
+            chain_id,
+            factory: deployment.factory,
+            deployer: deployment.factory,
+            pool_init_code_hash: deployment.pool_init_code_hash,
+            fee_tiers: get_fee_tiers(chain_id),
+            swap_router: deployment.swap_router02?,
+            swap_router_flavor: SwapRouterFlavor::SwapRouter02,
+        })
+    }
+
+    /// Returns the configuration of PancakeSwap V3 on a chain with the canonical deployment.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    #[inline]
+    #[must_use]
+    pub fn pancakeswap(chain_id: ChainId) -> Self {
+        Self {
+            chain_id,
+            factory: PANCAKESWAP_FACTORY_ADDRESS,
+            deployer: PANCAKESWAP_DEPLOYER_ADDRESS,
+            pool_init_code_hash: PANCAKESWAP_POOL_INIT_CODE_HASH,
+            fee_tiers: vec![
+                FeeAmount::LOWEST,
+                FeeAmount::LOW,
+                FeeAmount::new_custom(2500, 50),
+                FeeAmount::HIGH,
+            ],
+            swap_router: PANCAKESWAP_SMART_ROUTER_ADDRESS,
+            swap_router_flavor: SwapRouterFlavor::SwapRouter02,
+        }
+    }
+
+    /// Returns the configuration of SushiSwap V3 on a chain, or `None` if the factory on the chain
+    /// is not known. SushiSwap V3 pools share the init code and fee tiers of Uniswap V3, and its
+    /// periphery `SwapRouter` has the original interface with the deadline in the swap
+    /// parameters.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `swap_router`: The SushiSwap V3 `SwapRouter` address on the chain
+    #[inline]
+    #[must_use]
+    pub fn sushiswap(chain_id: ChainId, swap_router: Address) -> Option<Self> {
+        let factory = SUSHISWAP_FACTORY_ADDRESSES
+            .into_iter()
+            .find_map(|(id, factory)| (id == chain_id).then_some(factory))?;
+        Some(Self {
+            chain_id,
+            factory,
+            deployer: factory,
+            pool_init_code_hash: POOL_INIT_CODE_HASH,
+            fee_tiers: vec![
+                FeeAmount::LOWEST,
+                FeeAmount::LOW,
+                FeeAmount::MEDIUM,
+                FeeAmount::HIGH,
+            ],
+            swap_router,
+            swap_router_flavor: SwapRouterFlavor::SwapRouter,
+        })
+    }
+
+    /// Resolves a fee to the enabled fee tier with its tick spacing on the DEX.
+    ///
+    /// ## Arguments
+    ///
+    /// * `fee`: The fee in hundredths of bips
+    #[inline]
+    #[must_use]
+    pub fn fee_amount(&self, fee: U24) -> Option<FeeAmount> {
+        self.fee_tiers
+            .iter()
+            .copied()
+            .find(|&fee_tier| Into::<U24>::into(fee_tier) == fee)
+    }
+
+    /// Returns whether the fee tier is enabled on the DEX with the same tick spacing.
+    ///
+    /// ## Arguments
+    ///
+    /// * `fee`: The fee tier
+    #[inline]
+    #[must_use]
+    pub fn supports_fee(&self, fee: FeeAmount) -> bool {
+        self.fee_amount(fee.into())
+            .is_some_and(|fee_tier| fee_tier.tick_spacing() == fee.tick_spacing())
+    }
+
+    /// Computes the address of a pool on the DEX.
+    ///
+    /// ## Arguments
+    ///
+    /// * `token_a`: The first token of the pair, irrespective of sort order
+    /// * `token_b`: The second token of the pair, irrespective of sort order
+    /// * `fee`: The fee tier of the pool
+    #[inline]
+    #[must_use]
+    pub fn pool_address(&self, token_a: Address, token_b: Address, fee: FeeAmount) -> Address {
+        compute_pool_address(
+            self.deployer,
+            token_a,
+            token_b,
+            fee,
+            Some(self.pool_init_code_hash),
+            Some(self.chain_id),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uniswap_sdk_core::prelude::ChainId as Chain;
+
+    #[test]
+    fn test_uniswap() {
+        let config = DexConfig::uniswap(1).unwrap();
+        assert_eq!(config.deployer, FACTORY_ADDRESS);
+        assert_eq!(
+            config.pool_address(
+                address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+                address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+                FeeAmount::LOW,
+            ),
+            address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640")
+        );
+    }
+
+    #[test]
+    fn test_pancakeswap_pool_address() {
+        let config = DexConfig::pancakeswap(Chain::BNB as ChainId);
+        assert_eq!(
+            config.pool_address(
+                address!("55d398326f99059fF775485246999027B3197955"),
+                address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c"),
+                FeeAmount::LOW,
+            ),
+            address!("36696169C63e42cd08ce11f5deeBbCeBae652050")
+        );
+    }
+
+    #[test]
+    fn test_sushiswap() {
+        let swap_router = address!("0000000000000000000000000000000000000001");
+        let config = DexConfig::sushiswap(1, swap_router).unwrap();
+        assert_eq!(config.deployer, config.factory);
+        assert_eq!(config.swap_router, swap_router);
+        assert_eq!(config.swap_router_flavor, SwapRouterFlavor::SwapRouter);
+        assert!(config.supports_fee(FeeAmount::MEDIUM));
+        assert_eq!(
+            config.pool_address(
+                address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+                address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+                FeeAmount::LOW,
+            ),
+            compute_pool_address(
+                config.factory,
+                address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"),
+                address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"),
+                FeeAmount::LOW,
+                None,
+                None,
+            )
+        );
+        assert_eq!(
+            DexConfig::sushiswap(Chain::BNB as ChainId, swap_router),
+            None
+        );
+    }
+
+    #[test]
+    fn test_fee_tier_resolution() {
+        let config = DexConfig::pancakeswap(Chain::BNB as ChainId);
+        let fee_amount = config.fee_amount(U24::from(2500)).unwrap();
+        assert_eq!(fee_amount.tick_spacing().as_i32(), 50);
+        assert!(config.supports_fee(fee_amount));
+        assert!(!config.supports_fee(FeeAmount::MEDIUM));
+        assert_eq!(config.fee_amount(U24::from(3000)), None);
+    }
+}
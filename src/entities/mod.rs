@@ -7,7 +7,7 @@ pub mod tick_list_data_provider;
 pub mod trade;
 
 pub use pool::Pool;
-pub use position::{MintAmounts, Position};
+pub use position::{MintAmounts, Position, PositionFeeState, PositionPayoff};
 pub use route::Route;
 pub use tick::{Tick, TickIndex};
 pub use tick_data_provider::*;
@@ -1,5 +1,5 @@
 use crate::prelude::{Error, *};
-use alloy_primitives::{ChainId, B256, I256, U160};
+use alloy_primitives::{aliases::I24, ChainId, B256, I256, U160};
 use uniswap_sdk_core::prelude::*;
 
 /// Represents a V3 pool
@@ -15,6 +15,11 @@ where
     pub liquidity: u128,
     pub tick_current: TP::Index,
     pub tick_data_provider: TP,
+    /// The address that deployed the pool with `CREATE2`, or `None` for the factory of the
+    /// [`Deployment`] on the chain
+    pub deployer: Option<Address>,
+    /// The hash of the pool init code, or `None` for the one of the [`Deployment`] on the chain
+    pub init_code_hash: Option<B256>,
 }
 
 impl<TP> PartialEq for Pool<TP>
@@ -74,6 +79,10 @@ impl Pool {
     /// * `factory_address_override`: Override the factory address used to compute the pool address
     ///   if necessary
     ///
+    /// The factory and init code hash which are not overridden are those of the [`Deployment`] on
+    /// the chain of the tokens, falling back to the mainnet values. The deployment registry is not
+    /// consulted if both are overridden.
+    ///
     /// ## Returns
     ///
     /// The computed pool address
@@ -99,19 +108,69 @@ impl Pool {
         init_code_hash_manual_override: Option<B256>,
         factory_address_override: Option<Address>,
     ) -> Address {
+        let chain_id = token_a.chain_id();
+        let (factory, init_code_hash) = resolve_deployer(
+            chain_id,
+            factory_address_override,
+            init_code_hash_manual_override,
+        );
         compute_pool_address(
-            factory_address_override.unwrap_or(FACTORY_ADDRESS),
+            factory,
             token_a.address(),
             token_b.address(),
             fee,
-            init_code_hash_manual_override,
-            Some(token_a.chain_id()),
+            init_code_hash,
+            Some(chain_id),
         )
     }
 }
 
+/// Returns the deployer and init code hash of a pool, taking those of the [`Deployment`] on the
+/// chain which are not overridden, falling back to the mainnet values. The deployment registry is
+/// not consulted if both are overridden.
+fn resolve_deployer(
+    chain_id: ChainId,
+    factory_address_override: Option<Address>,
+    init_code_hash_manual_override: Option<B256>,
+) -> (Address, Option<B256>) {
+    match (factory_address_override, init_code_hash_manual_override) {
+        (Some(factory), Some(init_code_hash)) => (factory, Some(init_code_hash)),
+        (factory, init_code_hash) => {
+            let deployment = get_deployment(chain_id);
+            (
+                factory
+                    .or_else(|| deployment.as_ref().map(|deployment| deployment.factory))
+                    .unwrap_or(FACTORY_ADDRESS),
+                init_code_hash.or_else(|| {
+                    deployment
+                        .as_ref()
+                        .map(|deployment| deployment.pool_init_code_hash)
+                }),
+            )
+        }
+    }
+}
+
+impl<TP: TickDataProvider> From<&Pool<TP>> for SwapErrorPool {
+    #[inline]
+    fn from(pool: &Pool<TP>) -> Self {
+        let chain_id = pool.chain_id();
+        let (deployer, init_code_hash) =
+            resolve_deployer(chain_id, pool.deployer, pool.init_code_hash);
+        Self {
+            chain_id,
+            token0: pool.token0.address,
+            token1: pool.token1.address,
+            fee: pool.fee,
+            deployer,
+            init_code_hash,
+        }
+    }
+}
+
 impl<TP: TickDataProvider> Pool<TP> {
-    /// Returns the pool address
+    /// Returns the pool address, computed from the overrides if given, or else from the
+    /// [`deployer`](Self::deployer) and [`init_code_hash`](Self::init_code_hash) of the pool
     #[inline]
     pub fn address(
         &self,
@@ -122,11 +181,25 @@ impl<TP: TickDataProvider> Pool<TP> {
             &self.token0,
             &self.token1,
             self.fee,
-            init_code_hash_manual_override,
-            factory_address_override,
+            init_code_hash_manual_override.or(self.init_code_hash),
+            factory_address_override.or(self.deployer),
         )
     }
 
+    /// Sets the deployer and init code hash of the pool to those of a DEX, so that its address is
+    /// computed on the DEX
+    ///
+    /// ## Arguments
+    ///
+    /// * `config`: The configuration of the DEX of the pool
+    #[inline]
+    #[must_use]
+    pub const fn with_dex_config(mut self, config: &DexConfig) -> Self {
+        self.deployer = Some(config.deployer);
+        self.init_code_hash = Some(config.pool_init_code_hash);
+        self
+    }
+
     #[inline]
     pub fn chain_id(&self) -> ChainId {
         self.token0.chain_id()
@@ -227,6 +300,8 @@ impl<TP: TickDataProvider> Pool<TP> {
             liquidity,
             tick_current: TP::Index::from_i24(sqrt_ratio_x96.get_tick_at_sqrt_ratio()?),
             tick_data_provider,
+            deployer: None,
+            init_code_hash: None,
         })
     }
 
@@ -247,6 +322,17 @@ impl<TP: TickDataProvider> Pool<TP> {
             amount_specified,
             sqrt_price_limit_x96,
         )
+        .map_err(|e| self.swap_error(e, zero_for_one, None))
+    }
+
+    /// Adds the address of the pool, the direction and the tick to the error of a failed swap
+    fn swap_error(&self, error: Error, zero_for_one: bool, tick: Option<I24>) -> Error {
+        error.with_swap_context(SwapErrorContext {
+            pool: Some(self.into()),
+            hop: None,
+            zero_for_one,
+            tick,
+        })
     }
 
     /// Given an input amount of a token, return the computed output amount
@@ -272,6 +358,7 @@ impl<TP: TickDataProvider> Pool<TP> {
         let SwapState {
             amount_specified_remaining,
             amount_calculated: output_amount,
+            tick_current,
             ..
         } = self._swap(
             zero_for_one,
@@ -280,7 +367,11 @@ impl<TP: TickDataProvider> Pool<TP> {
         )?;
 
         if !amount_specified_remaining.is_zero() && sqrt_price_limit_x96.is_none() {
-            return Err(Error::InsufficientLiquidity);
+            return Err(self.swap_error(
+                Error::InsufficientLiquidity,
+                zero_for_one,
+                Some(tick_current.to_i24()),
+            ));
         }
 
         let output_token = if zero_for_one {
@@ -315,6 +406,7 @@ impl<TP: TickDataProvider> Pool<TP> {
         let SwapState {
             amount_specified_remaining,
             amount_calculated: output_amount,
+            tick_current,
             sqrt_price_x96,
             liquidity,
             ..
@@ -325,7 +417,11 @@ impl<TP: TickDataProvider> Pool<TP> {
         )?;
 
         if !amount_specified_remaining.is_zero() && sqrt_price_limit_x96.is_none() {
-            return Err(Error::InsufficientLiquidity);
+            return Err(self.swap_error(
+                Error::InsufficientLiquidity,
+                zero_for_one,
+                Some(tick_current.to_i24()),
+            ));
         }
 
         let output_token = if zero_for_one {
@@ -366,6 +462,7 @@ impl<TP: TickDataProvider> Pool<TP> {
         let SwapState {
             amount_specified_remaining,
             amount_calculated: input_amount,
+            tick_current,
             ..
         } = self._swap(
             zero_for_one,
@@ -374,7 +471,11 @@ impl<TP: TickDataProvider> Pool<TP> {
         )?;
 
         if !amount_specified_remaining.is_zero() && sqrt_price_limit_x96.is_none() {
-            return Err(Error::InsufficientLiquidity);
+            return Err(self.swap_error(
+                Error::InsufficientLiquidity,
+                zero_for_one,
+                Some(tick_current.to_i24()),
+            ));
         }
 
         let input_token = if zero_for_one {
@@ -412,6 +513,7 @@ impl<TP: TickDataProvider> Pool<TP> {
         let SwapState {
             amount_specified_remaining,
             amount_calculated: input_amount,
+            tick_current,
             sqrt_price_x96,
             liquidity,
             ..
@@ -422,7 +524,11 @@ impl<TP: TickDataProvider> Pool<TP> {
         )?;
 
         if !amount_specified_remaining.is_zero() && sqrt_price_limit_x96.is_none() {
-            return Err(Error::InsufficientLiquidity);
+            return Err(self.swap_error(
+                Error::InsufficientLiquidity,
+                zero_for_one,
+                Some(tick_current.to_i24()),
+            ));
         }
 
         let input_token = if zero_for_one {
@@ -495,6 +601,54 @@ mod tests {
         assert_eq!(result, address!("6c6Bc977E13Df9b0de53b251522280BB72383700"));
     }
 
+    #[test]
+    fn get_address_with_overrides() {
+        let factory = address!("1111111111111111111111111111111111111111");
+        assert_eq!(
+            Pool::get_address(
+                &USDC,
+                &DAI,
+                FeeAmount::LOW,
+                Some(POOL_INIT_CODE_HASH),
+                Some(factory)
+            ),
+            compute_pool_address(
+                factory,
+                USDC.address,
+                DAI.address,
+                FeeAmount::LOW,
+                None,
+                None
+            )
+        );
+    }
+
+    #[test]
+    fn address_with_dex_config() {
+        let config = DexConfig::pancakeswap(1);
+        let pool = Pool::new(
+            USDC.clone(),
+            DAI.clone(),
+            FeeAmount::LOW,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            pool.address(None, None),
+            address!("6c6Bc977E13Df9b0de53b251522280BB72383700")
+        );
+        let pool = pool.with_dex_config(&config);
+        assert_eq!(
+            pool.address(None, None),
+            config.pool_address(USDC.address, DAI.address, FeeAmount::LOW)
+        );
+        assert_eq!(
+            SwapErrorPool::from(&pool).address(),
+            pool.address(None, None)
+        );
+    }
+
     #[test]
     fn token0_always_is_the_token_that_sorts_before() {
         let pool = Pool::new(
@@ -698,6 +852,38 @@ mod tests {
             assert_eq!(output_amount.quotient(), 98.into());
         }
 
+        #[test]
+        fn get_output_amount_error_has_tick_context() {
+            let pool = Pool::new(
+                USDC.clone(),
+                DAI.clone(),
+                FeeAmount::LOW,
+                encode_sqrt_ratio_x96(1, 1),
+                0,
+            )
+            .unwrap();
+            let error = pool
+                .get_output_amount(
+                    &CurrencyAmount::from_raw_amount(USDC.clone(), 100).unwrap(),
+                    None,
+                )
+                .unwrap_err();
+            assert!(matches!(error.root_cause(), Error::NoTickDataError));
+            assert_eq!(
+                error.swap_context().unwrap().pool.unwrap().address(),
+                pool.address(None, None)
+            );
+            assert_eq!(
+                error.swap_context(),
+                Some(&SwapErrorContext {
+                    pool: Some((&pool).into()),
+                    hop: None,
+                    zero_for_one: pool.token0.equals(&USDC.clone()),
+                    tick: Some(I24::ZERO),
+                })
+            );
+        }
+
         #[test]
         fn get_output_amount_dai_to_usdc() {
             let output_amount = POOL
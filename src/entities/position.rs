@@ -1,4 +1,5 @@
 use crate::prelude::{Error, *};
+use alloc::vec::Vec;
 use alloy_primitives::{U160, U256};
 use num_traits::ToPrimitive;
 use uniswap_sdk_core::prelude::*;
@@ -13,6 +14,8 @@ where
     pub tick_lower: TP::Index,
     pub tick_upper: TP::Index,
     pub liquidity: u128,
+    /// The fee accounting snapshot of the position, if known
+    pub fee_state: Option<PositionFeeState>,
     _token0_amount: Option<CurrencyAmount<Token>>,
     _token1_amount: Option<CurrencyAmount<Token>>,
     _mint_amounts: Option<MintAmounts>,
@@ -24,6 +27,38 @@ pub struct MintAmounts {
     pub amount1: U256,
 }
 
+/// The fee accounting state of a position as tracked by the `NonfungiblePositionManager`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PositionFeeState {
+    /// The fee growth of token0 inside the tick range as of the last action on the position
+    pub fee_growth_inside0_last_x128: U256,
+    /// The fee growth of token1 inside the tick range as of the last action on the position
+    pub fee_growth_inside1_last_x128: U256,
+    /// The uncollected amount of token0 owed to the position as of the last computation
+    pub tokens_owed0: u128,
+    /// The uncollected amount of token1 owed to the position as of the last computation
+    pub tokens_owed1: u128,
+}
+
+/// The value and price sensitivities of a position at a given price
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionPayoff {
+    /// The sqrt price at which the position is evaluated
+    pub sqrt_ratio_x96: U160,
+    /// The amount of token0 the position's liquidity could be burned for
+    pub amount0: CurrencyAmount<Token>,
+    /// The amount of token1 the position's liquidity could be burned for
+    pub amount1: CurrencyAmount<Token>,
+    /// The value of the position denominated in token1
+    pub value: CurrencyAmount<Token>,
+    /// The first derivative of the value with respect to the price of token0, i.e. the exposure
+    /// to token0
+    pub delta: CurrencyAmount<Token>,
+    /// The second derivative of the value with respect to the price of token0, in raw units of
+    /// token0 per raw unit of price
+    pub gamma: Fraction,
+}
+
 impl<TP> PartialEq for Position<TP>
 where
     TP: TickDataProvider<Index: PartialEq>,
@@ -69,12 +104,105 @@ impl<TP: TickDataProvider> Position<TP> {
             liquidity,
             tick_lower,
             tick_upper,
+            fee_state: None,
             _token0_amount: None,
             _token1_amount: None,
             _mint_amounts: None,
         }
     }
 
+    /// Constructs a position like [`Position::new`], returning an error instead of panicking if
+    /// the ticks are out of order, out of bounds or not multiples of the tick spacing
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: For which pool the liquidity is assigned
+    /// * `liquidity`: The amount of liquidity that is in the position
+    /// * `tick_lower`: The lower tick of the position
+    /// * `tick_upper`: The upper tick of the position
+    #[inline]
+    pub fn try_new(
+        pool: Pool<TP>,
+        liquidity: u128,
+        tick_lower: TP::Index,
+        tick_upper: TP::Index,
+    ) -> Result<Self, Error> {
+        if tick_lower >= tick_upper {
+            return Err(Error::InvalidRange);
+        }
+        let tick_spacing = pool.tick_spacing();
+        for tick in [tick_lower, tick_upper] {
+            if tick < TP::Index::from_i24(MIN_TICK)
+                || tick > TP::Index::from_i24(MAX_TICK)
+                || !(tick % tick_spacing).is_zero()
+            {
+                return Err(Error::InvalidTick(tick.to_i24()));
+            }
+        }
+        Ok(Self::new(pool, liquidity, tick_lower, tick_upper))
+    }
+
+    /// Attaches the fee accounting snapshot of the position
+    ///
+    /// ## Arguments
+    ///
+    /// * `fee_state`: The fee growth inside and tokens owed as of the last action on the position
+    #[inline]
+    #[must_use]
+    pub const fn with_fee_state(mut self, fee_state: PositionFeeState) -> Self {
+        self.fee_state = Some(fee_state);
+        self
+    }
+
+    /// Computes the fees owed to the position without querying the chain
+    ///
+    /// ## Arguments
+    ///
+    /// * `lower`: The fee growth outside of the lower tick
+    /// * `upper`: The fee growth outside of the upper tick
+    /// * `fee_growth_global0_x128`: The global fee growth of token0 of the pool
+    /// * `fee_growth_global1_x128`: The global fee growth of token1 of the pool
+    ///
+    /// ## Returns
+    ///
+    /// The collectable amounts of token0 and token1, including the previously owed tokens
+    #[inline]
+    pub fn tokens_owed(
+        &self,
+        lower: FeeGrowthOutside<256, 4>,
+        upper: FeeGrowthOutside<256, 4>,
+        fee_growth_global0_x128: U256,
+        fee_growth_global1_x128: U256,
+    ) -> Result<(CurrencyAmount<Token>, CurrencyAmount<Token>), Error> {
+        let fee_state = self.fee_state.ok_or(Error::NoFeeStateError)?;
+        let (fee_growth_inside0_x128, fee_growth_inside1_x128) = get_fee_growth_inside(
+            lower,
+            upper,
+            self.tick_lower,
+            self.tick_upper,
+            self.pool.tick_current,
+            fee_growth_global0_x128,
+            fee_growth_global1_x128,
+        );
+        let (tokens_owed0, tokens_owed1) = get_tokens_owed(
+            fee_state.fee_growth_inside0_last_x128,
+            fee_state.fee_growth_inside1_last_x128,
+            self.liquidity,
+            fee_growth_inside0_x128,
+            fee_growth_inside1_x128,
+        );
+        Ok((
+            CurrencyAmount::from_raw_amount(
+                self.pool.token0.clone(),
+                (U256::from(fee_state.tokens_owed0) + tokens_owed0).to_big_int(),
+            )?,
+            CurrencyAmount::from_raw_amount(
+                self.pool.token1.clone(),
+                (U256::from(fee_state.tokens_owed1) + tokens_owed1).to_big_int(),
+            )?,
+        ))
+    }
+
     /// Returns the price of token0 at the lower tick
     #[inline]
     pub fn token0_price_lower(&self) -> Result<Price<Token, Token>, Error> {
@@ -183,6 +311,162 @@ impl<TP: TickDataProvider> Position<TP> {
         Ok(amount)
     }
 
+    /// Returns the amounts of token0 and token1 that this position's liquidity could be burned for
+    /// if the pool price were the given sqrt price
+    ///
+    /// ## Arguments
+    ///
+    /// * `sqrt_ratio_x96`: The sqrt price at which to evaluate the position
+    #[inline]
+    pub fn amounts_at_sqrt_ratio(&self, sqrt_ratio_x96: U160) -> Result<(U256, U256), Error> {
+        let sqrt_ratio_a_x96 = get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?;
+        let sqrt_ratio_b_x96 = get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?;
+        Ok(if sqrt_ratio_x96 <= sqrt_ratio_a_x96 {
+            (
+                get_amount_0_delta(sqrt_ratio_a_x96, sqrt_ratio_b_x96, self.liquidity, false)?,
+                U256::ZERO,
+            )
+        } else if sqrt_ratio_x96 < sqrt_ratio_b_x96 {
+            (
+                get_amount_0_delta(sqrt_ratio_x96, sqrt_ratio_b_x96, self.liquidity, false)?,
+                get_amount_1_delta(sqrt_ratio_a_x96, sqrt_ratio_x96, self.liquidity, false)?,
+            )
+        } else {
+            (
+                U256::ZERO,
+                get_amount_1_delta(sqrt_ratio_a_x96, sqrt_ratio_b_x96, self.liquidity, false)?,
+            )
+        })
+    }
+
+    /// Evaluates the value, token composition, delta and gamma of the position if the pool price
+    /// were the given sqrt price
+    ///
+    /// ## Arguments
+    ///
+    /// * `sqrt_ratio_x96`: The sqrt price at which to evaluate the position
+    #[inline]
+    pub fn payoff_at_sqrt_ratio(&self, sqrt_ratio_x96: U160) -> Result<PositionPayoff, Error> {
+        let (amount0, amount1) = self.amounts_at_sqrt_ratio(sqrt_ratio_x96)?;
+        let amount0 =
+            CurrencyAmount::from_raw_amount(self.pool.token0.clone(), amount0.to_big_int())?;
+        let amount1 =
+            CurrencyAmount::from_raw_amount(self.pool.token1.clone(), amount1.to_big_int())?;
+        let sqrt_ratio = sqrt_ratio_x96.to_big_int();
+        let token0_price = Price::new(
+            self.pool.token0.clone(),
+            self.pool.token1.clone(),
+            Q192_BIG_INT,
+            sqrt_ratio * sqrt_ratio,
+        );
+        let value = token0_price.quote(&amount0)?.add(&amount1)?;
+        // Within the range, the token0 holdings are `L / sqrt(P) - L / sqrt(P_b)`, so the gamma is
+        // `-L / (2 * sqrt(P)^3)`. Outside the range the value is linear in the price.
+        let gamma = if sqrt_ratio_x96 > get_sqrt_ratio_at_tick(self.tick_lower.to_i24())?
+            && sqrt_ratio_x96 < get_sqrt_ratio_at_tick(self.tick_upper.to_i24())?
+        {
+            Fraction::new(
+                -BigInt::from(self.liquidity) * Q96.to_big_int().pow(3),
+                BigInt::from(2) * sqrt_ratio.pow(3),
+            )
+        } else {
+            Fraction::new(0, 1)
+        };
+        Ok(PositionPayoff {
+            sqrt_ratio_x96,
+            delta: amount0.clone(),
+            amount0,
+            amount1,
+            value,
+            gamma,
+        })
+    }
+
+    /// Evaluates the value, token composition, delta and gamma of the position if the pool price
+    /// were the given price
+    ///
+    /// ## Arguments
+    ///
+    /// * `price`: The price of either pool token in terms of the other
+    #[inline]
+    pub fn payoff_at_price(&self, price: &Price<Token, Token>) -> Result<PositionPayoff, Error> {
+        self.payoff_at_sqrt_ratio(self.price_to_sqrt_ratio_x96(price)?)
+    }
+
+    /// Samples the payoff curve of the position at evenly spaced sqrt prices
+    ///
+    /// ## Arguments
+    ///
+    /// * `sqrt_ratio_lower_x96`: The lowest sqrt price to sample
+    /// * `sqrt_ratio_upper_x96`: The highest sqrt price to sample
+    /// * `num_points`: The number of samples, including both ends of the range
+    ///
+    /// Returns [`Error::InvalidRange`] if the lower sqrt price is above the upper one and
+    /// [`Error::InvalidNumPoints`] if fewer than two points are requested.
+    #[inline]
+    pub fn payoff_curve(
+        &self,
+        sqrt_ratio_lower_x96: U160,
+        sqrt_ratio_upper_x96: U160,
+        num_points: usize,
+    ) -> Result<Vec<PositionPayoff>, Error> {
+        if sqrt_ratio_lower_x96 > sqrt_ratio_upper_x96 {
+            return Err(Error::InvalidRange);
+        }
+        if num_points < 2 {
+            return Err(Error::InvalidNumPoints(num_points));
+        }
+        let step = (sqrt_ratio_upper_x96 - sqrt_ratio_lower_x96) / U160::from(num_points - 1);
+        (0..num_points)
+            .map(|i| {
+                let sqrt_ratio_x96 = if i == num_points - 1 {
+                    sqrt_ratio_upper_x96
+                } else {
+                    sqrt_ratio_lower_x96 + step * U160::from(i)
+                };
+                self.payoff_at_sqrt_ratio(sqrt_ratio_x96)
+            })
+            .collect()
+    }
+
+    /// Samples the payoff curve of the position at evenly spaced sqrt prices between two prices
+    ///
+    /// ## Arguments
+    ///
+    /// * `price_lower`: The lowest price to sample
+    /// * `price_upper`: The highest price to sample
+    /// * `num_points`: The number of samples, including both ends of the range
+    #[inline]
+    pub fn payoff_curve_at_prices(
+        &self,
+        price_lower: &Price<Token, Token>,
+        price_upper: &Price<Token, Token>,
+        num_points: usize,
+    ) -> Result<Vec<PositionPayoff>, Error> {
+        let sqrt_ratio_a_x96 = self.price_to_sqrt_ratio_x96(price_lower)?;
+        let sqrt_ratio_b_x96 = self.price_to_sqrt_ratio_x96(price_upper)?;
+        if sqrt_ratio_a_x96 <= sqrt_ratio_b_x96 {
+            self.payoff_curve(sqrt_ratio_a_x96, sqrt_ratio_b_x96, num_points)
+        } else {
+            self.payoff_curve(sqrt_ratio_b_x96, sqrt_ratio_a_x96, num_points)
+        }
+    }
+
+    /// Converts a price of either pool token into the sqrt price of token0 in terms of token1
+    fn price_to_sqrt_ratio_x96(&self, price: &Price<Token, Token>) -> Result<U160, Error> {
+        if price.base_currency.equals(&self.pool.token0)
+            && price.quote_currency.equals(&self.pool.token1)
+        {
+            Ok(encode_sqrt_ratio_x96(price.numerator, price.denominator))
+        } else if price.base_currency.equals(&self.pool.token1)
+            && price.quote_currency.equals(&self.pool.token0)
+        {
+            Ok(encode_sqrt_ratio_x96(price.denominator, price.numerator))
+        } else {
+            Err(Error::InvalidToken)
+        }
+    }
+
     /// Returns the lower and upper sqrt ratios if the price 'slips' up to slippage tolerance
     /// percentage
     ///
@@ -438,12 +722,7 @@ impl<TP: TickDataProvider> Position<TP> {
             amount1,
             use_full_precision,
         );
-        Ok(Self::new(
-            pool,
-            liquidity.to_u128().unwrap(),
-            tick_lower,
-            tick_upper,
-        ))
+        Self::try_new(pool, liquidity.to_u128().unwrap(), tick_lower, tick_upper)
     }
 
     /// Computes a position with the maximum amount of liquidity received for a given amount of
@@ -585,6 +864,160 @@ mod tests {
         );
     }
 
+    #[test]
+    fn try_new_returns_errors_for_invalid_ticks() {
+        assert!(Position::try_new(DAI_USDC_POOL.clone(), 1, -10, 10).is_ok());
+        assert!(matches!(
+            Position::try_new(DAI_USDC_POOL.clone(), 1, 10, -10),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            Position::try_new(DAI_USDC_POOL.clone(), 1, -5, 10),
+            Err(Error::InvalidTick(tick)) if tick == I24::from_limbs([5]).wrapping_neg()
+        ));
+        let tick_upper = nearest_usable_tick(MAX_TICK, TICK_SPACING) + TICK_SPACING;
+        assert!(matches!(
+            Position::try_new(DAI_USDC_POOL.clone(), 1, -10, tick_upper.as_i32()),
+            Err(Error::InvalidTick(tick)) if tick == tick_upper
+        ));
+    }
+
+    #[test]
+    fn tokens_owed_requires_fee_state() {
+        let position = Position::new(POOL_0_1.clone(), 1, -60, 60);
+        assert!(matches!(
+            position.tokens_owed(
+                FeeGrowthOutside::default(),
+                FeeGrowthOutside::default(),
+                Q128,
+                Q128
+            ),
+            Err(Error::NoFeeStateError)
+        ));
+    }
+
+    #[test]
+    fn tokens_owed_is_correct_for_position_in_range() {
+        let position =
+            Position::new(POOL_0_1.clone(), 2, -60, 60).with_fee_state(PositionFeeState {
+                fee_growth_inside0_last_x128: Q128,
+                fee_growth_inside1_last_x128: U256::ZERO,
+                tokens_owed0: 5,
+                tokens_owed1: 7,
+            });
+        let lower = FeeGrowthOutside {
+            fee_growth_outside0_x128: Q128,
+            fee_growth_outside1_x128: Q128,
+        };
+        let (tokens_owed0, tokens_owed1) = position
+            .tokens_owed(
+                lower,
+                FeeGrowthOutside::default(),
+                Q128 * U256::from(4),
+                Q128 * U256::from(4),
+            )
+            .unwrap();
+        // fee growth inside = global - lower - upper = 3 * Q128
+        assert_eq!(tokens_owed0.quotient(), BigInt::from(5 + 2 * 2));
+        assert_eq!(tokens_owed1.quotient(), BigInt::from(7 + 2 * 3));
+        assert!(tokens_owed0.currency.equals(&TOKEN0.clone()));
+    }
+
+    #[test]
+    fn tokens_owed_is_correct_for_position_below_range() {
+        let position =
+            Position::new(POOL_0_1.clone(), 1, 60, 120).with_fee_state(PositionFeeState::default());
+        let lower = FeeGrowthOutside {
+            fee_growth_outside0_x128: Q128 * U256::from(3),
+            fee_growth_outside1_x128: Q128 * U256::from(3),
+        };
+        let upper = FeeGrowthOutside {
+            fee_growth_outside0_x128: Q128,
+            fee_growth_outside1_x128: Q128,
+        };
+        let (tokens_owed0, tokens_owed1) = position
+            .tokens_owed(lower, upper, Q128 * U256::from(10), Q128 * U256::from(10))
+            .unwrap();
+        assert_eq!(tokens_owed0.quotient(), BigInt::from(2));
+        assert_eq!(tokens_owed1.quotient(), BigInt::from(2));
+    }
+
+    #[test]
+    fn payoff_at_current_price_matches_amounts() {
+        let position = Position::new(POOL_0_1.clone(), 1_000_000_000_000, -600, 600);
+        let payoff = position
+            .payoff_at_sqrt_ratio(position.pool.sqrt_ratio_x96)
+            .unwrap();
+        assert_eq!(payoff.amount0, position.amount0().unwrap());
+        assert_eq!(payoff.amount1, position.amount1().unwrap());
+        assert_eq!(payoff.delta, payoff.amount0);
+        assert_eq!(
+            payoff.value.quotient(),
+            payoff.amount0.quotient() + payoff.amount1.quotient()
+        );
+        // gamma = -L / 2 at a price of 1
+        assert_eq!(payoff.gamma, Fraction::new(-500_000_000_000_i64, 1));
+        assert_eq!(
+            position.payoff_at_price(&POOL_0_1.token1_price()).unwrap(),
+            payoff
+        );
+    }
+
+    #[test]
+    fn payoff_outside_range_has_no_gamma() {
+        let position = Position::new(POOL_0_1.clone(), 1_000_000_000_000, -600, 600);
+        let below = position
+            .payoff_at_sqrt_ratio(get_sqrt_ratio_at_tick(I24::unchecked_from(-1200)).unwrap())
+            .unwrap();
+        assert!(below.amount1.quotient().is_zero());
+        assert_eq!(below.gamma, Fraction::new(0, 1));
+        assert_eq!(below.delta, below.amount0);
+        let above = position
+            .payoff_at_sqrt_ratio(get_sqrt_ratio_at_tick(I24::unchecked_from(1200)).unwrap())
+            .unwrap();
+        assert!(above.amount0.quotient().is_zero());
+        assert!(above.delta.quotient().is_zero());
+        assert_eq!(above.value, above.amount1);
+    }
+
+    #[test]
+    fn payoff_curve_is_concave_in_range() {
+        let position = Position::new(POOL_0_1.clone(), 1_000_000_000_000, -600, 600);
+        let curve = position
+            .payoff_curve(
+                get_sqrt_ratio_at_tick(I24::unchecked_from(-1200)).unwrap(),
+                get_sqrt_ratio_at_tick(I24::unchecked_from(1200)).unwrap(),
+                9,
+            )
+            .unwrap();
+        assert_eq!(curve.len(), 9);
+        assert_eq!(
+            curve[8].sqrt_ratio_x96,
+            get_sqrt_ratio_at_tick(I24::unchecked_from(1200)).unwrap()
+        );
+        for window in curve.windows(2) {
+            // value is non-decreasing and delta is non-increasing in the price
+            assert!(window[0].value <= window[1].value);
+            assert!(window[0].delta >= window[1].delta);
+            assert!(window[1].gamma.numerator <= BigInt::ZERO);
+        }
+    }
+
+    #[test]
+    fn payoff_curve_returns_errors_for_invalid_arguments() {
+        let position = Position::new(POOL_0_1.clone(), 1_000_000_000_000, -600, 600);
+        let lower = get_sqrt_ratio_at_tick(I24::unchecked_from(-1200)).unwrap();
+        let upper = get_sqrt_ratio_at_tick(I24::unchecked_from(1200)).unwrap();
+        assert!(matches!(
+            position.payoff_curve(upper, lower, 9),
+            Err(Error::InvalidRange)
+        ));
+        assert!(matches!(
+            position.payoff_curve(lower, upper, 1),
+            Err(Error::InvalidNumPoints(1))
+        ));
+    }
+
     #[test]
     fn amount0_is_correct_for_price_above() {
         let position = Position::new(
@@ -548,13 +548,22 @@ where
     ) -> Result<Self, Error> {
         let mut token_amount: CurrencyAmount<Token> = amount.wrapped_owned()?;
         let currency = amount.meta.currency;
+        let hop_context = |pool: &Pool<TP>, hop: usize, zero_for_one: bool| SwapErrorContext {
+            pool: Some(pool.into()),
+            hop: Some(hop),
+            zero_for_one,
+            tick: None,
+        };
         let input_amount: CurrencyAmount<TInput>;
         let output_amount: CurrencyAmount<TOutput>;
         match trade_type {
             TradeType::ExactInput => {
                 assert!(currency.wrapped().equals(route.input.wrapped()), "INPUT");
-                for pool in &route.pools {
-                    token_amount = pool.get_output_amount(&token_amount, None)?;
+                for (hop, pool) in route.pools.iter().enumerate() {
+                    let zero_for_one = token_amount.currency.equals(&pool.token0);
+                    token_amount = pool
+                        .get_output_amount(&token_amount, None)
+                        .map_err(|e| e.with_swap_context(hop_context(pool, hop, zero_for_one)))?;
                 }
                 output_amount = CurrencyAmount::from_fractional_amount(
                     route.output.clone(),
@@ -569,8 +578,11 @@ where
             }
             TradeType::ExactOutput => {
                 assert!(currency.wrapped().equals(route.output.wrapped()), "OUTPUT");
-                for pool in route.pools.iter().rev() {
-                    token_amount = pool.get_input_amount(&token_amount, None)?;
+                for (hop, pool) in route.pools.iter().enumerate().rev() {
+                    let zero_for_one = token_amount.currency.equals(&pool.token1);
+                    token_amount = pool
+                        .get_input_amount(&token_amount, None)
+                        .map_err(|e| e.with_swap_context(hop_context(pool, hop, zero_for_one)))?;
                 }
                 input_amount = CurrencyAmount::from_fractional_amount(
                     route.input.clone(),
@@ -669,7 +681,7 @@ where
             }
             let amount_out = match pool.get_output_amount(&amount_in, None) {
                 Ok(amount_out) => amount_out,
-                Err(Error::InsufficientLiquidity) => continue,
+                Err(e) if matches!(e.root_cause(), Error::InsufficientLiquidity) => continue,
                 Err(e) => return Err(e),
             };
             // we have arrived at the output token, so this is the final trade of one of the paths
@@ -762,7 +774,7 @@ where
             }
             let amount_in = match pool.get_input_amount(&amount_out, None) {
                 Ok(amount_in) => amount_in,
-                Err(Error::InsufficientLiquidity) => continue,
+                Err(e) if matches!(e.root_cause(), Error::InsufficientLiquidity) => continue,
                 Err(e) => return Err(e),
             };
             // we have arrived at the input token, so this is the first trade of one of the paths
@@ -960,6 +972,33 @@ mod tests {
             assert_eq!(trade.input_amount().unwrap().currency, TOKEN0.clone());
             assert_eq!(trade.output_amount().unwrap().currency, ETHER.clone());
         }
+
+        #[test]
+        fn error_has_hop_context() {
+            let error = Trade::from_route(
+                Route::new(
+                    vec![POOL_0_1.clone(), POOL_1_2.clone(), POOL_WETH_2.clone()],
+                    TOKEN0.clone(),
+                    ETHER.clone(),
+                ),
+                CurrencyAmount::from_raw_amount(ETHER.clone(), 200000).unwrap(),
+                TradeType::ExactOutput,
+            )
+            .unwrap_err();
+            assert!(matches!(error.root_cause(), Error::InsufficientLiquidity));
+            let context = error.swap_context().unwrap();
+            assert_eq!(
+                context.pool.unwrap().address(),
+                POOL_WETH_2.address(None, None)
+            );
+            assert_eq!(context.hop, Some(2));
+            assert_eq!(
+                context.zero_for_one,
+                POOL_WETH_2.token1.equals(ETHER.wrapped())
+            );
+            assert!(context.tick.is_some());
+            assert!(error.to_string().contains("(hop 2)"));
+        }
     }
 
     mod from_routes {
@@ -10,11 +10,18 @@ use alloy::providers::MulticallError;
 #[cfg(feature = "extensions")]
 use uniswap_lens::error::Error as LensError;
 
-use alloy_primitives::{aliases::I24, U160};
+use alloc::boxed::Box;
+use alloy_primitives::{
+    aliases::{I24, U24},
+    Address, ChainId, B256, U160,
+};
+use core::fmt;
 use uniswap_sdk_core::error::Error as CoreError;
 
+use crate::{constants::FeeAmount, utils::compute_pool_address::compute_pool_address};
+
 #[derive(Debug, thiserror::Error)]
-#[cfg_attr(not(feature = "extensions"), derive(Clone, Copy, Hash, PartialEq, Eq))]
+#[cfg_attr(not(feature = "extensions"), derive(Clone, Hash, PartialEq, Eq))]
 pub enum Error {
     /// Thrown when an error occurs in the core library.
     #[error("{0}")]
@@ -58,13 +65,55 @@ pub enum Error {
     #[error("No tick data provider was given")]
     NoTickDataError,
 
+    /// Thrown when the fees owed to a [`Position`] are computed without a fee state snapshot.
+    #[error("No fee state was given")]
+    NoFeeStateError,
+
+    /// Thrown when a fee is not an enabled fee tier in the [fee tier registry](get_fee_amount).
+    #[error("Unknown fee tier: {0}")]
+    UnknownFeeTier(U24),
+
+    /// Thrown when a contract address is not given and the chain has no [`Deployment`] of it.
+    #[error("No deployment on chain {0}")]
+    UnknownDeployment(ChainId),
+
+    /// Thrown when the path passed to [`decode_path`] is not a token followed by one or more
+    /// hops.
+    #[error("Invalid path length: {0}")]
+    InvalidPathLength(usize),
+
+    /// Thrown when a fee in the path passed to [`decode_path`] is not less than 100%.
+    #[error("Invalid fee: {0}")]
+    InvalidFee(U24),
+
+    /// Thrown when the liquidity of a [`RangeOrder`] is removed before the order is filled.
+    #[error("Range order not filled")]
+    RangeOrderNotFilled,
+
     #[error("{0}")]
     TickListError(#[from] TickListError),
 
-    #[cfg(feature = "extensions")]
+    /// Thrown when a swap through a pool fails, with the context of where it failed. The
+    /// underlying error is available from [`Error::root_cause`].
+    #[error("Swap failed at {context}: {source}")]
+    Swap {
+        context: Box<SwapErrorContext>,
+        source: Box<Self>,
+    },
+
+    /// Thrown when the lower tick of a range is not below its upper tick, or the lower bound of a
+    /// sampled price range is above its upper bound.
     #[error("Invalid tick range")]
     InvalidRange,
 
+    /// Thrown when an approval of type [`ApprovalType::NotRequired`] is encoded.
+    #[error("Approval not required")]
+    ApprovalNotRequired,
+
+    /// Thrown when a payoff curve is sampled at fewer than two points.
+    #[error("Invalid number of points: {0}")]
+    InvalidNumPoints(usize),
+
     #[cfg(feature = "extensions")]
     #[error("{0}")]
     ContractError(#[from] ContractError),
@@ -80,6 +129,177 @@ pub enum Error {
     #[cfg(feature = "extensions")]
     #[error("Invalid access list")]
     InvalidAccessList,
+
+    /// Thrown when typed data is not of the expected type or cannot be hashed.
+    #[cfg(feature = "extensions")]
+    #[error("Invalid typed data")]
+    InvalidTypedData,
+
+    /// Thrown when typed data is not signed, or is to be signed, by the expected signer.
+    #[cfg(feature = "extensions")]
+    #[error("Invalid signature")]
+    InvalidSignature,
+
+    /// Thrown when a signature cannot be parsed or recovered.
+    #[cfg(feature = "extensions")]
+    #[error("{0}")]
+    SignatureError(#[from] alloy_primitives::SignatureError),
+
+    /// Thrown when a signer fails to sign a permit.
+    #[cfg(feature = "extensions")]
+    #[error("{0}")]
+    SignerError(#[from] alloy::signers::Error),
+
+    /// Thrown when the permit flavor or the EIP-712 domain of a token cannot be discovered.
+    #[cfg(feature = "extensions")]
+    #[error("Unsupported permit of token {0}")]
+    UnsupportedPermit(Address),
+
+    /// Thrown when a simulated transaction reverts.
+    #[cfg(feature = "extensions")]
+    #[error("Execution reverted: {reason}")]
+    Revert {
+        reason: alloc::string::String,
+        data: alloy_primitives::Bytes,
+    },
+
+    /// Thrown when a [`PoolEvent`] cannot be parsed.
+    #[cfg(feature = "extensions")]
+    #[error("{0}")]
+    JsonError(#[from] serde_json::Error),
+
+    /// Thrown when a file of [`PoolEvent`]s cannot be read.
+    #[cfg(all(feature = "extensions", feature = "std"))]
+    #[error("{0}")]
+    IoError(#[from] std::io::Error),
+
+    /// Thrown when a [`StrategyAction`] refers to a position a [`Backtest`] does not hold.
+    #[cfg(feature = "extensions")]
+    #[error("No position at index {0}")]
+    PositionNotFound(usize),
+
+    /// Thrown when a trade passed to [`analyze_sandwich`] is not an exact input trade through a
+    /// single route.
+    #[cfg(feature = "extensions")]
+    #[error("Unsupported trade: {0}")]
+    UnsupportedTrade(&'static str),
+}
+
+impl Error {
+    /// Returns the underlying error, unwrapping the context of a failed swap
+    #[inline]
+    #[must_use]
+    pub fn root_cause(&self) -> &Self {
+        match self {
+            Self::Swap { source, .. } => source.root_cause(),
+            e => e,
+        }
+    }
+
+    /// Returns the context of a failed swap, if any
+    #[inline]
+    #[must_use]
+    pub fn swap_context(&self) -> Option<&SwapErrorContext> {
+        match self {
+            Self::Swap { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// Adds the context of a failed swap to the error, keeping the fields of any existing context
+    /// which are more specific.
+    #[inline]
+    #[must_use]
+    pub fn with_swap_context(self, update: SwapErrorContext) -> Self {
+        match self {
+            Self::Swap {
+                mut context,
+                source,
+            } => {
+                *context = SwapErrorContext {
+                    pool: context.pool.or(update.pool),
+                    hop: context.hop.or(update.hop),
+                    zero_for_one: context.zero_for_one,
+                    tick: context.tick.or(update.tick),
+                };
+                Self::Swap { context, source }
+            }
+            e => Self::Swap {
+                context: Box::new(update),
+                source: Box::new(e),
+            },
+        }
+    }
+}
+
+/// The pool in which a swap failed, identified by its chain, tokens and fee tier, with the
+/// deployer and init code hash of the pool recorded when the error occurred
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct SwapErrorPool {
+    /// The chain of the pool
+    pub chain_id: ChainId,
+    /// The address of token0 of the pool
+    pub token0: Address,
+    /// The address of token1 of the pool
+    pub token1: Address,
+    /// The fee tier of the pool
+    pub fee: FeeAmount,
+    /// The address that deployed the pool with `CREATE2`
+    pub deployer: Address,
+    /// The hash of the pool init code, or `None` for the default of the chain
+    pub init_code_hash: Option<B256>,
+}
+
+impl SwapErrorPool {
+    /// Computes the address of the pool from its deployer and init code hash, like
+    /// [`Pool::address`]
+    #[inline]
+    #[must_use]
+    pub fn address(&self) -> Address {
+        compute_pool_address(
+            self.deployer,
+            self.token0,
+            self.token1,
+            self.fee,
+            self.init_code_hash,
+            Some(self.chain_id),
+        )
+    }
+}
+
+/// Where a swap through a pool failed
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct SwapErrorContext {
+    /// The pool, whose address is computed on demand by [`SwapErrorPool::address`]
+    pub pool: Option<SwapErrorPool>,
+    /// The index of the pool in the route
+    pub hop: Option<usize>,
+    /// Whether the swap was from token0 to token1
+    pub zero_for_one: bool,
+    /// The current tick of the swap when it failed
+    pub tick: Option<I24>,
+}
+
+impl fmt::Display for SwapErrorContext {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pool {
+            Some(pool) => write!(f, "pool {}", pool.address())?,
+            None => f.write_str("pool")?,
+        }
+        if let Some(hop) = self.hop {
+            write!(f, " (hop {hop})")?;
+        }
+        f.write_str(if self.zero_for_one {
+            ", zero for one"
+        } else {
+            ", one for zero"
+        })?;
+        if let Some(tick) = self.tick {
+            write!(f, ", tick {tick}")?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, thiserror::Error)]
@@ -0,0 +1,881 @@
+//! ## Backtest
+//! This module backtests LP strategies offline by replaying a historical series of swap and
+//! liquidity events against a pool with fee growth accounting, letting a strategy mint, burn or
+//! rebalance [`Position`]s after every event.
+
+use crate::prelude::{Error, *};
+use alloc::{collections::BTreeMap, vec::Vec};
+use alloy_primitives::{aliases::U24, I256, U160, U256};
+use serde::{Deserialize, Serialize};
+use uniswap_sdk_core::prelude::*;
+
+/// An event emitted by a pool, as recorded in a historical event series.
+///
+/// Events are encoded as externally tagged JSON, e.g.
+/// `{"swap":{"timestamp":1700000000,"sqrt_price_x96":"0x1000000000000000000000000"}}`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolEvent {
+    /// A swap which moved the pool price to `sqrt_price_x96`
+    Swap {
+        timestamp: u64,
+        sqrt_price_x96: U160,
+    },
+    /// Liquidity added to a tick range
+    Mint {
+        timestamp: u64,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: u128,
+    },
+    /// Liquidity removed from a tick range
+    Burn {
+        timestamp: u64,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity: u128,
+    },
+}
+
+impl PoolEvent {
+    /// Returns the timestamp of the event
+    #[inline]
+    #[must_use]
+    pub const fn timestamp(&self) -> u64 {
+        match self {
+            Self::Swap { timestamp, .. }
+            | Self::Mint { timestamp, .. }
+            | Self::Burn { timestamp, .. } => *timestamp,
+        }
+    }
+}
+
+/// Parses a series of [`PoolEvent`]s from JSON lines, skipping blank lines.
+///
+/// ## Arguments
+///
+/// * `json_lines`: One JSON encoded event per line
+#[inline]
+pub fn parse_pool_events(json_lines: &str) -> Result<Vec<PoolEvent>, Error> {
+    json_lines
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(line).map_err(Error::from))
+        .collect()
+}
+
+/// Reads a series of [`PoolEvent`]s from a local file of JSON lines.
+///
+/// ## Arguments
+///
+/// * `path`: The path to the file
+#[cfg(feature = "std")]
+#[inline]
+pub fn read_pool_events<P: AsRef<std::path::Path>>(path: P) -> Result<Vec<PoolEvent>, Error> {
+    parse_pool_events(&std::fs::read_to_string(path)?)
+}
+
+/// An action taken by a strategy on the positions of a [`Backtest`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StrategyAction {
+    /// Mints a position with up to the given amounts from the idle balances
+    Mint {
+        tick_lower: i32,
+        tick_upper: i32,
+        amount0: U256,
+        amount1: U256,
+    },
+    /// Burns the position at `index`, returning its amounts and fees to the idle balances
+    Burn { index: usize },
+    /// Collects the fees of the position at `index` and moves its equity to a new tick range with
+    /// [`get_rebalanced_position`], swapping the excess token at the pool price without price
+    /// impact but paying the pool fee, and returns the leftovers to the idle balances
+    Rebalance {
+        index: usize,
+        tick_lower: i32,
+        tick_upper: i32,
+    },
+}
+
+/// The state of a backtest after an event, with values denominated in token1
+#[derive(Clone, Debug, PartialEq)]
+pub struct BacktestSnapshot {
+    /// The timestamp of the event
+    pub timestamp: u64,
+    /// The pool price after the event
+    pub sqrt_price_x96: U160,
+    /// The value of the liquidity of the open positions, excluding fees
+    pub position_value: CurrencyAmount<Token>,
+    /// The value of the idle balances, including collected fees
+    pub idle_value: CurrencyAmount<Token>,
+    /// The value of the fees earned so far, collected or not
+    pub fees_earned: CurrencyAmount<Token>,
+    /// The value of the positions and idle balances excluding fees minus the value of holding the
+    /// initial balances, negative when providing liquidity underperforms
+    pub impermanent_loss: CurrencyAmount<Token>,
+    /// The gas spent on strategy actions so far
+    pub gas_spent: CurrencyAmount<Token>,
+}
+
+/// The liquidity and fee growth outside of an initialized tick
+#[derive(Clone, Copy, Debug, Default)]
+struct TickState {
+    liquidity_gross: u128,
+    liquidity_net: i128,
+    fee_growth_outside: FeeGrowthOutside<256, 4>,
+}
+
+/// Replays pool events against a pool, tracking the fee growth of every tick like the pool
+/// contract, and the positions, idle balances and fees of a strategy.
+///
+/// Swaps are replayed to the recorded price, so the price path follows history regardless of the
+/// liquidity added by the strategy, which earns its pro rata share of the fees of every step.
+#[derive(Clone, Debug)]
+pub struct Backtest {
+    token0: Token,
+    token1: Token,
+    fee: FeeAmount,
+    sqrt_price_x96: U160,
+    tick_current: i32,
+    liquidity: u128,
+    fee_growth_global0_x128: U256,
+    fee_growth_global1_x128: U256,
+    ticks: BTreeMap<i32, TickState>,
+    positions: Vec<Position>,
+    initial_balances: (U256, U256),
+    balances: (U256, U256),
+    collected_fees: (U256, U256),
+    gas_cost: U256,
+    gas_spent: U256,
+}
+
+impl Backtest {
+    /// Creates a backtest starting from the state of a pool.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool with its initialized ticks at the start of the event series
+    /// * `amount0`: The initial idle balance of token0
+    /// * `amount1`: The initial idle balance of token1
+    /// * `gas_cost`: The gas cost of a strategy action, denominated in token1
+    #[inline]
+    #[must_use]
+    pub fn new(
+        pool: &Pool<TickListDataProvider>,
+        amount0: U256,
+        amount1: U256,
+        gas_cost: U256,
+    ) -> Self {
+        let ticks = pool
+            .tick_data_provider
+            .iter()
+            .map(|tick| {
+                (
+                    tick.index,
+                    TickState {
+                        liquidity_gross: tick.liquidity_gross,
+                        liquidity_net: tick.liquidity_net,
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect();
+        Self {
+            token0: pool.token0.clone(),
+            token1: pool.token1.clone(),
+            fee: pool.fee,
+            sqrt_price_x96: pool.sqrt_ratio_x96,
+            tick_current: pool.tick_current,
+            liquidity: pool.liquidity,
+            fee_growth_global0_x128: U256::ZERO,
+            fee_growth_global1_x128: U256::ZERO,
+            ticks,
+            positions: Vec::new(),
+            initial_balances: (amount0, amount1),
+            balances: (amount0, amount1),
+            collected_fees: (U256::ZERO, U256::ZERO),
+            gas_cost,
+            gas_spent: U256::ZERO,
+        }
+    }
+
+    /// Returns the current state of the pool without tick data
+    #[inline]
+    pub fn pool(&self) -> Result<Pool, Error> {
+        let mut pool = Pool::new(
+            self.token0.clone(),
+            self.token1.clone(),
+            self.fee,
+            self.sqrt_price_x96,
+            self.liquidity,
+        )?;
+        // the tick is one below the price after crossing a tick downwards
+        pool.tick_current = self.tick_current;
+        Ok(pool)
+    }
+
+    /// Returns the open positions of the strategy
+    #[inline]
+    #[must_use]
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Returns the idle balances of token0 and token1
+    #[inline]
+    #[must_use]
+    pub const fn balances(&self) -> (U256, U256) {
+        self.balances
+    }
+
+    /// Returns the fee growth per unit of liquidity of token0 and token1 over the whole pool
+    #[inline]
+    #[must_use]
+    pub const fn fee_growth_global_x128(&self) -> (U256, U256) {
+        (self.fee_growth_global0_x128, self.fee_growth_global1_x128)
+    }
+
+    /// Returns the uncollected fees of token0 and token1 of the position at `index`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `index`: The index of the position in [`Backtest::positions`]
+    #[inline]
+    pub fn tokens_owed(&self, index: usize) -> Result<(U256, U256), Error> {
+        let position = self
+            .positions
+            .get(index)
+            .ok_or(Error::PositionNotFound(index))?;
+        let (tokens_owed0, tokens_owed1) = position.tokens_owed(
+            self.ticks[&position.tick_lower].fee_growth_outside,
+            self.ticks[&position.tick_upper].fee_growth_outside,
+            self.fee_growth_global0_x128,
+            self.fee_growth_global1_x128,
+        )?;
+        Ok((
+            U256::from_big_int(tokens_owed0.quotient()),
+            U256::from_big_int(tokens_owed1.quotient()),
+        ))
+    }
+
+    /// Applies a pool event.
+    ///
+    /// ## Arguments
+    ///
+    /// * `event`: The event to apply
+    #[inline]
+    pub fn apply_event(&mut self, event: &PoolEvent) -> Result<(), Error> {
+        match *event {
+            PoolEvent::Swap { sqrt_price_x96, .. } => self.swap_to(sqrt_price_x96)?,
+            PoolEvent::Mint {
+                tick_lower,
+                tick_upper,
+                liquidity,
+                ..
+            } => self.modify_liquidity(tick_lower, tick_upper, liquidity_delta(liquidity)?)?,
+            PoolEvent::Burn {
+                tick_lower,
+                tick_upper,
+                liquidity,
+                ..
+            } => self.modify_liquidity(tick_lower, tick_upper, -liquidity_delta(liquidity)?)?,
+        }
+        self.refresh_positions()
+    }
+
+    /// Applies a strategy action and charges its gas cost.
+    ///
+    /// ## Arguments
+    ///
+    /// * `action`: The action to apply
+    #[inline]
+    pub fn apply_action(&mut self, action: &StrategyAction) -> Result<(), Error> {
+        match *action {
+            StrategyAction::Mint {
+                tick_lower,
+                tick_upper,
+                amount0,
+                amount1,
+            } => {
+                let position = Position::from_amounts(
+                    self.pool()?,
+                    tick_lower,
+                    tick_upper,
+                    amount0.min(self.balances.0),
+                    amount1.min(self.balances.1),
+                    true,
+                )?;
+                let MintAmounts { amount0, amount1 } = position.mint_amounts()?;
+                self.balances.0 = self.balances.0.saturating_sub(amount0);
+                self.balances.1 = self.balances.1.saturating_sub(amount1);
+                self.open_position(position)?;
+            }
+            StrategyAction::Burn { index } => {
+                let position = self.close_position(index)?;
+                self.balances.0 += U256::from_big_int(position.amount0()?.quotient());
+                self.balances.1 += U256::from_big_int(position.amount1()?.quotient());
+            }
+            StrategyAction::Rebalance {
+                index,
+                tick_lower,
+                tick_upper,
+            } => {
+                let position = self.close_position(index)?;
+                let mut amount0 = U256::from_big_int(position.amount0()?.quotient());
+                let mut amount1 = U256::from_big_int(position.amount1()?.quotient());
+                let target = get_rebalanced_position(position, tick_lower, tick_upper)?;
+                let target0 = U256::from_big_int(target.amount0()?.quotient());
+                let target1 = U256::from_big_int(target.amount1()?.quotient());
+                self.balances.0 += amount0;
+                self.balances.1 += amount1;
+                if amount0 > target0 {
+                    let amount_out = self.quote(amount0 - target0, true)?;
+                    self.balances.0 -= amount0 - target0;
+                    self.balances.1 += amount_out;
+                    (amount0, amount1) = (target0, amount1 + amount_out);
+                } else if amount1 > target1 {
+                    let amount_out = self.quote(amount1 - target1, false)?;
+                    self.balances.1 -= amount1 - target1;
+                    self.balances.0 += amount_out;
+                    (amount0, amount1) = (amount0 + amount_out, target1);
+                }
+                // the swap fee leaves less than the target, so the dust stays idle
+                let position = Position::from_amounts(
+                    target.pool,
+                    tick_lower,
+                    tick_upper,
+                    amount0,
+                    amount1,
+                    true,
+                )?;
+                let MintAmounts { amount0, amount1 } = position.mint_amounts()?;
+                self.balances.0 = self.balances.0.saturating_sub(amount0);
+                self.balances.1 = self.balances.1.saturating_sub(amount1);
+                self.open_position(position)?;
+            }
+        }
+        self.gas_spent += self.gas_cost;
+        self.refresh_positions()
+    }
+
+    /// Replays the events, letting the strategy act after every event, and returns a snapshot
+    /// after every event.
+    ///
+    /// ## Arguments
+    ///
+    /// * `events`: The historical event series in chronological order
+    /// * `strategy`: Returns the actions to take given the backtest after an event and the event
+    #[inline]
+    pub fn run<'a, I, S>(
+        &mut self,
+        events: I,
+        mut strategy: S,
+    ) -> Result<Vec<BacktestSnapshot>, Error>
+    where
+        I: IntoIterator<Item = &'a PoolEvent>,
+        S: FnMut(&Self, &PoolEvent) -> Vec<StrategyAction>,
+    {
+        events
+            .into_iter()
+            .map(|event| {
+                self.apply_event(event)?;
+                for action in strategy(self, event) {
+                    self.apply_action(&action)?;
+                }
+                self.snapshot(event.timestamp())
+            })
+            .collect()
+    }
+
+    /// Values the positions, balances and fees at the current price.
+    ///
+    /// ## Arguments
+    ///
+    /// * `timestamp`: The timestamp of the snapshot
+    #[inline]
+    pub fn snapshot(&self, timestamp: u64) -> Result<BacktestSnapshot, Error> {
+        let mut amounts = (U256::ZERO, U256::ZERO);
+        let mut fees = self.collected_fees;
+        for (index, position) in self.positions.iter().enumerate() {
+            let (amount0, amount1) = position.amounts_at_sqrt_ratio(self.sqrt_price_x96)?;
+            let (tokens_owed0, tokens_owed1) = self.tokens_owed(index)?;
+            amounts = (amounts.0 + amount0, amounts.1 + amount1);
+            fees = (fees.0 + tokens_owed0, fees.1 + tokens_owed1);
+        }
+        let position_value = self.value(amounts)?;
+        let idle_value = self.value(self.balances)?;
+        let collected_fee_value = self.value(self.collected_fees)?;
+        let hodl_value = self.value(self.initial_balances)?;
+        let amount = |amount: BigInt| CurrencyAmount::from_raw_amount(self.token1.clone(), amount);
+        Ok(BacktestSnapshot {
+            timestamp,
+            sqrt_price_x96: self.sqrt_price_x96,
+            position_value: amount(position_value.to_big_int())?,
+            idle_value: amount(idle_value.to_big_int())?,
+            fees_earned: amount(self.value(fees)?.to_big_int())?,
+            impermanent_loss: amount(
+                (position_value + idle_value - collected_fee_value).to_big_int()
+                    - hodl_value.to_big_int(),
+            )?,
+            gas_spent: amount(self.gas_spent.to_big_int())?,
+        })
+    }
+
+    /// Values amounts of token0 and token1 in token1 at the current price.
+    fn value(&self, (amount0, amount1): (U256, U256)) -> Result<U256, Error> {
+        let sqrt_price_x96 = U256::from(self.sqrt_price_x96);
+        Ok(mul_div(mul_div(amount0, sqrt_price_x96, Q96)?, sqrt_price_x96, Q96)? + amount1)
+    }
+
+    /// Quotes the output of swapping an amount at the current price after the pool fee.
+    fn quote(&self, amount_in: U256, zero_for_one: bool) -> Result<U256, Error> {
+        const MAX_FEE: U256 = U256::from_limbs([1000000, 0, 0, 0]);
+        let sqrt_price_x96 = U256::from(self.sqrt_price_x96);
+        let fee: U24 = self.fee.into();
+        let amount_in = mul_div(amount_in, MAX_FEE - U256::from(fee), MAX_FEE)?;
+        if zero_for_one {
+            mul_div(
+                mul_div(amount_in, sqrt_price_x96, Q96)?,
+                sqrt_price_x96,
+                Q96,
+            )
+        } else {
+            mul_div(
+                mul_div(amount_in, Q96, sqrt_price_x96)?,
+                Q96,
+                sqrt_price_x96,
+            )
+        }
+    }
+
+    /// Returns the fee growth inside a tick range at the current tick.
+    fn fee_growth_inside(&self, tick_lower: i32, tick_upper: i32) -> (U256, U256) {
+        get_fee_growth_inside(
+            self.ticks[&tick_lower].fee_growth_outside,
+            self.ticks[&tick_upper].fee_growth_outside,
+            tick_lower,
+            tick_upper,
+            self.tick_current,
+            self.fee_growth_global0_x128,
+            self.fee_growth_global1_x128,
+        )
+    }
+
+    /// Adds the liquidity of a position to the pool and snapshots its fee growth inside.
+    fn open_position(&mut self, mut position: Position) -> Result<(), Error> {
+        self.modify_liquidity(
+            position.tick_lower,
+            position.tick_upper,
+            liquidity_delta(position.liquidity)?,
+        )?;
+        let (fee_growth_inside0_last_x128, fee_growth_inside1_last_x128) =
+            self.fee_growth_inside(position.tick_lower, position.tick_upper);
+        position.pool = self.pool()?;
+        self.positions
+            .push(position.with_fee_state(PositionFeeState {
+                fee_growth_inside0_last_x128,
+                fee_growth_inside1_last_x128,
+                tokens_owed0: 0,
+                tokens_owed1: 0,
+            }));
+        Ok(())
+    }
+
+    /// Collects the fees of a position into the idle balances and removes its liquidity from the
+    /// pool.
+    fn close_position(&mut self, index: usize) -> Result<Position, Error> {
+        if index >= self.positions.len() {
+            return Err(Error::PositionNotFound(index));
+        }
+        let (tokens_owed0, tokens_owed1) = self.tokens_owed(index)?;
+        self.balances = (
+            self.balances.0 + tokens_owed0,
+            self.balances.1 + tokens_owed1,
+        );
+        self.collected_fees = (
+            self.collected_fees.0 + tokens_owed0,
+            self.collected_fees.1 + tokens_owed1,
+        );
+        let mut position = self.positions.remove(index);
+        self.modify_liquidity(
+            position.tick_lower,
+            position.tick_upper,
+            -liquidity_delta(position.liquidity)?,
+        )?;
+        position.fee_state = None;
+        Ok(position)
+    }
+
+    /// Updates the pool state held by the positions after the pool changes.
+    fn refresh_positions(&mut self) -> Result<(), Error> {
+        if self.positions.is_empty() {
+            return Ok(());
+        }
+        let pool = self.pool()?;
+        for position in &mut self.positions {
+            position.pool = pool.clone();
+        }
+        Ok(())
+    }
+
+    /// Adds a liquidity delta to a tick range, initializing the fee growth outside of new ticks
+    /// like the pool contract.
+    fn modify_liquidity(
+        &mut self,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: i128,
+    ) -> Result<(), Error> {
+        for tick in [tick_lower, tick_upper] {
+            if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+                // clamped to the nearest invalid tick that fits in an `I24`
+                return Err(Error::InvalidTick(
+                    tick.clamp(MIN_TICK_I32 - 1, MAX_TICK_I32 + 1).to_i24(),
+                ));
+            }
+        }
+        if tick_lower >= tick_upper {
+            return Err(Error::InvalidTick(tick_upper.to_i24()));
+        }
+        for (tick, liquidity_net) in [
+            (tick_lower, liquidity_delta),
+            (tick_upper, -liquidity_delta),
+        ] {
+            let mut state = self.ticks.get(&tick).copied().unwrap_or_else(|| TickState {
+                // all fee growth is assumed to have happened below the tick
+                fee_growth_outside: if tick <= self.tick_current {
+                    FeeGrowthOutside {
+                        fee_growth_outside0_x128: self.fee_growth_global0_x128,
+                        fee_growth_outside1_x128: self.fee_growth_global1_x128,
+                    }
+                } else {
+                    FeeGrowthOutside::default()
+                },
+                ..Default::default()
+            });
+            state.liquidity_gross = add_delta(state.liquidity_gross, liquidity_delta)?;
+            state.liquidity_net = state
+                .liquidity_net
+                .checked_add(liquidity_net)
+                .ok_or(Error::AddDeltaOverflow)?;
+            if state.liquidity_gross == 0 {
+                self.ticks.remove(&tick);
+            } else {
+                self.ticks.insert(tick, state);
+            }
+        }
+        if tick_lower <= self.tick_current && self.tick_current < tick_upper {
+            self.liquidity = add_delta(self.liquidity, liquidity_delta)?;
+        }
+        Ok(())
+    }
+
+    /// Swaps to the target price with [`v3_swap_with_callback`], accruing the fees of every step
+    /// to the fee growth and crossing the initialized ticks on the way like the pool contract.
+    fn swap_to(&mut self, sqrt_price_target_x96: U160) -> Result<(), Error> {
+        // validates the target price, which must be a valid price limit
+        let tick_target = get_tick_at_sqrt_ratio(sqrt_price_target_x96)?;
+        if sqrt_price_target_x96 == MIN_SQRT_RATIO {
+            return Err(Error::InvalidSqrtPrice(sqrt_price_target_x96));
+        }
+        if sqrt_price_target_x96 == self.sqrt_price_x96 {
+            return Ok(());
+        }
+        if self.ticks.is_empty() {
+            // there is no liquidity to earn fees or ticks to cross
+            self.sqrt_price_x96 = sqrt_price_target_x96;
+            self.tick_current = tick_target.as_i32();
+            return Ok(());
+        }
+        let zero_for_one = sqrt_price_target_x96 < self.sqrt_price_x96;
+        let ticks = self
+            .ticks
+            .iter()
+            .map(|(&index, state)| Tick {
+                index,
+                liquidity_gross: state.liquidity_gross,
+                liquidity_net: state.liquidity_net,
+            })
+            .collect::<Vec<_>>();
+        let (mut fee_growth_global0_x128, mut fee_growth_global1_x128) =
+            self.fee_growth_global_x128();
+        let mut crossed_ticks = Vec::new();
+        let state = v3_swap_with_callback(
+            self.fee.into(),
+            self.sqrt_price_x96,
+            self.tick_current,
+            self.liquidity,
+            self.fee.tick_spacing().as_i32(),
+            &ticks,
+            zero_for_one,
+            I256::MAX,
+            Some(sqrt_price_target_x96),
+            |step| {
+                if step.liquidity > 0 {
+                    let fee_growth = mul_div(step.fee_amount, Q128, U256::from(step.liquidity))?;
+                    if zero_for_one {
+                        fee_growth_global0_x128 += fee_growth;
+                    } else {
+                        fee_growth_global1_x128 += fee_growth;
+                    }
+                }
+                if let Some(tick) = step.crossed_tick {
+                    crossed_ticks.push((tick, fee_growth_global0_x128, fee_growth_global1_x128));
+                }
+                Ok(())
+            },
+        )?;
+        for (tick, fee_growth_global0_x128, fee_growth_global1_x128) in crossed_ticks {
+            let outside = &mut self
+                .ticks
+                .get_mut(&tick)
+                .ok_or(Error::InvalidTick(tick.to_i24()))?
+                .fee_growth_outside;
+            outside.fee_growth_outside0_x128 =
+                fee_growth_global0_x128 - outside.fee_growth_outside0_x128;
+            outside.fee_growth_outside1_x128 =
+                fee_growth_global1_x128 - outside.fee_growth_outside1_x128;
+        }
+        self.fee_growth_global0_x128 = fee_growth_global0_x128;
+        self.fee_growth_global1_x128 = fee_growth_global1_x128;
+        self.sqrt_price_x96 = state.sqrt_price_x96;
+        self.tick_current = state.tick_current;
+        self.liquidity = state.liquidity;
+        Ok(())
+    }
+}
+
+/// Converts liquidity to a liquidity delta, failing if it does not fit in an `i128`
+fn liquidity_delta(liquidity: u128) -> Result<i128, Error> {
+    i128::try_from(liquidity).map_err(|_| Error::AddDeltaOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloc::vec;
+
+    const GAS_COST: U256 = U256::from_limbs([1000, 0, 0, 0]);
+
+    fn backtest() -> Backtest {
+        Backtest::new(
+            &make_pool(TOKEN0.clone(), TOKEN1.clone()),
+            U256::from(1_000_000),
+            U256::from(1_000_000),
+            GAS_COST,
+        )
+    }
+
+    fn swap(timestamp: u64, tick: i32) -> PoolEvent {
+        PoolEvent::Swap {
+            timestamp,
+            sqrt_price_x96: get_sqrt_ratio_at_tick(tick.to_i24()).unwrap(),
+        }
+    }
+
+    fn mint(tick_lower: i32, tick_upper: i32) -> StrategyAction {
+        StrategyAction::Mint {
+            tick_lower,
+            tick_upper,
+            amount0: U256::from(1_000_000),
+            amount1: U256::from(1_000_000),
+        }
+    }
+
+    #[test]
+    fn test_parse_pool_events() {
+        let events = [
+            swap(1, -100),
+            PoolEvent::Mint {
+                timestamp: 2,
+                tick_lower: -60,
+                tick_upper: 60,
+                liquidity: 1000,
+            },
+            PoolEvent::Burn {
+                timestamp: 3,
+                tick_lower: -60,
+                tick_upper: 60,
+                liquidity: 1000,
+            },
+        ];
+        let json_lines = events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(parse_pool_events(&json_lines).unwrap(), events);
+        assert_eq!(events[2].timestamp(), 3);
+        assert!(parse_pool_events("{\"flash\":{\"timestamp\":1}}").is_err());
+    }
+
+    #[test]
+    fn test_fees_accrue_pro_rata_in_range() {
+        let mut backtest = backtest();
+        backtest.apply_action(&mint(-600, 600)).unwrap();
+        let snapshots = backtest
+            .run(&[swap(1, -300), swap(2, 0)], |_, _| vec![])
+            .unwrap();
+        assert_eq!(snapshots.len(), 2);
+        let (fee_growth_global0_x128, fee_growth_global1_x128) = backtest.fee_growth_global_x128();
+        assert!(fee_growth_global0_x128 > U256::ZERO && fee_growth_global1_x128 > U256::ZERO);
+
+        // the price stayed in range, so the position earns the fee growth of the whole pool
+        let (tokens_owed0, tokens_owed1) = backtest.tokens_owed(0).unwrap();
+        let liquidity = U256::from(backtest.positions()[0].liquidity);
+        assert_eq!(
+            tokens_owed0,
+            mul_div(fee_growth_global0_x128, liquidity, Q128).unwrap()
+        );
+        assert_eq!(
+            tokens_owed1,
+            mul_div(fee_growth_global1_x128, liquidity, Q128).unwrap()
+        );
+
+        // the price is back to the entry price, so there is no impermanent loss beyond rounding
+        let snapshot = snapshots.last().unwrap();
+        assert_eq!(snapshot.sqrt_price_x96, SQRT_RATIO_X96);
+        assert!(snapshot.impermanent_loss.quotient().abs() <= BigInt::from(2));
+        assert!(snapshot.fees_earned.quotient() > BigInt::ZERO);
+        assert_eq!(snapshot.gas_spent.quotient(), GAS_COST.to_big_int());
+
+        // burning returns the liquidity and the fees to the idle balances
+        backtest
+            .apply_action(&StrategyAction::Burn { index: 0 })
+            .unwrap();
+        assert!(backtest.positions().is_empty());
+        let (balance0, balance1) = backtest.balances();
+        assert!(balance0 + U256::from(2) >= U256::from(1_000_000) + tokens_owed0);
+        assert!(balance1 + U256::from(2) >= U256::from(1_000_000) + tokens_owed1);
+        let snapshot = backtest.snapshot(3).unwrap();
+        assert_eq!(snapshot.fees_earned, snapshots[1].fees_earned);
+        assert!(snapshot.position_value.quotient().is_zero());
+    }
+
+    #[test]
+    fn test_no_fees_out_of_range() {
+        let mut backtest = backtest();
+        backtest.apply_action(&mint(600, 1200)).unwrap();
+        backtest
+            .run(&[swap(1, -300), swap(2, 300)], |_, _| vec![])
+            .unwrap();
+        assert_eq!(backtest.tokens_owed(0).unwrap(), (U256::ZERO, U256::ZERO));
+
+        // crossing into the range earns fees only while in range
+        backtest
+            .run(&[swap(3, 900), swap(4, 1500)], |_, _| vec![])
+            .unwrap();
+        let (tokens_owed0, tokens_owed1) = backtest.tokens_owed(0).unwrap();
+        assert!(tokens_owed0.is_zero());
+        assert!(tokens_owed1 > U256::ZERO);
+        let (_, fee_growth_global1_x128) = backtest.fee_growth_global_x128();
+        let liquidity = backtest.positions()[0].liquidity;
+        assert!(
+            tokens_owed1 < mul_div(fee_growth_global1_x128, U256::from(liquidity), Q128).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_rebalance_strategy() {
+        let mut backtest = backtest();
+        let events = [swap(1, 0), swap(2, -900), swap(3, -1200)];
+        let snapshots = backtest
+            .run(&events, |backtest, _| {
+                let tick = backtest.pool().unwrap().tick_current;
+                let tick = tick.div_euclid(60) * 60;
+                match backtest.positions().first() {
+                    None => vec![mint(tick - 600, tick + 600)],
+                    Some(position) if tick < position.tick_lower || tick >= position.tick_upper => {
+                        vec![StrategyAction::Rebalance {
+                            index: 0,
+                            tick_lower: tick - 600,
+                            tick_upper: tick + 600,
+                        }]
+                    }
+                    Some(_) => vec![],
+                }
+            })
+            .unwrap();
+        // minted at tick 0 and rebalanced at tick -900
+        assert_eq!(backtest.positions().len(), 1);
+        assert_eq!(backtest.positions()[0].tick_lower, -1500);
+        assert_eq!(
+            snapshots.last().unwrap().gas_spent.quotient(),
+            (GAS_COST * U256::from(2)).to_big_int()
+        );
+        // the fees of the first range were collected on rebalance
+        assert!(snapshots[1].idle_value.quotient() > snapshots[0].idle_value.quotient());
+    }
+
+    #[test]
+    fn test_rebalance_pays_swap_fee() {
+        let mut backtest = backtest();
+        backtest.apply_event(&swap(1, -900)).unwrap();
+        backtest.apply_action(&mint(0, 600)).unwrap();
+        let before = backtest.snapshot(1).unwrap();
+        let total = |snapshot: &BacktestSnapshot| {
+            snapshot.position_value.quotient() + snapshot.idle_value.quotient()
+        };
+        backtest
+            .apply_action(&StrategyAction::Rebalance {
+                index: 0,
+                tick_lower: -1500,
+                tick_upper: -300,
+            })
+            .unwrap();
+        let after = backtest.snapshot(1).unwrap();
+        // the position above the price is all token0, so part of it is swapped to token1 at the
+        // 0.3% fee without earning fees
+        let loss = total(&before) - total(&after);
+        assert!(loss > BigInt::ZERO);
+        assert!(loss * BigInt::from(1000) <= before.position_value.quotient() * BigInt::from(3));
+        // the leftovers of the swap and the mint are credited to the idle balances
+        assert!(after.idle_value.quotient() >= before.idle_value.quotient());
+    }
+
+    #[test]
+    fn test_position_not_found() {
+        let mut backtest = backtest();
+        assert!(matches!(
+            backtest.tokens_owed(0),
+            Err(Error::PositionNotFound(0))
+        ));
+        assert!(matches!(
+            backtest.apply_action(&StrategyAction::Burn { index: 1 }),
+            Err(Error::PositionNotFound(1))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_liquidity_events() {
+        let mut backtest = backtest();
+        assert!(matches!(
+            backtest.apply_event(&PoolEvent::Mint {
+                timestamp: 1,
+                tick_lower: 60,
+                tick_upper: -60,
+                liquidity: 1000,
+            }),
+            Err(Error::InvalidTick(tick)) if tick == (-60).to_i24()
+        ));
+        assert!(matches!(
+            backtest.apply_event(&PoolEvent::Mint {
+                timestamp: 1,
+                tick_lower: -60,
+                tick_upper: i32::MAX,
+                liquidity: 1000,
+            }),
+            Err(Error::InvalidTick(tick)) if tick == (MAX_TICK_I32 + 1).to_i24()
+        ));
+        assert!(matches!(
+            backtest.apply_event(&PoolEvent::Burn {
+                timestamp: 1,
+                tick_lower: -60,
+                tick_upper: 60,
+                liquidity: u128::MAX,
+            }),
+            Err(Error::AddDeltaOverflow)
+        ));
+    }
+}
@@ -1,21 +1,35 @@
 //! Extensions to the core library.
 
+mod backtest;
 mod ephemeral_tick_data_provider;
 mod ephemeral_tick_map_data_provider;
+mod permit;
 mod pool;
 mod position;
+mod position_analytics;
 mod price_tick_conversions;
+mod sandwich;
 mod state_overrides;
 mod tick_bit_map;
 mod tick_map;
+mod transaction;
+mod typed_data;
+mod zap;
 
+pub use backtest::*;
 pub use ephemeral_tick_data_provider::EphemeralTickDataProvider;
 pub use ephemeral_tick_map_data_provider::EphemeralTickMapDataProvider;
+pub use permit::*;
 pub use pool::*;
 pub use position::*;
+pub use position_analytics::*;
 pub use price_tick_conversions::*;
+pub use sandwich::*;
 pub use state_overrides::*;
 pub use tick_bit_map::*;
 pub use tick_map::*;
+pub use transaction::*;
+pub use typed_data::*;
+pub use zap::*;
 
 pub use uniswap_lens as lens;
@@ -0,0 +1,889 @@
+//! ## Permit
+//! This module provides functions to sign EIP-2612 and DAI permits for ERC20 tokens and EIP-4494
+//! permits for position NFTs with an alloy [`Signer`], fetching the nonce and the EIP-712 domain
+//! through the provider. The domain and the flavor of an ERC20 permit are discovered on chain and
+//! the domain is verified against the `DOMAIN_SEPARATOR` of the token.
+
+use crate::prelude::{Error, *};
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use alloy::{
+    contract::Error as ContractError,
+    eips::{BlockId, BlockNumberOrTag},
+    network::Network,
+    providers::Provider,
+    signers::Signer,
+    sol,
+};
+use alloy_primitives::{keccak256, Address, B256, U256};
+use alloy_sol_types::{Eip712Domain, SolStruct};
+
+sol! {
+    #[sol(rpc)]
+    interface IERC20PermitMetadata {
+        function name() external view returns (string);
+        function version() external view returns (string);
+        function nonces(address owner) external view returns (uint256);
+        function DOMAIN_SEPARATOR() external view returns (bytes32);
+        function PERMIT_TYPEHASH() external view returns (bytes32);
+        function eip712Domain() external view returns (
+            bytes1 fields,
+            string name,
+            string version,
+            uint256 chainId,
+            address verifyingContract,
+            bytes32 salt,
+            uint256[] extensions
+        );
+    }
+}
+
+/// The flavor of an ERC20 permit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ERC20PermitKind {
+    /// An EIP-2612 permit of an amount, redeemed with `selfPermit`.
+    Standard,
+    /// A DAI permit of all of the tokens, redeemed with `selfPermitAllowed`.
+    Allowed,
+}
+
+/// The permit flavor and the verified EIP-712 domain of an ERC20 token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ERC20PermitInfo {
+    pub kind: ERC20PermitKind,
+    pub domain: Eip712Domain,
+}
+
+/// The permit data of an ERC20 token of either [`ERC20PermitKind`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnyERC20PermitData {
+    Standard(ERC20PermitData<IERC20Permit::Permit>),
+    Allowed(ERC20PermitData<IDaiPermit::Permit>),
+}
+
+/// Builds the EIP-712 domain described by an EIP-5267 `eip712Domain()` result, keeping only the
+/// fields flagged in its `fields` bitmap.
+fn eip5267_domain(domain: IERC20PermitMetadata::eip712DomainReturn) -> Eip712Domain {
+    let fields = domain.fields.0[0];
+    Eip712Domain::new(
+        (fields & 0x01 != 0).then_some(Cow::Owned(domain.name)),
+        (fields & 0x02 != 0).then_some(Cow::Owned(domain.version)),
+        (fields & 0x04 != 0).then_some(domain.chainId),
+        (fields & 0x08 != 0).then_some(domain.verifyingContract),
+        (fields & 0x10 != 0).then_some(domain.salt),
+    )
+}
+
+/// Returns the candidate EIP-712 domains of a token from its name and optional version. Tokens
+/// without a `version()` getter usually sign with version `1` or `2`, or leave it out.
+fn erc20_permit_domain_candidates(
+    name: &str,
+    version: Option<String>,
+    chain_id: u64,
+    token: Address,
+) -> Vec<Eip712Domain> {
+    let mut versions: Vec<Option<String>> = version.into_iter().map(Some).collect();
+    for fallback in [Some("1".into()), Some("2".into()), None] {
+        if !versions.contains(&fallback) {
+            versions.push(fallback);
+        }
+    }
+    versions
+        .into_iter()
+        .map(|version| {
+            Eip712Domain::new(
+                Some(Cow::Owned(name.into())),
+                version.map(Cow::Owned),
+                Some(U256::from(chain_id)),
+                Some(token),
+                None,
+            )
+        })
+        .collect()
+}
+
+/// Returns the result of a getter the token may not implement, or `None` if the call reverts or
+/// its return data does not decode, propagating other errors such as transport failures.
+fn optional_getter<T>(result: Result<T, ContractError>) -> Result<Option<T>, Error> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(ContractError::ZeroData(..) | ContractError::AbiError(_)) => Ok(None),
+        Err(ContractError::TransportError(e)) if is_revert(&e) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Fetches the EIP-712 domain of an ERC20 permit, from EIP-5267 `eip712Domain()` if implemented
+/// or from the token name and version otherwise, and verifies it against `DOMAIN_SEPARATOR()`.
+async fn fetch_erc20_permit_domain<N, P>(
+    token: Address,
+    provider: &P,
+    block_id: BlockId,
+) -> Result<Eip712Domain, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let contract = IERC20PermitMetadata::new(token, provider);
+    let domain_separator =
+        optional_getter(contract.DOMAIN_SEPARATOR().block(block_id).call().await)?
+            .ok_or(Error::UnsupportedPermit(token))?
+            ._0;
+    if let Some(domain) = optional_getter(contract.eip712Domain().block(block_id).call().await)? {
+        let domain = eip5267_domain(domain);
+        if domain.separator() == domain_separator {
+            return Ok(domain);
+        }
+    }
+    let name = optional_getter(contract.name().block(block_id).call().await)?
+        .ok_or(Error::UnsupportedPermit(token))?
+        ._0;
+    let version =
+        optional_getter(contract.version().block(block_id).call().await)?.map(|version| version._0);
+    let chain_id = provider.get_chain_id().await?;
+    erc20_permit_domain_candidates(&name, version, chain_id, token)
+        .into_iter()
+        .find(|domain| domain.separator() == domain_separator)
+        .ok_or(Error::UnsupportedPermit(token))
+}
+
+/// Returns the permit flavor of a `PERMIT_TYPEHASH()`, defaulting to EIP-2612 for tokens which
+/// do not expose it.
+fn erc20_permit_kind(permit_typehash: Option<B256>) -> Option<ERC20PermitKind> {
+    match permit_typehash {
+        None => Some(ERC20PermitKind::Standard),
+        Some(typehash)
+            if typehash == keccak256(IERC20Permit::Permit::eip712_encode_type().as_bytes()) =>
+        {
+            Some(ERC20PermitKind::Standard)
+        }
+        Some(typehash)
+            if typehash == keccak256(IDaiPermit::Permit::eip712_encode_type().as_bytes()) =>
+        {
+            Some(ERC20PermitKind::Allowed)
+        }
+        Some(_) => None,
+    }
+}
+
+/// Inspects an ERC20 token to detect its permit flavor and its EIP-712 domain, which is verified
+/// against the on-chain `DOMAIN_SEPARATOR()`.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn fetch_erc20_permit_info<N, P>(
+    token: Address,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<ERC20PermitInfo, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let domain = fetch_erc20_permit_domain(token, &provider, block_id).await?;
+    let permit_typehash = optional_getter(
+        IERC20PermitMetadata::new(token, &provider)
+            .PERMIT_TYPEHASH()
+            .block(block_id)
+            .call()
+            .await,
+    )?
+    .map(|typehash| typehash._0);
+    let kind = erc20_permit_kind(permit_typehash).ok_or(Error::UnsupportedPermit(token))?;
+    Ok(ERC20PermitInfo { kind, domain })
+}
+
+/// Fetches the permit data of `owner` approving `spender` to spend `amount` of `token` until
+/// `deadline`, in the permit flavor of the token. DAI permits allow the spender to spend all of
+/// the tokens, with `deadline` as the expiry.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `owner`: The owner of the tokens
+/// * `spender`: The spender to approve
+/// * `amount`: The amount to approve, ignored by DAI permits
+/// * `deadline`: The deadline of the permit, in epoch seconds
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn fetch_any_erc20_permit_data<N, P>(
+    token: Address,
+    owner: Address,
+    spender: Address,
+    amount: U256,
+    deadline: U256,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<AnyERC20PermitData, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let ERC20PermitInfo { kind, domain } =
+        fetch_erc20_permit_info(token, &provider, Some(block_id)).await?;
+    let nonce = IERC20PermitMetadata::new(token, &provider)
+        .nonces(owner)
+        .block(block_id)
+        .call()
+        .await?
+        ._0;
+    Ok(match kind {
+        ERC20PermitKind::Standard => AnyERC20PermitData::Standard(ERC20PermitData {
+            domain,
+            values: IERC20Permit::Permit {
+                owner,
+                spender,
+                value: amount,
+                nonce,
+                deadline,
+            },
+        }),
+        ERC20PermitKind::Allowed => AnyERC20PermitData::Allowed(ERC20PermitData {
+            domain,
+            values: IDaiPermit::Permit {
+                holder: owner,
+                spender,
+                nonce,
+                expiry: deadline,
+                allowed: true,
+            },
+        }),
+    })
+}
+
+/// Fetches the EIP-2612 permit data of `owner` approving `spender` to spend `amount` of `token`
+/// until `deadline`.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `owner`: The owner of the tokens
+/// * `spender`: The spender to approve
+/// * `amount`: The amount to approve
+/// * `deadline`: The deadline of the permit, in epoch seconds
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn fetch_erc20_permit_data<N, P>(
+    token: Address,
+    owner: Address,
+    spender: Address,
+    amount: U256,
+    deadline: U256,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<ERC20PermitData<IERC20Permit::Permit>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let domain = fetch_erc20_permit_domain(token, &provider, block_id).await?;
+    let nonce = IERC20PermitMetadata::new(token, &provider)
+        .nonces(owner)
+        .block(block_id)
+        .call()
+        .await?
+        ._0;
+    Ok(ERC20PermitData {
+        domain,
+        values: IERC20Permit::Permit {
+            owner,
+            spender,
+            value: amount,
+            nonce,
+            deadline,
+        },
+    })
+}
+
+/// Fetches the DAI permit data of `holder` allowing `spender` to spend all of `token` until
+/// `expiry`.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `holder`: The holder of the tokens
+/// * `spender`: The spender to allow
+/// * `expiry`: The expiry of the permit, in epoch seconds
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn fetch_dai_permit_data<N, P>(
+    token: Address,
+    holder: Address,
+    spender: Address,
+    expiry: U256,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<ERC20PermitData<IDaiPermit::Permit>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let domain = fetch_erc20_permit_domain(token, &provider, block_id).await?;
+    let nonce = IERC20PermitMetadata::new(token, &provider)
+        .nonces(holder)
+        .block(block_id)
+        .call()
+        .await?
+        ._0;
+    Ok(ERC20PermitData {
+        domain,
+        values: IDaiPermit::Permit {
+            holder,
+            spender,
+            nonce,
+            expiry,
+            allowed: true,
+        },
+    })
+}
+
+/// Fetches the permit data approving `spender` to manage the position `token_id` until
+/// `deadline`.
+///
+/// ## Arguments
+///
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `token_id`: The token id of the position
+/// * `spender`: The spender to approve
+/// * `deadline`: The deadline of the permit, in epoch seconds
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn fetch_nft_permit_data<N, P>(
+    nonfungible_position_manager: Address,
+    token_id: U256,
+    spender: Address,
+    deadline: U256,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<NFTPermitData, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let chain_id = provider.get_chain_id().await?;
+    let nonce = get_nonfungible_position_manager_contract(nonfungible_position_manager, provider)
+        .positions(token_id)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?
+        .nonce;
+    Ok(get_permit_data(
+        NFTPermitValues {
+            spender,
+            tokenId: token_id,
+            nonce: U256::from(nonce),
+            deadline,
+        },
+        nonfungible_position_manager,
+        chain_id,
+    ))
+}
+
+impl ERC20PermitData<IERC20Permit::Permit> {
+    /// Signs the permit, returning the [`PermitOptions`] of a `selfPermit` call.
+    ///
+    /// ## Arguments
+    ///
+    /// * `signer`: The signer, which must be the owner of the tokens
+    #[inline]
+    pub async fn sign<S: Signer + Sync>(&self, signer: &S) -> Result<PermitOptions, Error> {
+        if signer.address() != self.values.owner {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(PermitOptions::Standard(StandardPermitArguments {
+            signature: signer.sign_hash(&self.eip712_signing_hash()).await?,
+            amount: self.values.value,
+            deadline: self.values.deadline,
+        }))
+    }
+}
+
+impl ERC20PermitData<IDaiPermit::Permit> {
+    /// Signs the permit, returning the [`PermitOptions`] of a `selfPermitAllowed` call.
+    ///
+    /// ## Arguments
+    ///
+    /// * `signer`: The signer, which must be the holder of the tokens
+    #[inline]
+    pub async fn sign<S: Signer + Sync>(&self, signer: &S) -> Result<PermitOptions, Error> {
+        if signer.address() != self.values.holder {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(PermitOptions::Allowed(AllowedPermitArguments {
+            signature: signer.sign_hash(&self.eip712_signing_hash()).await?,
+            nonce: self.values.nonce,
+            expiry: self.values.expiry,
+        }))
+    }
+}
+
+impl AnyERC20PermitData {
+    /// Returns the flavor of the permit.
+    #[inline]
+    #[must_use]
+    pub const fn kind(&self) -> ERC20PermitKind {
+        match self {
+            Self::Standard(_) => ERC20PermitKind::Standard,
+            Self::Allowed(_) => ERC20PermitKind::Allowed,
+        }
+    }
+
+    /// Returns the EIP-712 domain of the permit.
+    #[inline]
+    #[must_use]
+    pub const fn domain(&self) -> &Eip712Domain {
+        match self {
+            Self::Standard(data) => &data.domain,
+            Self::Allowed(data) => &data.domain,
+        }
+    }
+
+    /// Returns the EIP-712 signing hash of the permit, to be signed by the owner of the tokens.
+    #[inline]
+    #[must_use]
+    pub fn eip712_signing_hash(&self) -> B256 {
+        match self {
+            Self::Standard(data) => data.eip712_signing_hash(),
+            Self::Allowed(data) => data.eip712_signing_hash(),
+        }
+    }
+
+    /// Serializes the permit to EIP-712 typed data JSON, for signing by a wallet.
+    #[inline]
+    pub fn to_typed_data_json(&self) -> Result<String, Error> {
+        match self {
+            Self::Standard(data) => data.to_typed_data_json(),
+            Self::Allowed(data) => data.to_typed_data_json(),
+        }
+    }
+
+    /// Signs the permit, returning the [`PermitOptions`] of a `selfPermit` or `selfPermitAllowed`
+    /// call.
+    ///
+    /// ## Arguments
+    ///
+    /// * `signer`: The signer, which must be the owner of the tokens
+    #[inline]
+    pub async fn sign<S: Signer + Sync>(&self, signer: &S) -> Result<PermitOptions, Error> {
+        match self {
+            Self::Standard(data) => data.sign(signer).await,
+            Self::Allowed(data) => data.sign(signer).await,
+        }
+    }
+}
+
+impl NFTPermitData {
+    /// Signs the permit, returning the [`NFTPermitOptions`] of a `permit` call.
+    ///
+    /// ## Arguments
+    ///
+    /// * `signer`: The signer, which must own or be approved for the position
+    #[inline]
+    pub async fn sign<S: Signer + Sync>(&self, signer: &S) -> Result<NFTPermitOptions, Error> {
+        Ok(NFTPermitOptions {
+            signature: signer.sign_hash(&self.eip712_signing_hash()).await?,
+            deadline: self.values.deadline,
+            spender: self.values.spender,
+        })
+    }
+}
+
+/// Signs an EIP-2612 permit approving `spender` to spend `amount` of `token` held by the signer
+/// until `deadline`, fetching the nonce and the domain through the provider.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `spender`: The spender to approve
+/// * `amount`: The amount to approve
+/// * `deadline`: The deadline of the permit, in epoch seconds
+/// * `signer`: The signer holding the tokens
+/// * `provider`: The alloy provider
+#[inline]
+pub async fn sign_erc20_permit<N, P, S>(
+    token: Address,
+    spender: Address,
+    amount: U256,
+    deadline: U256,
+    signer: &S,
+    provider: P,
+) -> Result<PermitOptions, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    S: Signer + Sync,
+{
+    fetch_erc20_permit_data(
+        token,
+        signer.address(),
+        spender,
+        amount,
+        deadline,
+        provider,
+        None,
+    )
+    .await?
+    .sign(signer)
+    .await
+}
+
+/// Signs a DAI permit allowing `spender` to spend all of `token` held by the signer until
+/// `expiry`, fetching the nonce and the domain through the provider.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `spender`: The spender to allow
+/// * `expiry`: The expiry of the permit, in epoch seconds
+/// * `signer`: The signer holding the tokens
+/// * `provider`: The alloy provider
+#[inline]
+pub async fn sign_dai_permit<N, P, S>(
+    token: Address,
+    spender: Address,
+    expiry: U256,
+    signer: &S,
+    provider: P,
+) -> Result<PermitOptions, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    S: Signer + Sync,
+{
+    fetch_dai_permit_data(token, signer.address(), spender, expiry, provider, None)
+        .await?
+        .sign(signer)
+        .await
+}
+
+/// Signs a permit approving `spender` to manage the position `token_id` until `deadline`,
+/// fetching the nonce through the provider.
+///
+/// ## Arguments
+///
+/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `token_id`: The token id of the position
+/// * `spender`: The spender to approve
+/// * `deadline`: The deadline of the permit, in epoch seconds
+/// * `signer`: The signer owning or approved for the position
+/// * `provider`: The alloy provider
+#[inline]
+pub async fn sign_nft_permit<N, P, S>(
+    nonfungible_position_manager: Address,
+    token_id: U256,
+    spender: Address,
+    deadline: U256,
+    signer: &S,
+    provider: P,
+) -> Result<NFTPermitOptions, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    S: Signer + Sync,
+{
+    fetch_nft_permit_data(
+        nonfungible_position_manager,
+        token_id,
+        spender,
+        deadline,
+        provider,
+        None,
+    )
+    .await?
+    .sign(signer)
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy_primitives::{address, b256, uint, FixedBytes};
+    use uniswap_sdk_core::prelude::{BaseCurrency, NONFUNGIBLE_POSITION_MANAGER_ADDRESSES};
+
+    const SPENDER: Address = address!("0000000000000000000000000000000000000002");
+    const DEADLINE: U256 = uint!(123_U256);
+
+    #[tokio::test]
+    async fn test_sign_erc20_permit_data() {
+        let signer = PrivateKeySigner::random();
+        let data = get_erc20_permit_data(
+            IERC20Permit::Permit {
+                owner: signer.address(),
+                spender: SPENDER,
+                value: uint!(1000_U256),
+                nonce: U256::ZERO,
+                deadline: DEADLINE,
+            },
+            "USD Coin",
+            "2",
+            USDC.address(),
+            1,
+        );
+        let PermitOptions::Standard(args) = data.sign(&signer).await.unwrap() else {
+            panic!("expected a standard permit");
+        };
+        assert_eq!(args.amount, uint!(1000_U256));
+        assert_eq!(args.deadline, DEADLINE);
+        assert_eq!(
+            args.signature
+                .recover_address_from_prehash(&data.eip712_signing_hash())
+                .unwrap(),
+            signer.address()
+        );
+        assert!(matches!(
+            data.sign(&PrivateKeySigner::random()).await,
+            Err(Error::InvalidSignature)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_dai_permit_data() {
+        let signer = PrivateKeySigner::random();
+        let data = get_erc20_permit_data(
+            IDaiPermit::Permit {
+                holder: signer.address(),
+                spender: SPENDER,
+                nonce: uint!(1_U256),
+                expiry: DEADLINE,
+                allowed: true,
+            },
+            "Dai Stablecoin",
+            "1",
+            DAI.address(),
+            1,
+        );
+        let PermitOptions::Allowed(args) = data.sign(&signer).await.unwrap() else {
+            panic!("expected an allowed permit");
+        };
+        assert_eq!(args.nonce, uint!(1_U256));
+        assert_eq!(args.expiry, DEADLINE);
+        assert_eq!(
+            args.signature
+                .recover_address_from_prehash(&data.eip712_signing_hash())
+                .unwrap(),
+            signer.address()
+        );
+    }
+
+    #[test]
+    fn test_eip5267_domain() {
+        let domain = eip5267_domain(IERC20PermitMetadata::eip712DomainReturn {
+            fields: FixedBytes([0x0d]),
+            name: "USD Coin".into(),
+            version: "2".into(),
+            chainId: uint!(1_U256),
+            verifyingContract: USDC.address(),
+            salt: B256::repeat_byte(1),
+            extensions: vec![],
+        });
+        assert_eq!(
+            domain,
+            Eip712Domain::new(
+                Some("USD Coin".into()),
+                None,
+                Some(uint!(1_U256)),
+                Some(USDC.address()),
+                None,
+            )
+        );
+    }
+
+    #[test]
+    fn test_erc20_permit_domain_candidates() {
+        let expected = Eip712Domain::new(
+            Some("Uniswap".into()),
+            None,
+            Some(uint!(1_U256)),
+            Some(USDC.address()),
+            None,
+        );
+        let candidates = erc20_permit_domain_candidates("Uniswap", None, 1, USDC.address());
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(
+            candidates
+                .into_iter()
+                .find(|domain| domain.separator() == expected.separator()),
+            Some(expected)
+        );
+        let candidates =
+            erc20_permit_domain_candidates("USD Coin", Some("2".into()), 1, USDC.address());
+        assert_eq!(candidates.len(), 3);
+        assert_eq!(candidates[0].version.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_optional_getter() {
+        use alloy::{
+            rpc::json_rpc::{ErrorPayload, RpcError},
+            transports::TransportErrorKind,
+        };
+
+        assert_eq!(optional_getter(Ok(1)).unwrap(), Some(1));
+        let revert = RpcError::ErrorResp(ErrorPayload {
+            code: 3,
+            message: "execution reverted".into(),
+            data: None,
+        });
+        assert_eq!(
+            optional_getter::<()>(Err(ContractError::TransportError(revert))).unwrap(),
+            None
+        );
+        let abi_error = alloy_sol_types::Error::Overrun.into();
+        assert_eq!(
+            optional_getter::<()>(Err(ContractError::AbiError(abi_error))).unwrap(),
+            None
+        );
+        // transport failures are not mistaken for a missing getter
+        let transport_error = TransportErrorKind::custom_str("connection refused");
+        assert!(
+            optional_getter::<()>(Err(ContractError::TransportError(transport_error))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_erc20_permit_kind() {
+        assert_eq!(erc20_permit_kind(None), Some(ERC20PermitKind::Standard));
+        assert_eq!(
+            erc20_permit_kind(Some(b256!(
+                "6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9"
+            ))),
+            Some(ERC20PermitKind::Standard)
+        );
+        assert_eq!(
+            erc20_permit_kind(Some(b256!(
+                "ea2aa0a1be11a07ed86d755c93467f4f82362b452371d1ba94d1715123511acb"
+            ))),
+            Some(ERC20PermitKind::Allowed)
+        );
+        assert_eq!(erc20_permit_kind(Some(B256::ZERO)), None);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_erc20_permit_info() {
+        let info = fetch_erc20_permit_info(USDC.address(), PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(info.kind, ERC20PermitKind::Standard);
+        assert_eq!(info.domain.version.as_deref(), Some("2"));
+        let info = fetch_erc20_permit_info(DAI.address(), PROVIDER.clone(), *BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(info.kind, ERC20PermitKind::Allowed);
+        assert_eq!(info.domain.name.as_deref(), Some("Dai Stablecoin"));
+        assert!(matches!(
+            fetch_erc20_permit_info(TOKEN0.address(), PROVIDER.clone(), *BLOCK_ID).await,
+            Err(Error::UnsupportedPermit(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_any_erc20_permit_data() {
+        let signer = PrivateKeySigner::random();
+        let data = fetch_any_erc20_permit_data(
+            DAI.address(),
+            signer.address(),
+            SPENDER,
+            uint!(1000_U256),
+            DEADLINE,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(data.kind(), ERC20PermitKind::Allowed);
+        assert!(matches!(
+            data.sign(&signer).await.unwrap(),
+            PermitOptions::Allowed(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sign_erc20_permit() {
+        let signer = PrivateKeySigner::random();
+        let data = fetch_erc20_permit_data(
+            USDC.address(),
+            signer.address(),
+            SPENDER,
+            uint!(1000_U256),
+            DEADLINE,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(data.domain.name.as_deref(), Some("USD Coin"));
+        assert_eq!(data.domain.version.as_deref(), Some("2"));
+        assert_eq!(data.values.nonce, U256::ZERO);
+        let options = sign_erc20_permit(
+            USDC.address(),
+            SPENDER,
+            uint!(1000_U256),
+            DEADLINE,
+            &signer,
+            PROVIDER.clone(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(options, data.sign(&signer).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sign_dai_permit() {
+        let signer = PrivateKeySigner::random();
+        let data = fetch_dai_permit_data(
+            DAI.address(),
+            signer.address(),
+            SPENDER,
+            DEADLINE,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(data.domain.name.as_deref(), Some("Dai Stablecoin"));
+        assert_eq!(data.domain.version.as_deref(), Some("1"));
+        let options = sign_dai_permit(DAI.address(), SPENDER, DEADLINE, &signer, PROVIDER.clone())
+            .await
+            .unwrap();
+        assert!(matches!(options, PermitOptions::Allowed(_)));
+    }
+
+    #[tokio::test]
+    async fn test_sign_nft_permit() {
+        let signer = PrivateKeySigner::random();
+        let npm = *NONFUNGIBLE_POSITION_MANAGER_ADDRESSES.get(&1).unwrap();
+        let data = fetch_nft_permit_data(
+            npm,
+            uint!(4_U256),
+            SPENDER,
+            DEADLINE,
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        let options = data.sign(&signer).await.unwrap();
+        assert_eq!(options.spender, SPENDER);
+        assert_eq!(options.deadline, DEADLINE);
+        assert_eq!(
+            options
+                .signature
+                .recover_address_from_prehash(&data.eip712_signing_hash())
+                .unwrap(),
+            signer.address()
+        );
+    }
+}
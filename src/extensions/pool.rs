@@ -1,19 +1,30 @@
 //! ## Pool Extension
-//! This module provides functions to create a [`Pool`] struct from a pool key and to fetch the
+//! This module provides functions to create a [`Pool`] struct from a pool key, or many pools in a
+//! few `Multicall3` batches with a [`PoolLoader`], and to fetch the
 //! liquidity map within a tick range for the specified pool using an [ephemeral contract](https://github.com/Aperture-Finance/Aperture-Lens/blob/904101e4daed59e02fd4b758b98b0749e70b583b/contracts/EphemeralGetPopulatedTicksInRange.sol)
 //! in a single `eth_call`.
 
-use crate::prelude::*;
-use alloc::{string::ToString, vec, vec::Vec};
+use crate::prelude::{Error, *};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use alloy::{
     eips::{BlockId, BlockNumberOrTag},
-    network::Network,
-    providers::Provider,
+    network::{Network, TransactionBuilder},
+    providers::{bindings::IMulticall3, Failure, MulticallError, Provider, MULTICALL3_ADDRESS},
 };
-use alloy_primitives::{Address, ChainId, B256};
+use alloy_primitives::{aliases::U24, Address, ChainId, B256};
+use alloy_sol_types::{SolCall, SolValue};
+use core::marker::PhantomData;
+use futures::{stream, StreamExt};
 use uniswap_lens::{
     bindings::{
-        ierc20metadata::IERC20Metadata, iuniswapv3pool::IUniswapV3Pool::IUniswapV3PoolInstance,
+        ierc20metadata::IERC20Metadata,
+        iuniswapv3factory::IUniswapV3Factory,
+        iuniswapv3pool::IUniswapV3Pool::{self, IUniswapV3PoolInstance},
     },
     pool_lens,
 };
@@ -37,13 +48,144 @@ where
     )
 }
 
+/// Returns the address of a pool of `factory`, or of the factory of the [`Deployment`] on the
+/// chain if not given.
+fn get_pool_address(
+    chain_id: ChainId,
+    factory: Option<Address>,
+    token_a: Address,
+    token_b: Address,
+    fee: FeeAmount,
+) -> Result<Address, Error> {
+    let (factory, init_code_hash) = match factory {
+        Some(factory) => (factory, None),
+        None => {
+            let deployment = get_deployment(chain_id).ok_or(Error::UnknownDeployment(chain_id))?;
+            (deployment.factory, Some(deployment.pool_init_code_hash))
+        }
+    };
+    Ok(compute_pool_address(
+        factory,
+        token_a,
+        token_b,
+        fee,
+        init_code_hash,
+        Some(chain_id),
+    ))
+}
+
+/// Loads a fee tier with its tick spacing from the factory's `feeAmountTickSpacing`, e.g. to
+/// pass to [`register_fee_tier`].
+///
+/// ## Arguments
+///
+/// * `factory`: The factory address
+/// * `fee`: The fee in hundredths of bips
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query.
+///
+/// ## Returns
+///
+/// The fee tier, or [`Error::UnknownFeeTier`] if the fee is not enabled on the factory
+#[inline]
+pub async fn get_fee_amount_from_factory<N, P>(
+    factory: Address,
+    fee: U24,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<FeeAmount, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let tick_spacing = IUniswapV3Factory::new(factory, provider)
+        .feeAmountTickSpacing(fee)
+        .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+        .call()
+        .await?
+        ._0;
+    if tick_spacing.is_zero() {
+        return Err(Error::UnknownFeeTier(fee));
+    }
+    Ok(match FeeAmount::try_from(fee) {
+        Ok(fee_amount) if fee_amount.tick_spacing() == tick_spacing => fee_amount,
+        _ => FeeAmount::new_custom(fee.to::<u32>(), tick_spacing.as_i32()),
+    })
+}
+
+/// Resolves a fee read from chain to its fee tier with the tick spacing, from the
+/// [fee tier registry](get_fee_amount) or else from the factory's `feeAmountTickSpacing`.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `factory`: The factory address
+/// * `fee`: The fee in hundredths of bips
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query.
+///
+/// ## Returns
+///
+/// The fee tier, or [`Error::UnknownFeeTier`] if the fee is neither registered nor enabled on the
+/// factory
+#[inline]
+pub async fn resolve_fee_amount<N, P>(
+    chain_id: ChainId,
+    factory: Address,
+    fee: U24,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<FeeAmount, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    match get_fee_amount(chain_id, fee) {
+        Ok(fee_amount) => Ok(fee_amount),
+        Err(_) => get_fee_amount_from_factory(factory, fee, provider, block_id).await,
+    }
+}
+
+impl DexConfig {
+    /// Resolves a fee read from chain to the fee tier of the DEX with its tick spacing, from the
+    /// [fee tiers](Self::fee_tiers) of the DEX or else from the `feeAmountTickSpacing` of its
+    /// factory.
+    ///
+    /// ## Arguments
+    ///
+    /// * `fee`: The fee in hundredths of bips
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query.
+    ///
+    /// ## Returns
+    ///
+    /// The fee tier, or [`Error::UnknownFeeTier`] if the fee is not enabled on the DEX
+    #[inline]
+    pub async fn resolve_fee_amount<N, P>(
+        &self,
+        fee: U24,
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<FeeAmount, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        match self.fee_amount(fee) {
+            Some(fee_amount) => Ok(fee_amount),
+            None => get_fee_amount_from_factory(self.factory, fee, provider, block_id).await,
+        }
+    }
+}
+
 impl Pool {
     /// Get a [`Pool`] struct from pool key
     ///
     /// ## Arguments
     ///
     /// * `chain_id`: The chain id
-    /// * `factory`: The factory address
+    /// * `factory`: The factory address, or `None` for the factory of the [`Deployment`] on the
+    ///   chain
     /// * `token_a`: One of the tokens in the pool
     /// * `token_b`: The other token in the pool
     /// * `fee`: Fee tier of the pool
@@ -52,7 +194,82 @@ impl Pool {
     #[inline]
     pub async fn from_pool_key<N, P>(
         chain_id: ChainId,
-        factory: Address,
+        factory: impl Into<Option<Address>>,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let factory = factory.into();
+        let pool = Self::from_pool_address(
+            chain_id,
+            get_pool_address(chain_id, factory, token_a, token_b, fee)?,
+            token_a,
+            token_b,
+            fee,
+            provider,
+            block_id,
+        )
+        .await?;
+        Ok(Self {
+            deployer: factory,
+            ..pool
+        })
+    }
+
+    /// Get a [`Pool`] struct from pool key on a Uniswap V3 fork, whose address is computed from
+    /// the deployer and init code hash of the DEX
+    ///
+    /// ## Arguments
+    ///
+    /// * `config`: The configuration of the DEX
+    /// * `token_a`: One of the tokens in the pool
+    /// * `token_b`: The other token in the pool
+    /// * `fee`: Fee tier of the pool
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query.
+    ///
+    /// ## Returns
+    ///
+    /// The pool, or [`Error::UnknownFeeTier`] if the fee tier is not enabled on the DEX
+    #[inline]
+    pub async fn from_dex_config<N, P>(
+        config: &DexConfig,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        if !config.supports_fee(fee) {
+            return Err(Error::UnknownFeeTier(fee.into()));
+        }
+        let pool = Self::from_pool_address(
+            config.chain_id,
+            config.pool_address(token_a, token_b, fee),
+            token_a,
+            token_b,
+            fee,
+            provider,
+            block_id,
+        )
+        .await?;
+        Ok(pool.with_dex_config(config))
+    }
+
+    /// Get a [`Pool`] struct from the state of the pool at `pool_address` and its tokens
+    async fn from_pool_address<N, P>(
+        chain_id: ChainId,
+        pool_address: Address,
         token_a: Address,
         token_b: Address,
         fee: FeeAmount,
@@ -64,7 +281,7 @@ impl Pool {
         P: Provider<N>,
     {
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
-        let pool_contract = get_pool_contract(factory, token_a, token_b, fee, provider.root());
+        let pool_contract = IUniswapV3PoolInstance::new(pool_address, provider.root());
         let token_a_contract = IERC20Metadata::new(token_a, provider.root());
         let token_b_contract = IERC20Metadata::new(token_b, provider.root());
         let multicall = provider
@@ -120,7 +337,8 @@ impl<I: TickIndex> Pool<EphemeralTickMapDataProvider<I>> {
     /// ## Arguments
     ///
     /// * `chain_id`: The chain id
-    /// * `factory`: The factory address
+    /// * `factory`: The factory address, or `None` for the factory of the [`Deployment`] on the
+    ///   chain
     /// * `token_a`: One of the tokens in the pool
     /// * `token_b`: The other token in the pool
     /// * `fee`: Fee tier of the pool
@@ -160,7 +378,7 @@ impl<I: TickIndex> Pool<EphemeralTickMapDataProvider<I>> {
     #[inline]
     pub async fn from_pool_key_with_tick_data_provider<N, P>(
         chain_id: ChainId,
-        factory: Address,
+        factory: impl Into<Option<Address>>,
         token_a: Address,
         token_b: Address,
         fee: FeeAmount,
@@ -181,22 +399,494 @@ impl<I: TickIndex> Pool<EphemeralTickMapDataProvider<I>> {
             block_id,
         )
         .await?;
+        Self::from_pool(pool, provider, block_id).await
+    }
+
+    /// Get a [`Pool`] struct with tick data provider from pool key on a Uniswap V3 fork, see
+    /// [`Pool::from_dex_config`]
+    ///
+    /// ## Arguments
+    ///
+    /// * `config`: The configuration of the DEX
+    /// * `token_a`: One of the tokens in the pool
+    /// * `token_b`: The other token in the pool
+    /// * `fee`: Fee tier of the pool
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query.
+    #[inline]
+    pub async fn from_dex_config_with_tick_data_provider<N, P>(
+        config: &DexConfig,
+        token_a: Address,
+        token_b: Address,
+        fee: FeeAmount,
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let pool =
+            Pool::from_dex_config(config, token_a, token_b, fee, provider.root(), block_id).await?;
+        Self::from_pool(pool, provider, block_id).await
+    }
+
+    /// Attaches the tick data of a pool fetched through the ephemeral lens
+    async fn from_pool<N, P>(
+        pool: Pool,
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<Self, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
         let tick_data_provider = EphemeralTickMapDataProvider::new(
-            pool.address(None, Some(factory)),
+            pool.address(None, None),
             provider,
             None,
             None,
             block_id,
         )
         .await?;
-        Self::new_with_tick_data_provider(
-            pool.token0,
-            pool.token1,
-            pool.fee,
-            pool.sqrt_ratio_x96,
-            pool.liquidity,
-            tick_data_provider,
-        )
+        Ok(Self {
+            deployer: pool.deployer,
+            init_code_hash: pool.init_code_hash,
+            ..Self::new_with_tick_data_provider(
+                pool.token0,
+                pool.token1,
+                pool.fee,
+                pool.sqrt_ratio_x96,
+                pool.liquidity,
+                tick_data_provider,
+            )?
+        })
+    }
+}
+
+/// The default number of pools fetched per `Multicall3` batch by a [`PoolLoader`].
+pub const DEFAULT_POOL_BATCH_SIZE: usize = 200;
+
+/// The maximum number of tick maps of different pools fetched concurrently.
+pub(crate) const MAX_CONCURRENT_TICK_MAPS: usize = 8;
+
+/// Calls each target with its calldata in a `Multicall3` `aggregate3` call which allows every call
+/// to fail, so that a revert only fails its own call.
+pub(crate) async fn aggregate3_allow_failure<N, P, C>(
+    calls: impl IntoIterator<Item = (Address, C)>,
+    provider: &P,
+    block_id: BlockId,
+) -> Result<Vec<IMulticall3::Result>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    C: SolCall,
+{
+    let calls: Vec<_> = calls
+        .into_iter()
+        .map(|(target, call)| IMulticall3::Call3 {
+            target,
+            allowFailure: true,
+            callData: call.abi_encode().into(),
+        })
+        .collect();
+    if calls.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tx = N::TransactionRequest::default()
+        .with_to(MULTICALL3_ADDRESS)
+        .with_input(IMulticall3::aggregate3Call { calls }.abi_encode());
+    let output = provider
+        .call(tx)
+        .block(block_id)
+        .await
+        .map_err(MulticallError::from)?;
+    Ok(
+        IMulticall3::aggregate3Call::abi_decode_returns(&output, true)
+            .map_err(MulticallError::DecodeError)?
+            .returnData,
+    )
+}
+
+/// Decodes the result of the call at `idx` of an `aggregate3` call, failing if the call reverted
+/// or its return data does not decode, e.g. if the target has no code.
+pub(crate) fn decode_result<C: SolCall>(
+    (idx, result): (usize, IMulticall3::Result),
+) -> Result<C::Return, Failure> {
+    if result.success {
+        if let Ok(decoded) = C::abi_decode_returns(&result.returnData, true) {
+            return Ok(decoded);
+        }
+    }
+    Err(Failure {
+        idx,
+        return_data: result.returnData,
+    })
+}
+
+/// Decodes the `string` returned by a successful call, or the `bytes32` returned instead by tokens
+/// such as MKR for their name and symbol.
+fn decode_string(result: &IMulticall3::Result) -> Option<String> {
+    if !result.success {
+        return None;
+    }
+    String::abi_decode(&result.returnData, true)
+        .ok()
+        .or_else(|| {
+            let bytes = B256::abi_decode(&result.returnData, true).ok()?;
+            let len = bytes.iter().position(|&byte| byte == 0).unwrap_or(32);
+            String::from_utf8(bytes[..len].to_vec()).ok()
+        })
+}
+
+/// Fetches the decimals, symbol and name of ERC20 tokens in a few multicalls, each call of which
+/// may fail on its own. Tokens whose `symbol` or `name` can't be decoded as a `string` or
+/// `bytes32` are created without them, and tokens whose `decimals` call fails, such as accounts
+/// without code, are left out.
+pub(crate) async fn fetch_tokens<N, P>(
+    chain_id: ChainId,
+    tokens: &BTreeSet<Address>,
+    provider: &P,
+    block_id: BlockId,
+) -> Result<BTreeMap<Address, Token>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let decimals = aggregate3_allow_failure(
+        tokens
+            .iter()
+            .map(|&token| (token, IERC20Metadata::decimalsCall {})),
+        provider,
+        block_id,
+    )
+    .await?;
+    let symbols = aggregate3_allow_failure(
+        tokens
+            .iter()
+            .map(|&token| (token, IERC20Metadata::symbolCall {})),
+        provider,
+        block_id,
+    )
+    .await?;
+    let names = aggregate3_allow_failure(
+        tokens
+            .iter()
+            .map(|&token| (token, IERC20Metadata::nameCall {})),
+        provider,
+        block_id,
+    )
+    .await?;
+    Ok(tokens
+        .iter()
+        .zip(decimals.into_iter().enumerate())
+        .zip(symbols.iter().zip(&names))
+        .filter_map(|((&address, decimals), (symbol, name))| {
+            let decimals = decode_result::<IERC20Metadata::decimalsCall>(decimals)
+                .ok()?
+                ._0;
+            let token = match (decode_string(symbol), decode_string(name)) {
+                (Some(symbol), Some(name)) => token!(chain_id, address, decimals, symbol, name),
+                _ => token!(chain_id, address, decimals),
+            };
+            Some((address, token))
+        })
+        .collect())
+}
+
+/// Creates a pool from the results of its batched calls.
+fn new_pool(
+    tokens: &BTreeMap<Address, Token>,
+    (token_a, token_b, fee): (Address, Address, FeeAmount),
+    slot0: Result<IUniswapV3Pool::slot0Return, Failure>,
+    liquidity: Result<IUniswapV3Pool::liquidityReturn, Failure>,
+) -> Result<Pool, Error> {
+    let slot0 = slot0.map_err(|failure| MulticallError::CallFailed(failure.return_data))?;
+    let liquidity = liquidity.map_err(|failure| MulticallError::CallFailed(failure.return_data))?;
+    let token_a = tokens.get(&token_a).cloned().ok_or(Error::InvalidToken)?;
+    let token_b = tokens.get(&token_b).cloned().ok_or(Error::InvalidToken)?;
+    Pool::new(token_a, token_b, fee, slot0.sqrtPriceX96, liquidity._0)
+}
+
+/// Loads many pools of a factory at a consistent block in a few `Multicall3` batches, reporting
+/// the failure of each pool, e.g. if it is not deployed or not initialized, instead of failing the
+/// whole batch.
+///
+/// ## Examples
+///
+/// ```
+/// use alloy::{eips::BlockId, providers::ProviderBuilder};
+/// use alloy_primitives::address;
+/// use uniswap_v3_sdk::prelude::*;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     dotenv::dotenv().ok();
+///     let rpc_url = std::env::var("MAINNET_RPC_URL").unwrap().parse().unwrap();
+///     let provider = ProviderBuilder::new().on_http(rpc_url);
+///     let wbtc = address!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599");
+///     let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+///     let pools = PoolLoader::new(1, FACTORY_ADDRESS, provider)
+///         .with_block_id(BlockId::from(17000000))
+///         .load(&[(wbtc, weth, FeeAmount::LOW), (wbtc, weth, FeeAmount::MEDIUM)])
+///         .await
+///         .unwrap();
+///     assert!(pools.iter().all(Result::is_ok));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PoolLoader<N, P> {
+    chain_id: ChainId,
+    factory: Option<Address>,
+    dex_config: Option<DexConfig>,
+    provider: P,
+    block_id: Option<BlockId>,
+    batch_size: usize,
+    _network: PhantomData<N>,
+}
+
+impl<N, P> PoolLoader<N, P>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    /// Creates a loader of the pools of `factory`, at the latest block by default.
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `factory`: The factory address, or `None` for the factory of the [`Deployment`] on the
+    ///   chain
+    /// * `provider`: The alloy provider
+    #[inline]
+    pub fn new(chain_id: ChainId, factory: impl Into<Option<Address>>, provider: P) -> Self {
+        Self {
+            chain_id,
+            factory: factory.into(),
+            dex_config: None,
+            provider,
+            block_id: None,
+            batch_size: DEFAULT_POOL_BATCH_SIZE,
+            _network: PhantomData,
+        }
+    }
+
+    /// Creates a loader of the pools of a Uniswap V3 fork, whose addresses are computed from the
+    /// deployer and init code hash of the DEX, at the latest block by default. Pools of fee tiers
+    /// which are not enabled on the DEX fail with [`Error::UnknownFeeTier`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `config`: The configuration of the DEX
+    /// * `provider`: The alloy provider
+    #[inline]
+    pub const fn from_dex_config(config: DexConfig, provider: P) -> Self {
+        Self {
+            chain_id: config.chain_id,
+            factory: Some(config.factory),
+            dex_config: Some(config),
+            provider,
+            block_id: None,
+            batch_size: DEFAULT_POOL_BATCH_SIZE,
+            _network: PhantomData,
+        }
+    }
+
+    /// Sets the block to load the pools at.
+    #[inline]
+    #[must_use]
+    pub const fn with_block_id(mut self, block_id: BlockId) -> Self {
+        self.block_id = Some(block_id);
+        self
+    }
+
+    /// Sets the number of pools fetched per `Multicall3` batch, at least one.
+    #[inline]
+    #[must_use]
+    pub const fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = if batch_size == 0 { 1 } else { batch_size };
+        self
+    }
+
+    /// Returns the block to load the pools at, pinning the latest block if none was set so that
+    /// all batches see the same state.
+    async fn block_id(&self) -> Result<BlockId, Error> {
+        match self.block_id {
+            Some(block_id) => Ok(block_id),
+            None => Ok(BlockId::from(self.provider.get_block_number().await?)),
+        }
+    }
+
+    /// Returns the address of a pool on the DEX or factory of the loader.
+    fn pool_address(
+        &self,
+        (token_a, token_b, fee): (Address, Address, FeeAmount),
+    ) -> Result<Address, Error> {
+        match &self.dex_config {
+            Some(config) => Ok(config.pool_address(token_a, token_b, fee)),
+            None => get_pool_address(self.chain_id, self.factory, token_a, token_b, fee),
+        }
+    }
+
+    /// Sets the deployer of a loaded pool, so that its address is computed like the loader's.
+    fn with_deployer<TP: TickDataProvider>(&self, pool: Pool<TP>) -> Pool<TP> {
+        match &self.dex_config {
+            Some(config) => pool.with_dex_config(config),
+            None => Pool {
+                deployer: self.factory,
+                ..pool
+            },
+        }
+    }
+
+    async fn load_at(
+        &self,
+        pool_keys: &[(Address, Address, FeeAmount)],
+        block_id: BlockId,
+    ) -> Result<Vec<Result<Pool, Error>>, Error> {
+        let provider = self.provider.root();
+        let mut pools = Vec::with_capacity(pool_keys.len());
+        // the tokens shared by pools in different batches are only fetched once
+        let mut fetched_tokens = BTreeSet::new();
+        let mut tokens = BTreeMap::new();
+        for batch in pool_keys.chunks(self.batch_size) {
+            let pool_addresses = batch
+                .iter()
+                .map(|&key| self.pool_address(key))
+                .collect::<Result<Vec<_>, _>>()?;
+            let slot0s = aggregate3_allow_failure(
+                pool_addresses
+                    .iter()
+                    .map(|&pool| (pool, IUniswapV3Pool::slot0Call {})),
+                provider,
+                block_id,
+            )
+            .await?;
+            let liquidities = aggregate3_allow_failure(
+                pool_addresses
+                    .iter()
+                    .map(|&pool| (pool, IUniswapV3Pool::liquidityCall {})),
+                provider,
+                block_id,
+            )
+            .await?;
+            let new_tokens = batch
+                .iter()
+                .flat_map(|&(token_a, token_b, _)| [token_a, token_b])
+                .filter(|&token| fetched_tokens.insert(token))
+                .collect();
+            tokens.extend(fetch_tokens(self.chain_id, &new_tokens, provider, block_id).await?);
+            pools.extend(
+                batch
+                    .iter()
+                    .zip(slot0s.into_iter().enumerate())
+                    .zip(liquidities.into_iter().enumerate())
+                    .map(|((&key, slot0), liquidity)| {
+                        if let Some(config) = &self.dex_config {
+                            if !config.supports_fee(key.2) {
+                                return Err(Error::UnknownFeeTier(key.2.into()));
+                            }
+                        }
+                        new_pool(
+                            &tokens,
+                            key,
+                            decode_result::<IUniswapV3Pool::slot0Call>(slot0),
+                            decode_result::<IUniswapV3Pool::liquidityCall>(liquidity),
+                        )
+                        .map(|pool| self.with_deployer(pool))
+                    }),
+            );
+        }
+        Ok(pools)
+    }
+
+    /// Loads the pools of the given keys, in order.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool_keys`: The tokens and fee tier of each pool
+    #[inline]
+    pub async fn load(
+        &self,
+        pool_keys: &[(Address, Address, FeeAmount)],
+    ) -> Result<Vec<Result<Pool, Error>>, Error> {
+        self.load_at(pool_keys, self.block_id().await?).await
+    }
+
+    /// Loads the pools of the given keys, in order, attaching the tick data of each pool fetched
+    /// through the ephemeral lens with one `eth_call` per pool, several pools at a time.
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool_keys`: The tokens and fee tier of each pool
+    #[inline]
+    pub async fn load_with_tick_data_provider<I: TickIndex>(
+        &self,
+        pool_keys: &[(Address, Address, FeeAmount)],
+    ) -> Result<Vec<Result<Pool<EphemeralTickMapDataProvider<I>>, Error>>, Error> {
+        let block_id = self.block_id().await?;
+        let pools = self.load_at(pool_keys, block_id).await?;
+        let provider = self.provider.root();
+        Ok(stream::iter(pools)
+            .map(|pool| async move {
+                let pool = pool?;
+                let tick_data_provider = EphemeralTickMapDataProvider::new(
+                    pool.address(None, None),
+                    provider,
+                    None,
+                    None,
+                    Some(block_id),
+                )
+                .await?;
+                Pool::new_with_tick_data_provider(
+                    pool.token0,
+                    pool.token1,
+                    pool.fee,
+                    pool.sqrt_ratio_x96,
+                    pool.liquidity,
+                    tick_data_provider,
+                )
+                .map(|pool| self.with_deployer(pool))
+            })
+            .buffered(MAX_CONCURRENT_TICK_MAPS)
+            .collect()
+            .await)
+    }
+}
+
+impl Pool {
+    /// Get [`Pool`] structs from many pool keys at a consistent block in a few `Multicall3`
+    /// batches, see [`PoolLoader`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    /// * `factory`: The factory address, or `None` for the factory of the [`Deployment`] on the
+    ///   chain
+    /// * `pool_keys`: The tokens and fee tier of each pool
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to query, the latest block by default
+    ///
+    /// ## Returns
+    ///
+    /// The result of each pool, in order
+    #[inline]
+    pub async fn from_pool_keys<N, P>(
+        chain_id: ChainId,
+        factory: impl Into<Option<Address>>,
+        pool_keys: &[(Address, Address, FeeAmount)],
+        provider: P,
+        block_id: Option<BlockId>,
+    ) -> Result<Vec<Result<Self, Error>>, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let mut loader = PoolLoader::new(chain_id, factory, provider);
+        if let Some(block_id) = block_id {
+            loader = loader.with_block_id(block_id);
+        }
+        loader.load(pool_keys).await
     }
 }
 
@@ -320,7 +1010,7 @@ where
 mod tests {
     use super::*;
     use crate::tests::*;
-    use alloy_primitives::address;
+    use alloy_primitives::{address, U256};
 
     async fn pool() -> Pool {
         Pool::from_pool_key(
@@ -345,6 +1035,29 @@ mod tests {
         assert_eq!(pool.liquidity, 786352807736110014);
     }
 
+    #[tokio::test]
+    async fn test_get_fee_amount_from_factory() {
+        let fee_amount = get_fee_amount_from_factory(
+            FACTORY_ADDRESS,
+            U24::from(500),
+            PROVIDER.clone(),
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fee_amount, FeeAmount::LOW);
+        assert!(matches!(
+            get_fee_amount_from_factory(
+                FACTORY_ADDRESS,
+                U24::from(2500),
+                PROVIDER.clone(),
+                *BLOCK_ID
+            )
+            .await,
+            Err(Error::UnknownFeeTier(_))
+        ));
+    }
+
     #[tokio::test]
     async fn test_get_liquidity_array_for_pool() {
         let pool = pool().await;
@@ -375,4 +1088,214 @@ mod tests {
             liquidity
         );
     }
+
+    #[tokio::test]
+    async fn test_resolve_registered_fee_amount() {
+        // a registered fee tier is resolved without querying the factory
+        assert_eq!(
+            resolve_fee_amount(
+                1,
+                FACTORY_ADDRESS,
+                U24::from(3000),
+                PROVIDER.clone(),
+                *BLOCK_ID
+            )
+            .await
+            .unwrap(),
+            FeeAmount::MEDIUM
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pool_loader() {
+        let wbtc = address!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599");
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let mkr = address!("9f8F72aA9304c8B593d555F12eF6589cC3A579A2");
+        let dead = address!("000000000000000000000000000000000000dEaD");
+        let pools = PoolLoader::new(1, None, PROVIDER.clone())
+            .with_block_id(BLOCK_ID.unwrap())
+            .with_batch_size(2)
+            .load(&[
+                (wbtc, weth, FeeAmount::LOW),
+                (wbtc, weth, FeeAmount::MEDIUM),
+                (mkr, weth, FeeAmount::MEDIUM),
+                (dead, weth, FeeAmount::MEDIUM),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(pools.len(), 4);
+        let expected = pool().await;
+        let pool = pools[0].as_ref().unwrap();
+        assert_eq!(pool.token0, expected.token0);
+        assert_eq!(pool.token0.symbol, expected.token0.symbol);
+        assert_eq!(pool.sqrt_ratio_x96, expected.sqrt_ratio_x96);
+        assert_eq!(pool.liquidity, expected.liquidity);
+        assert!(pools[1].is_ok());
+        // MKR returns its symbol as `bytes32`
+        let pool = pools[2].as_ref().unwrap();
+        assert_eq!(pool.token0.symbol.as_deref(), Some("MKR"));
+        // no pool is deployed for a token without code, which fails only its own entry
+        assert!(matches!(
+            pools[3],
+            Err(Error::MulticallError(MulticallError::CallFailed(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_pool_loader_from_dex_config() {
+        let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let config = DexConfig::sushiswap(1, Address::ZERO).unwrap();
+        let pools = PoolLoader::from_dex_config(config.clone(), PROVIDER.clone())
+            .load(&[
+                (usdc, weth, FeeAmount::LOW),
+                (usdc, weth, FeeAmount::new_custom(3000, 50)),
+            ])
+            .await
+            .unwrap();
+        let pool = pools[0].as_ref().unwrap();
+        assert_eq!(
+            pool.address(None, None),
+            config.pool_address(usdc, weth, FeeAmount::LOW)
+        );
+        assert!(matches!(pools[1], Err(Error::UnknownFeeTier(_))));
+    }
+
+    #[tokio::test]
+    async fn test_from_dex_config_unknown_fee_tier() {
+        // the fee tier is checked before any call
+        let provider = alloy::providers::ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .on_http("http://localhost:1".parse().unwrap());
+        let result = Pool::from_dex_config(
+            &DexConfig::pancakeswap(1),
+            TOKEN0.address,
+            TOKEN1.address,
+            FeeAmount::MEDIUM,
+            provider,
+            None,
+        )
+        .await;
+        assert!(matches!(result, Err(Error::UnknownFeeTier(_))));
+    }
+
+    #[test]
+    fn test_pool_loader_batch_size() {
+        let provider = alloy::providers::ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .on_http("http://localhost:1".parse().unwrap());
+        let loader = PoolLoader::new(1, None, provider);
+        assert_eq!(loader.batch_size, DEFAULT_POOL_BATCH_SIZE);
+        assert_eq!(loader.clone().with_batch_size(0).batch_size, 1);
+        assert_eq!(loader.with_batch_size(2).batch_size, 2);
+    }
+
+    #[tokio::test]
+    async fn test_pool_loader_with_tick_data_provider() {
+        let wbtc = address!("2260FAC5E5542a773Aa44fBCfeDf7C193bc2C599");
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+        let pools = PoolLoader::new(1, FACTORY_ADDRESS, PROVIDER.clone())
+            .with_block_id(BLOCK_ID.unwrap())
+            .load_with_tick_data_provider::<i32>(&[(wbtc, weth, FeeAmount::LOW)])
+            .await
+            .unwrap();
+        let pool = pools[0].as_ref().unwrap();
+        assert_eq!(pool.tick_current, 257344);
+        assert!(!pool.tick_data_provider.tick_map.inner.is_empty());
+    }
+
+    #[test]
+    fn test_new_pool_reports_failures() {
+        let key = (TOKEN0.address, TOKEN1.address, FeeAmount::MEDIUM);
+        let tokens = BTreeMap::from([
+            (TOKEN0.address, TOKEN0.clone()),
+            (TOKEN1.address, TOKEN1.clone()),
+        ]);
+        let sqrt_price_x96 = encode_sqrt_ratio_x96(1, 1);
+        let slot0 = || IUniswapV3Pool::slot0Return {
+            sqrtPriceX96: sqrt_price_x96,
+            tick: Default::default(),
+            observationIndex: 0,
+            observationCardinality: 0,
+            observationCardinalityNext: 0,
+            feeProtocol: 0,
+            unlocked: true,
+        };
+        let liquidity = || IUniswapV3Pool::liquidityReturn { _0: 1 };
+
+        let pool = new_pool(&tokens, key, Ok(slot0()), Ok(liquidity())).unwrap();
+        assert_eq!(pool.token0, *TOKEN0);
+        assert_eq!(pool.liquidity, 1);
+
+        let failure = Failure {
+            idx: 0,
+            return_data: Default::default(),
+        };
+        assert!(matches!(
+            new_pool(&tokens, key, Err(failure), Ok(liquidity())),
+            Err(Error::MulticallError(MulticallError::CallFailed(_)))
+        ));
+        // the calls to an undeployed pool succeed without return data
+        let undeployed = IMulticall3::Result {
+            success: true,
+            returnData: Default::default(),
+        };
+        assert!(matches!(
+            new_pool(
+                &tokens,
+                key,
+                decode_result::<IUniswapV3Pool::slot0Call>((0, undeployed.clone())),
+                decode_result::<IUniswapV3Pool::liquidityCall>((0, undeployed)),
+            ),
+            Err(Error::MulticallError(MulticallError::CallFailed(_)))
+        ));
+        assert!(matches!(
+            new_pool(&BTreeMap::new(), key, Ok(slot0()), Ok(liquidity())),
+            Err(Error::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn test_decode_result() {
+        let liquidity = |success, return_data: Vec<u8>| IMulticall3::Result {
+            success,
+            returnData: return_data.into(),
+        };
+        let decoded = decode_result::<IUniswapV3Pool::liquidityCall>((
+            0,
+            liquidity(true, U256::from(1).abi_encode()),
+        ));
+        assert_eq!(decoded.unwrap()._0, 1);
+        // an account without code returns no data
+        let failure = decode_result::<IUniswapV3Pool::liquidityCall>((1, liquidity(true, vec![])));
+        assert!(matches!(failure, Err(Failure { idx: 1, .. })));
+        let failure = decode_result::<IUniswapV3Pool::liquidityCall>((
+            2,
+            liquidity(false, U256::from(1).abi_encode()),
+        ));
+        assert!(matches!(failure, Err(Failure { idx: 2, .. })));
+    }
+
+    #[test]
+    fn test_decode_string() {
+        let result = |success, return_data: Vec<u8>| IMulticall3::Result {
+            success,
+            returnData: return_data.into(),
+        };
+        assert_eq!(
+            decode_string(&result(true, "WETH".to_string().abi_encode())).unwrap(),
+            "WETH"
+        );
+        // MKR returns its symbol as `bytes32`
+        let mkr = B256::right_padding_from(b"MKR");
+        assert_eq!(
+            decode_string(&result(true, mkr.abi_encode())).unwrap(),
+            "MKR"
+        );
+        assert_eq!(decode_string(&result(true, vec![])), None);
+        assert_eq!(
+            decode_string(&result(false, "WETH".to_string().abi_encode())),
+            None
+        );
+    }
 }
@@ -3,22 +3,30 @@
 //! and pool for all positions of the specified owner by deploying an ephemeral contract via
 //! `eth_call`, etc.
 
+use super::pool::{fetch_tokens, MAX_CONCURRENT_TICK_MAPS};
 use crate::prelude::{Error, *};
-use alloc::string::ToString;
+use alloc::{
+    collections::{btree_map, BTreeMap, BTreeSet},
+    string::ToString,
+    vec::Vec,
+};
 use alloy::{
     eips::{BlockId, BlockNumberOrTag},
     network::Network,
-    providers::Provider,
+    providers::{MulticallBuilder, Provider},
     transports::{TransportError, TransportErrorKind},
 };
-use alloy_primitives::{Address, ChainId, U256};
+use alloy_primitives::{aliases::U24, Address, ChainId, U256};
+use futures::{stream, StreamExt, TryStreamExt};
 use uniswap_lens::{
     bindings::{
         ephemeralallpositionsbyowner::EphemeralAllPositionsByOwner,
         ephemeralgetposition::EphemeralGetPosition,
+        iuniswapv3factory::IUniswapV3Factory,
         iuniswapv3nonfungiblepositionmanager::IUniswapV3NonfungiblePositionManager::{
             positionsReturn, IUniswapV3NonfungiblePositionManagerInstance,
         },
+        iuniswapv3pool::IUniswapV3Pool::IUniswapV3PoolInstance,
     },
     position_lens,
 };
@@ -41,14 +49,15 @@ where
 /// ## Arguments
 ///
 /// * `chain_id`: The chain id
-/// * `nonfungible_position_manager`: The nonfungible position manager address
+/// * `nonfungible_position_manager`: The nonfungible position manager address, or `None` for
+///   the one of the [`Deployment`] on the chain
 /// * `token_id`: The token id
 /// * `provider`: The alloy provider
 /// * `block_id`: Optional block number to query
 #[inline]
 pub async fn get_position<N, P>(
     chain_id: ChainId,
-    nonfungible_position_manager: Address,
+    nonfungible_position_manager: impl Into<Option<Address>>,
     token_id: U256,
     provider: P,
     block_id: Option<BlockId>,
@@ -58,6 +67,11 @@ where
     P: Provider<N>,
 {
     let block_id_ = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let nonfungible_position_manager = resolve_deployment_address(
+        chain_id,
+        nonfungible_position_manager.into(),
+        |deployment| Some(deployment.nonfungible_position_manager),
+    )?;
     let npm_contract =
         get_nonfungible_position_manager_contract(nonfungible_position_manager, provider.root());
     let multicall = provider
@@ -73,25 +87,28 @@ where
             tickLower: tick_lower,
             tickUpper: tick_upper,
             liquidity,
+            feeGrowthInside0LastX128: fee_growth_inside0_last_x128,
+            feeGrowthInside1LastX128: fee_growth_inside1_last_x128,
+            tokensOwed0: tokens_owed0,
+            tokensOwed1: tokens_owed1,
             ..
         },
     ) = multicall.block(block_id_).aggregate().await?;
+    let fee = resolve_fee_amount(chain_id, factory._0, fee, provider.root(), block_id).await?;
     let pool = Pool::from_pool_key(
-        chain_id,
-        factory._0,
-        token0,
-        token1,
-        fee.into(),
-        provider,
-        block_id,
+        chain_id, factory._0, token0, token1, fee, provider, block_id,
     )
     .await?;
-    Ok(Position::new(
-        pool,
-        liquidity,
-        tick_lower.as_i32(),
-        tick_upper.as_i32(),
-    ))
+    Ok(
+        Position::new(pool, liquidity, tick_lower.as_i32(), tick_upper.as_i32()).with_fee_state(
+            PositionFeeState {
+                fee_growth_inside0_last_x128,
+                fee_growth_inside1_last_x128,
+                tokens_owed0,
+                tokens_owed1,
+            },
+        ),
+    )
 }
 
 impl Position {
@@ -101,14 +118,15 @@ impl Position {
     /// ## Arguments
     ///
     /// * `chain_id`: The chain id
-    /// * `nonfungible_position_manager`: The nonfungible position manager address
+    /// * `nonfungible_position_manager`: The nonfungible position manager address, or `None` for
+    ///   the one of the [`Deployment`] on the chain
     /// * `token_id`: The token id
     /// * `provider`: The alloy provider
     /// * `block_id`: Optional block number to query
     #[inline]
     pub async fn from_token_id<N, P>(
         chain_id: ChainId,
-        nonfungible_position_manager: Address,
+        nonfungible_position_manager: impl Into<Option<Address>>,
         token_id: U256,
         provider: P,
         block_id: Option<BlockId>,
@@ -117,6 +135,11 @@ impl Position {
         N: Network,
         P: Provider<N>,
     {
+        let nonfungible_position_manager = resolve_deployment_address(
+            chain_id,
+            nonfungible_position_manager.into(),
+            |deployment| Some(deployment.nonfungible_position_manager),
+        )?;
         let EphemeralGetPosition::PositionState {
             position,
             slot0,
@@ -127,15 +150,30 @@ impl Position {
         } = position_lens::get_position_details(
             nonfungible_position_manager,
             token_id,
-            provider,
+            provider.root(),
             block_id,
         )
         .await
         .map_err(Error::LensError)?;
+        let fee = match get_fee_amount(chain_id, position.fee) {
+            Ok(fee) => fee,
+            Err(_) => {
+                let factory = get_nonfungible_position_manager_contract(
+                    nonfungible_position_manager,
+                    &provider,
+                )
+                .factory()
+                .block(block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))
+                .call()
+                .await?
+                ._0;
+                get_fee_amount_from_factory(factory, position.fee, provider, block_id).await?
+            }
+        };
         let pool = Pool::new(
             token!(chain_id, position.token0, decimals0),
             token!(chain_id, position.token1, decimals1),
-            position.fee.into(),
+            fee,
             slot0.sqrtPriceX96,
             active_liquidity,
         )?;
@@ -144,7 +182,13 @@ impl Position {
             position.liquidity,
             position.tickLower.as_i32(),
             position.tickUpper.as_i32(),
-        ))
+        )
+        .with_fee_state(PositionFeeState {
+            fee_growth_inside0_last_x128: position.feeGrowthInside0LastX128,
+            fee_growth_inside1_last_x128: position.feeGrowthInside1LastX128,
+            tokens_owed0: position.tokensOwed0,
+            tokens_owed1: position.tokensOwed1,
+        }))
     }
 }
 
@@ -154,7 +198,8 @@ impl<I: TickIndex> Position<EphemeralTickMapDataProvider<I>> {
     /// ## Arguments
     ///
     /// * `chain_id`: The chain id
-    /// * `nonfungible_position_manager`: The nonfungible position manager address
+    /// * `nonfungible_position_manager`: The nonfungible position manager address, or `None` for
+    ///   the one of the [`Deployment`] on the chain
     /// * `token_id`: The token id
     /// * `provider`: The alloy provider
     /// * `block_id`: Optional block number to query
@@ -165,7 +210,7 @@ impl<I: TickIndex> Position<EphemeralTickMapDataProvider<I>> {
     #[inline]
     pub async fn from_token_id_with_tick_data_provider<N, P>(
         chain_id: ChainId,
-        nonfungible_position_manager: Address,
+        nonfungible_position_manager: impl Into<Option<Address>>,
         token_id: U256,
         provider: P,
         block_id: Option<BlockId>,
@@ -183,6 +228,7 @@ impl<I: TickIndex> Position<EphemeralTickMapDataProvider<I>> {
         )
         .await?;
         let pool = position.pool;
+        let fee_state = position.fee_state;
         let tick_data_provider = EphemeralTickMapDataProvider::new(
             pool.address(None, None),
             provider,
@@ -191,20 +237,26 @@ impl<I: TickIndex> Position<EphemeralTickMapDataProvider<I>> {
             block_id,
         )
         .await?;
-        let pool = Pool::new_with_tick_data_provider(
-            pool.token0,
-            pool.token1,
-            pool.fee,
-            pool.sqrt_ratio_x96,
-            pool.liquidity,
-            tick_data_provider,
-        )?;
-        Ok(Self::new(
+        let pool = Pool {
+            deployer: pool.deployer,
+            init_code_hash: pool.init_code_hash,
+            ..Pool::new_with_tick_data_provider(
+                pool.token0,
+                pool.token1,
+                pool.fee,
+                pool.sqrt_ratio_x96,
+                pool.liquidity,
+                tick_data_provider,
+            )?
+        };
+        let mut position = Self::new(
             pool,
             position.liquidity,
             position.tick_lower.try_into().unwrap(),
             position.tick_upper.try_into().unwrap(),
-        ))
+        );
+        position.fee_state = fee_state;
+        Ok(position)
     }
 }
 
@@ -214,7 +266,8 @@ impl<I: TickIndex> Position<EphemeralTickMapDataProvider<I>> {
 /// ## Note
 ///
 /// Each position consumes about 200k gas, so this method may fail if the number of positions
-/// exceeds 1500 assuming the provider gas limit is 300m.
+/// exceeds 1500 assuming the provider gas limit is 300m. Use [`get_positions_by_owner`] to fetch
+/// the positions page by page instead.
 ///
 /// ## Arguments
 ///
@@ -243,6 +296,356 @@ where
     .map_err(Error::LensError)
 }
 
+/// A position of an owner with its token id, uncollected fees and in-range status.
+#[derive(Clone, Debug)]
+pub struct OwnedPosition<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    /// The token id of the position
+    pub token_id: U256,
+    /// The position, whose pool tokens carry the on-chain decimals, symbol and name
+    pub position: Position<TP>,
+    /// The collectable amounts of token0 and token1, including the uncollected fees
+    pub tokens_owed: (CurrencyAmount<Token>, CurrencyAmount<Token>),
+    /// Whether the current tick of the pool is within the tick range of the position
+    pub in_range: bool,
+}
+
+/// A page of the positions of an owner, see [`get_positions_by_owner`].
+#[derive(Clone, Debug)]
+pub struct PositionsPage<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    /// The positions in the page, in the order of `tokenOfOwnerByIndex`
+    pub positions: Vec<OwnedPosition<TP>>,
+    /// The total number of positions of the owner
+    pub total: usize,
+    /// The index of the first position of the next page, if any
+    pub next_index: Option<usize>,
+}
+
+/// Get a page of the positions of the specified owner as typed [`OwnedPosition`]s, enumerating
+/// the token ids with `tokenOfOwnerByIndex`. The positions, pools, token metadata and ticks are
+/// each fetched in a single multicall, and pools and tokens shared by several positions are only
+/// fetched once.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `nonfungible_position_manager`: The nonfungible position manager address, or `None` for
+///   the one of the [`Deployment`] on the chain
+/// * `owner`: The owner address
+/// * `start_index`: The index of the first position of the page
+/// * `page_size`: The maximum number of positions in the page
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_positions_by_owner<N, P>(
+    chain_id: ChainId,
+    nonfungible_position_manager: impl Into<Option<Address>>,
+    owner: Address,
+    start_index: usize,
+    page_size: usize,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PositionsPage, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let provider = provider.root();
+    let nonfungible_position_manager = resolve_deployment_address(
+        chain_id,
+        nonfungible_position_manager.into(),
+        |deployment| Some(deployment.nonfungible_position_manager),
+    )?;
+    let npm_contract =
+        get_nonfungible_position_manager_contract(nonfungible_position_manager, provider);
+    let (factory, balance) = provider
+        .multicall()
+        .add(npm_contract.factory())
+        .add(npm_contract.balanceOf(owner))
+        .block(block_id)
+        .aggregate()
+        .await?;
+    let total: usize = balance.balance.to();
+    let end_index = total.min(start_index.saturating_add(page_size));
+    let next_index = (end_index < total).then_some(end_index);
+    if start_index >= end_index {
+        return Ok(PositionsPage {
+            positions: Vec::new(),
+            total,
+            next_index,
+        });
+    }
+
+    let token_ids: Vec<U256> = MulticallBuilder::new_dynamic(provider)
+        .extend(
+            (start_index..end_index)
+                .map(|index| npm_contract.tokenOfOwnerByIndex(owner, U256::from(index))),
+        )
+        .block(block_id)
+        .aggregate()
+        .await?
+        .into_iter()
+        .map(|token_id| token_id._0)
+        .collect();
+    let positions = MulticallBuilder::new_dynamic(provider)
+        .extend(
+            token_ids
+                .iter()
+                .map(|&token_id| npm_contract.positions(token_id)),
+        )
+        .block(block_id)
+        .aggregate()
+        .await?;
+
+    // deduplicate the pools, tokens and ticks shared by the positions
+    let mut pool_keys = BTreeSet::new();
+    let mut tokens = BTreeSet::new();
+    let mut ticks = BTreeSet::new();
+    for position in &positions {
+        let pool_key = (position.token0, position.token1, position.fee);
+        pool_keys.insert(pool_key);
+        tokens.extend([position.token0, position.token1]);
+        ticks.extend([
+            (pool_key, position.tickLower),
+            (pool_key, position.tickUpper),
+        ]);
+    }
+    let mut fee_amounts = BTreeMap::new();
+    for &(_, _, fee) in &pool_keys {
+        if let btree_map::Entry::Vacant(entry) = fee_amounts.entry(fee) {
+            entry.insert(
+                resolve_fee_amount(chain_id, factory._0, fee, provider, Some(block_id)).await?,
+            );
+        }
+    }
+    let pool_contracts: BTreeMap<_, _> = pool_keys
+        .iter()
+        .map(|&(token0, token1, fee): &(Address, Address, U24)| {
+            let pool_contract =
+                get_pool_contract(factory._0, token0, token1, fee_amounts[&fee], provider);
+            ((token0, token1, fee), pool_contract)
+        })
+        .collect();
+    let slot0s = MulticallBuilder::new_dynamic(provider)
+        .extend(pool_contracts.values().map(|pool| pool.slot0()))
+        .block(block_id)
+        .aggregate()
+        .await?;
+    let liquidities = MulticallBuilder::new_dynamic(provider)
+        .extend(pool_contracts.values().map(|pool| pool.liquidity()))
+        .block(block_id)
+        .aggregate()
+        .await?;
+    let fee_growth_globals0 = MulticallBuilder::new_dynamic(provider)
+        .extend(
+            pool_contracts
+                .values()
+                .map(|pool| pool.feeGrowthGlobal0X128()),
+        )
+        .block(block_id)
+        .aggregate()
+        .await?;
+    let fee_growth_globals1 = MulticallBuilder::new_dynamic(provider)
+        .extend(
+            pool_contracts
+                .values()
+                .map(|pool| pool.feeGrowthGlobal1X128()),
+        )
+        .block(block_id)
+        .aggregate()
+        .await?;
+    let tick_infos = MulticallBuilder::new_dynamic(provider)
+        .extend(
+            ticks
+                .iter()
+                .map(|(key, tick)| pool_contracts[key].ticks(*tick)),
+        )
+        .block(block_id)
+        .aggregate()
+        .await?;
+    let tokens = fetch_tokens(chain_id, &tokens, provider, block_id).await?;
+    let mut pools = BTreeMap::new();
+    for ((((key, slot0), liquidity), fee_growth_global0), fee_growth_global1) in pool_keys
+        .into_iter()
+        .zip(slot0s)
+        .zip(liquidities)
+        .zip(fee_growth_globals0)
+        .zip(fee_growth_globals1)
+    {
+        let mut pool = Pool::new(
+            tokens.get(&key.0).cloned().ok_or(Error::InvalidToken)?,
+            tokens.get(&key.1).cloned().ok_or(Error::InvalidToken)?,
+            fee_amounts[&key.2],
+            slot0.sqrtPriceX96,
+            liquidity._0,
+        )?;
+        // the tick is one below the price after crossing a tick downwards
+        pool.tick_current = slot0.tick.as_i32();
+        pool.deployer = Some(factory._0);
+        pools.insert(key, (pool, fee_growth_global0._0, fee_growth_global1._0));
+    }
+    let fee_growth_outsides: BTreeMap<_, _> = ticks
+        .into_iter()
+        .zip(tick_infos)
+        .map(|(key, info)| {
+            (
+                key,
+                FeeGrowthOutside {
+                    fee_growth_outside0_x128: info.feeGrowthOutside0X128,
+                    fee_growth_outside1_x128: info.feeGrowthOutside1X128,
+                },
+            )
+        })
+        .collect();
+
+    let positions = token_ids
+        .into_iter()
+        .zip(positions)
+        .map(|(token_id, position)| {
+            let pool_key = (position.token0, position.token1, position.fee);
+            let (pool, fee_growth_global0, fee_growth_global1) = &pools[&pool_key];
+            let position_ = Position::try_new(
+                pool.clone(),
+                position.liquidity,
+                position.tickLower.as_i32(),
+                position.tickUpper.as_i32(),
+            )?
+            .with_fee_state(PositionFeeState {
+                fee_growth_inside0_last_x128: position.feeGrowthInside0LastX128,
+                fee_growth_inside1_last_x128: position.feeGrowthInside1LastX128,
+                tokens_owed0: position.tokensOwed0,
+                tokens_owed1: position.tokensOwed1,
+            });
+            let tokens_owed = position_.tokens_owed(
+                fee_growth_outsides[&(pool_key, position.tickLower)],
+                fee_growth_outsides[&(pool_key, position.tickUpper)],
+                *fee_growth_global0,
+                *fee_growth_global1,
+            )?;
+            let in_range = position_.tick_lower <= pool.tick_current
+                && pool.tick_current < position_.tick_upper;
+            Ok(OwnedPosition {
+                token_id,
+                position: position_,
+                tokens_owed,
+                in_range,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(PositionsPage {
+        positions,
+        total,
+        next_index,
+    })
+}
+
+/// Get a page of the positions of the specified owner like [`get_positions_by_owner`], with the
+/// tick map of the pool of every position fetched by [`EphemeralTickMapDataProvider`]. The tick
+/// maps of different pools are fetched concurrently, and once for positions in the same pool.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `nonfungible_position_manager`: The nonfungible position manager address, or `None` for
+///   the one of the [`Deployment`] on the chain
+/// * `owner`: The owner address
+/// * `start_index`: The index of the first position of the page
+/// * `page_size`: The maximum number of positions in the page
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to query
+#[inline]
+pub async fn get_positions_by_owner_with_tick_data_provider<N, P, I>(
+    chain_id: ChainId,
+    nonfungible_position_manager: impl Into<Option<Address>>,
+    owner: Address,
+    start_index: usize,
+    page_size: usize,
+    provider: P,
+    block_id: Option<BlockId>,
+) -> Result<PositionsPage<EphemeralTickMapDataProvider<I>>, Error>
+where
+    N: Network,
+    P: Provider<N>,
+    I: TickIndex,
+{
+    let page = get_positions_by_owner(
+        chain_id,
+        nonfungible_position_manager,
+        owner,
+        start_index,
+        page_size,
+        provider.root(),
+        block_id,
+    )
+    .await?;
+    let pool_addresses: BTreeSet<Address> = page
+        .positions
+        .iter()
+        .map(|owned| owned.position.pool.address(None, None))
+        .collect();
+    let provider = provider.root();
+    let tick_data_providers: BTreeMap<_, _> = stream::iter(pool_addresses)
+        .map(|pool| async move {
+            EphemeralTickMapDataProvider::new(pool, provider, None, None, block_id)
+                .await
+                .map(|tick_data_provider| (pool, tick_data_provider))
+        })
+        .buffer_unordered(MAX_CONCURRENT_TICK_MAPS)
+        .try_collect()
+        .await?;
+    let positions = page
+        .positions
+        .into_iter()
+        .map(|owned| {
+            let OwnedPosition {
+                token_id,
+                position,
+                tokens_owed,
+                in_range,
+            } = owned;
+            let pool = position.pool;
+            let tick_data_provider = tick_data_providers[&pool.address(None, None)].clone();
+            let pool = Pool {
+                tick_current: I::from_i24(pool.tick_current.to_i24()),
+                deployer: pool.deployer,
+                init_code_hash: pool.init_code_hash,
+                ..Pool::new_with_tick_data_provider(
+                    pool.token0,
+                    pool.token1,
+                    pool.fee,
+                    pool.sqrt_ratio_x96,
+                    pool.liquidity,
+                    tick_data_provider,
+                )?
+            };
+            let mut position_ = Position::try_new(
+                pool,
+                position.liquidity,
+                I::from_i24(position.tick_lower.to_i24()),
+                I::from_i24(position.tick_upper.to_i24()),
+            )?;
+            position_.fee_state = position.fee_state;
+            Ok(OwnedPosition {
+                token_id,
+                position: position_,
+                tokens_owed,
+                in_range,
+            })
+        })
+        .collect::<Result<_, Error>>()?;
+    Ok(PositionsPage {
+        positions,
+        total: page.total,
+        next_index: page.next_index,
+    })
+}
+
 /// Get the real-time collectable token amounts.
 ///
 /// ## Arguments
@@ -274,13 +677,13 @@ where
         .add(npm_contract.factory())
         .add(npm_contract.positions(token_id));
     let (factory, position) = multicall.block(block_id_).aggregate().await?;
-    let pool_contract = get_pool_contract(
-        factory._0,
-        position.token0,
-        position.token1,
-        position.fee.into(),
-        provider.root(),
-    );
+    let pool = IUniswapV3Factory::new(factory._0, provider.root())
+        .getPool(position.token0, position.token1, position.fee)
+        .block(block_id_)
+        .call()
+        .await?
+        .pool;
+    let pool_contract = IUniswapV3PoolInstance::new(pool, provider.root());
     let multicall = provider
         .multicall()
         .add(pool_contract.slot0())
@@ -423,20 +826,26 @@ where
     TP: TickDataProvider,
 {
     let sqrt_price_x96 = price_to_sqrt_ratio_x96(new_price);
-    let pool_at_new_price = Pool::new_with_tick_data_provider(
-        position.pool.token0,
-        position.pool.token1,
-        position.pool.fee,
-        sqrt_price_x96,
-        position.pool.liquidity,
-        position.pool.tick_data_provider,
-    )?;
-    Ok(Position::new(
+    let pool_at_new_price = Pool {
+        deployer: position.pool.deployer,
+        init_code_hash: position.pool.init_code_hash,
+        ..Pool::new_with_tick_data_provider(
+            position.pool.token0,
+            position.pool.token1,
+            position.pool.fee,
+            sqrt_price_x96,
+            position.pool.liquidity,
+            position.pool.tick_data_provider,
+        )?
+    };
+    let mut position_at_new_price = Position::new(
         pool_at_new_price,
         position.liquidity,
         position.tick_lower,
         position.tick_upper,
-    ))
+    );
+    position_at_new_price.fee_state = position.fee_state;
+    Ok(position_at_new_price)
 }
 
 /// Predict the position after rebalance assuming the pool price becomes the specified price.
@@ -533,6 +942,68 @@ mod tests {
         // });
     }
 
+    #[tokio::test]
+    async fn test_get_positions_by_owner() {
+        let provider = PROVIDER.clone();
+        let owner = address!("4bD047CA72fa05F0B89ad08FE5Ba5ccdC07DFFBF");
+        let all = get_all_positions_by_owner(NPM, owner, provider.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        let page = get_positions_by_owner(1, NPM, owner, 1, 3, provider.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        assert_eq!(page.total, all.len());
+        assert_eq!(page.next_index, (all.len() > 4).then_some(4));
+        for (owned, state) in page.positions.iter().zip(&all[1..]) {
+            assert_eq!(owned.token_id, state.tokenId);
+            assert_eq!(owned.position.liquidity, state.position.liquidity);
+            assert_eq!(owned.position.tick_lower, state.position.tickLower.as_i32());
+            assert_eq!(
+                owned.in_range,
+                owned.position.tick_lower <= state.slot0.tick.as_i32()
+                    && state.slot0.tick.as_i32() < owned.position.tick_upper
+            );
+            let (tokens_owed0, tokens_owed1) =
+                get_collectable_token_amounts(NPM, owned.token_id, provider.clone(), BLOCK_ID)
+                    .await
+                    .unwrap();
+            assert_eq!(owned.tokens_owed.0.quotient(), tokens_owed0.to_big_int());
+            assert_eq!(owned.tokens_owed.1.quotient(), tokens_owed1.to_big_int());
+        }
+        let empty = get_positions_by_owner(1, NPM, owner, all.len(), 3, provider, BLOCK_ID)
+            .await
+            .unwrap();
+        assert!(empty.positions.is_empty());
+        assert_eq!(empty.next_index, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_positions_by_owner_with_tick_data_provider() {
+        let provider = PROVIDER.clone();
+        let owner = address!("4bD047CA72fa05F0B89ad08FE5Ba5ccdC07DFFBF");
+        let page = get_positions_by_owner(1, NPM, owner, 0, 3, provider.clone(), BLOCK_ID)
+            .await
+            .unwrap();
+        let with_ticks = get_positions_by_owner_with_tick_data_provider::<_, _, i32>(
+            1, NPM, owner, 0, 3, provider, BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert_eq!(with_ticks.total, page.total);
+        assert_eq!(with_ticks.positions.len(), page.positions.len());
+        for (owned, with_ticks) in page.positions.iter().zip(&with_ticks.positions) {
+            let (position, pool) = (&owned.position, &with_ticks.position.pool);
+            assert_eq!(with_ticks.token_id, owned.token_id);
+            assert_eq!(with_ticks.position.liquidity, position.liquidity);
+            assert_eq!(pool.tick_current, position.pool.tick_current);
+            assert_eq!(
+                pool.tick_data_provider.pool,
+                position.pool.address(None, None)
+            );
+            assert_eq!(with_ticks.tokens_owed, owned.tokens_owed);
+        }
+    }
+
     #[tokio::test]
     async fn test_get_collectable_token_amounts() {
         let (tokens_owed_0, tokens_owed_1) =
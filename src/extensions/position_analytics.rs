@@ -0,0 +1,219 @@
+//! ## Position Analytics
+//! This module provides functions to evaluate the performance of a [`Position`] against simply
+//! holding the deposited tokens, including impermanent loss, fee income and net PnL.
+
+use crate::prelude::{Error, *};
+use uniswap_sdk_core::prelude::*;
+
+/// The state of a position when it was opened, used as the baseline for analytics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionEntry {
+    /// The amount of token0 deposited
+    pub amount0: CurrencyAmount<Token>,
+    /// The amount of token1 deposited
+    pub amount1: CurrencyAmount<Token>,
+    /// The price of token0 in terms of token1 when the position was opened
+    pub token0_price: Price<Token, Token>,
+}
+
+impl PositionEntry {
+    /// Records the entry state of a position at the current pool price
+    ///
+    /// ## Arguments
+    ///
+    /// * `position`: The position that was just opened
+    #[inline]
+    pub fn from_position<TP: TickDataProvider>(position: &Position<TP>) -> Result<Self, Error> {
+        Ok(Self {
+            amount0: position.amount0()?,
+            amount1: position.amount1()?,
+            token0_price: position.pool.token0_price(),
+        })
+    }
+}
+
+/// The performance of a position relative to its entry state, denominated in a numeraire token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PositionPnL {
+    /// The value of the deposited amounts at the entry price
+    pub entry_value: CurrencyAmount<Token>,
+    /// The value of holding the deposited amounts at the current price
+    pub hodl_value: CurrencyAmount<Token>,
+    /// The value of the position's liquidity at the current price, excluding fees
+    pub position_value: CurrencyAmount<Token>,
+    /// The value of the fees earned by the position at the current price
+    pub fee_value: CurrencyAmount<Token>,
+    /// The position value minus the HODL value, negative when the position underperforms
+    pub impermanent_loss: CurrencyAmount<Token>,
+    /// The impermanent loss relative to the HODL value
+    pub impermanent_loss_percent: Percent,
+    /// The position value plus fees minus the entry value
+    pub net_pnl: CurrencyAmount<Token>,
+}
+
+/// Values a pair of token amounts in terms of the numeraire at the given price of token0.
+fn value_in_numeraire(
+    amount0: &CurrencyAmount<Token>,
+    amount1: &CurrencyAmount<Token>,
+    token0_price: &Price<Token, Token>,
+    numeraire: &Token,
+) -> Result<CurrencyAmount<Token>, Error> {
+    if numeraire.equals(&token0_price.quote_currency) {
+        Ok(token0_price.quote(amount0)?.add(amount1)?)
+    } else if numeraire.equals(&token0_price.base_currency) {
+        Ok(amount0.add(&token0_price.invert().quote(amount1)?)?)
+    } else {
+        Err(Error::InvalidToken)
+    }
+}
+
+/// Computes the value, impermanent loss, fee income and net PnL of a position at the current pool
+/// price.
+///
+/// ## Arguments
+///
+/// * `entry`: The entry state of the position
+/// * `position`: The current position
+/// * `fees`: The uncollected fees of token0 and token1 earned by the position, if any
+/// * `numeraire`: The pool token in which values are denominated
+#[inline]
+pub fn get_position_pnl<TP>(
+    entry: &PositionEntry,
+    position: &Position<TP>,
+    fees: Option<&(CurrencyAmount<Token>, CurrencyAmount<Token>)>,
+    numeraire: &Token,
+) -> Result<PositionPnL, Error>
+where
+    TP: TickDataProvider,
+{
+    if !position.pool.involves_token(numeraire) {
+        return Err(Error::InvalidToken);
+    }
+    let price = position.pool.token0_price();
+    let entry_value = value_in_numeraire(
+        &entry.amount0,
+        &entry.amount1,
+        &entry.token0_price,
+        numeraire,
+    )?;
+    let hodl_value = value_in_numeraire(&entry.amount0, &entry.amount1, &price, numeraire)?;
+    let position_value = value_in_numeraire(
+        &position.amount0()?,
+        &position.amount1()?,
+        &price,
+        numeraire,
+    )?;
+    let fee_value = match fees {
+        Some((fees0, fees1)) => value_in_numeraire(fees0, fees1, &price, numeraire)?,
+        None => CurrencyAmount::from_raw_amount(numeraire.clone(), 0)?,
+    };
+    let impermanent_loss = position_value.subtract(&hodl_value)?;
+    let impermanent_loss_percent = if hodl_value.numerator.is_zero() {
+        Percent::new(0, 1)
+    } else {
+        Percent::new(
+            impermanent_loss.numerator * hodl_value.denominator,
+            impermanent_loss.denominator * hodl_value.numerator,
+        )
+    };
+    let net_pnl = position_value.add(&fee_value)?.subtract(&entry_value)?;
+    Ok(PositionPnL {
+        entry_value,
+        hodl_value,
+        position_value,
+        fee_value,
+        impermanent_loss,
+        impermanent_loss_percent,
+        net_pnl,
+    })
+}
+
+/// Computes the value, impermanent loss, fee income and net PnL of a position if the pool price
+/// becomes the specified price.
+///
+/// ## Arguments
+///
+/// * `entry`: The entry state of the position
+/// * `position`: The current position
+/// * `new_price`: The hypothetical pool price
+/// * `fees`: The uncollected fees of token0 and token1 earned by the position, if any
+/// * `numeraire`: The pool token in which values are denominated
+#[inline]
+pub fn get_position_pnl_at_price<TP>(
+    entry: &PositionEntry,
+    position: Position<TP>,
+    new_price: BigDecimal,
+    fees: Option<&(CurrencyAmount<Token>, CurrencyAmount<Token>)>,
+    numeraire: &Token,
+) -> Result<PositionPnL, Error>
+where
+    TP: TickDataProvider,
+{
+    get_position_pnl(
+        entry,
+        &get_position_at_price(position, new_price)?,
+        fees,
+        numeraire,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+
+    fn make_position() -> Position {
+        Position::new(POOL_0_1.clone(), 1_000_000_000_000, -600, 600)
+    }
+
+    #[test]
+    fn test_no_loss_at_entry_price() {
+        let position = make_position();
+        let entry = PositionEntry::from_position(&position).unwrap();
+        let pnl = get_position_pnl(&entry, &position, None, &TOKEN1).unwrap();
+        assert_eq!(pnl.entry_value, pnl.hodl_value);
+        assert_eq!(pnl.position_value, pnl.hodl_value);
+        assert!(pnl.impermanent_loss.numerator.is_zero());
+        assert!(pnl.net_pnl.numerator.is_zero());
+    }
+
+    #[test]
+    fn test_fees_count_towards_pnl() {
+        let position = make_position();
+        let entry = PositionEntry::from_position(&position).unwrap();
+        let fees = (
+            CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+            CurrencyAmount::from_raw_amount(TOKEN1.clone(), 50).unwrap(),
+        );
+        let pnl = get_position_pnl(&entry, &position, Some(&fees), &TOKEN0).unwrap();
+        assert_eq!(pnl.fee_value.quotient(), BigInt::from(150));
+        assert_eq!(pnl.net_pnl.quotient(), BigInt::from(150));
+    }
+
+    #[test]
+    fn test_loss_when_price_moves() {
+        let position = make_position();
+        let entry = PositionEntry::from_position(&position).unwrap();
+        for price in [BigDecimal::from(1.05), BigDecimal::from(0.95)] {
+            let pnl =
+                get_position_pnl_at_price(&entry, position.clone(), price, None, &TOKEN1).unwrap();
+            assert!(pnl.impermanent_loss.numerator.is_negative());
+            assert!(pnl.impermanent_loss_percent.numerator.is_negative());
+            assert!(pnl.position_value < pnl.hodl_value);
+            assert_eq!(
+                pnl.net_pnl,
+                pnl.position_value.subtract(&pnl.entry_value).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_numeraire() {
+        let position = make_position();
+        let entry = PositionEntry::from_position(&position).unwrap();
+        assert!(matches!(
+            get_position_pnl(&entry, &position, None, &DAI),
+            Err(Error::InvalidToken)
+        ));
+    }
+}
@@ -0,0 +1,374 @@
+//! ## Sandwich
+//! This module estimates how much a sandwich attacker can extract from an exact input trade given
+//! its slippage tolerance, by simulating the front-run, the trade and the back-run on clones of
+//! the pools in the route.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::U256;
+use uniswap_sdk_core::prelude::*;
+
+/// The outcome of the most profitable sandwich of a trade within its slippage tolerance
+#[derive(Clone, Debug, PartialEq)]
+pub struct SandwichAnalysis {
+    /// The amount of the input token the attacker swaps before the trade
+    pub front_run_amount_in: CurrencyAmount<Token>,
+    /// The amount of the output token the attacker receives from the front-run
+    pub front_run_amount_out: CurrencyAmount<Token>,
+    /// The amount of the input token the attacker receives from swapping back after the trade
+    pub back_run_amount_out: CurrencyAmount<Token>,
+    /// The profit of the attacker in the input token before gas, or zero if the sandwich is
+    /// unprofitable
+    pub attacker_profit: CurrencyAmount<Token>,
+    /// The output of the trade without a sandwich
+    pub expected_amount_out: CurrencyAmount<Token>,
+    /// The output of the trade when sandwiched, at least the minimum output of the trade
+    pub sandwiched_amount_out: CurrencyAmount<Token>,
+    /// The output the trade loses to the sandwich
+    pub user_loss: CurrencyAmount<Token>,
+    /// The largest slippage tolerance at which a sandwich is not profitable before gas
+    pub recommended_slippage_tolerance: Percent,
+}
+
+/// The amounts of a simulated sandwich
+#[derive(Clone, Copy, Debug, Default)]
+struct Sandwich {
+    front_run_amount_in: U256,
+    front_run_amount_out: U256,
+    victim_amount_out: U256,
+    back_run_amount_out: U256,
+}
+
+impl Sandwich {
+    const fn profit(&self) -> U256 {
+        self.back_run_amount_out
+            .saturating_sub(self.front_run_amount_in)
+    }
+}
+
+/// Swaps `amount_in` through the pools in order, or in reverse order if `reverse`, updating the
+/// pool states. Returns `None` if a pool cannot absorb the swap.
+fn swap_through<TP: TickDataProvider>(
+    pools: &mut [Pool<TP>],
+    token_in: &Token,
+    amount_in: U256,
+    reverse: bool,
+) -> Result<Option<U256>, Error> {
+    if amount_in.is_zero() {
+        return Ok(Some(U256::ZERO));
+    }
+    let mut amount = CurrencyAmount::from_raw_amount(token_in.clone(), amount_in.to_big_int())?;
+    let len = pools.len();
+    for i in 0..len {
+        let pool = &mut pools[if reverse { len - 1 - i } else { i }];
+        amount = match pool.get_output_amount_mut(&amount, None) {
+            Ok(amount_out) => amount_out,
+            Err(e) if matches!(e.root_cause(), Error::InsufficientLiquidity) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+    }
+    Ok(Some(U256::from_big_int(amount.quotient())))
+}
+
+/// Simulates a front-run of `front_run_amount_in`, the trade and a back-run of the front-run
+/// output on clones of the pools. Returns `None` if a pool cannot absorb one of the swaps.
+fn simulate_sandwich<TP: Clone + TickDataProvider>(
+    pools: &[Pool<TP>],
+    token_in: &Token,
+    token_out: &Token,
+    front_run_amount_in: U256,
+    victim_amount_in: U256,
+) -> Result<Option<Sandwich>, Error> {
+    let mut pools = pools.to_vec();
+    let Some(front_run_amount_out) =
+        swap_through(&mut pools, token_in, front_run_amount_in, false)?
+    else {
+        return Ok(None);
+    };
+    let Some(victim_amount_out) = swap_through(&mut pools, token_in, victim_amount_in, false)?
+    else {
+        return Ok(None);
+    };
+    let Some(back_run_amount_out) =
+        swap_through(&mut pools, token_out, front_run_amount_out, true)?
+    else {
+        return Ok(None);
+    };
+    Ok(Some(Sandwich {
+        front_run_amount_in,
+        front_run_amount_out,
+        victim_amount_out,
+        back_run_amount_out,
+    }))
+}
+
+/// Finds the largest front-run after which the trade still receives at least
+/// `minimum_amount_out`, which is the most profitable sandwich as the attacker's profit grows with
+/// the price impact imposed on the trade.
+fn max_sandwich<TP: Clone + TickDataProvider>(
+    pools: &[Pool<TP>],
+    token_in: &Token,
+    token_out: &Token,
+    victim_amount_in: U256,
+    minimum_amount_out: U256,
+) -> Result<Sandwich, Error> {
+    let feasible = |front_run_amount_in: U256| -> Result<Option<Sandwich>, Error> {
+        Ok(simulate_sandwich(
+            pools,
+            token_in,
+            token_out,
+            front_run_amount_in,
+            victim_amount_in,
+        )?
+        .filter(|sandwich| sandwich.victim_amount_out >= minimum_amount_out))
+    };
+    let mut best = feasible(U256::ZERO)?.ok_or(Error::InsufficientLiquidity)?;
+    // The trade output decreases with the front-run, so find an infeasible upper bound and bisect.
+    let mut lo = U256::ZERO;
+    let mut hi = victim_amount_in.max(U256::from(1));
+    while let Some(sandwich) = feasible(hi)? {
+        lo = hi;
+        best = sandwich;
+        match hi.checked_mul(U256::from(2)) {
+            Some(next) => hi = next,
+            None => return Ok(best),
+        }
+    }
+    while hi - lo > U256::from(1) {
+        let mid = lo + ((hi - lo) >> 1);
+        match feasible(mid)? {
+            Some(sandwich) => {
+                lo = mid;
+                best = sandwich;
+            }
+            None => hi = mid,
+        }
+    }
+    Ok(best)
+}
+
+/// Analyzes the exposure of an exact input trade to a sandwich attack given its slippage
+/// tolerance.
+///
+/// The attacker front-runs the trade with the largest swap after which the trade still receives
+/// its minimum output, then swaps the front-run output back after the trade. Every swap is
+/// simulated with [`Pool::get_output_amount_mut`] on clones of the pools in the route, so
+/// multi-hop routes account for the price impact on every pool.
+///
+/// ## Arguments
+///
+/// * `trade`: The exact input trade through a single route
+/// * `slippage_tolerance`: The slippage tolerance the trade is submitted with
+///
+/// Returns [`Error::UnsupportedTrade`] if the trade is not an exact input trade through a single
+/// route.
+#[inline]
+pub fn analyze_sandwich<TInput, TOutput, TP>(
+    trade: &Trade<TInput, TOutput, TP>,
+    slippage_tolerance: &Percent,
+) -> Result<SandwichAnalysis, Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: Clone + TickDataProvider,
+{
+    if trade.trade_type != TradeType::ExactInput {
+        return Err(Error::UnsupportedTrade("EXACT_INPUT"));
+    }
+    if trade.swaps.len() != 1 {
+        return Err(Error::UnsupportedTrade("SINGLE_ROUTE"));
+    }
+    let route = trade.route();
+    let token_in = route.input.wrapped();
+    let token_out = route.output.wrapped();
+    let victim_amount_in = U256::from_big_int(trade.input_amount()?.quotient());
+    let sandwich_at = |slippage_tolerance: &Percent| -> Result<Sandwich, Error> {
+        let minimum_amount_out = U256::from_big_int(
+            trade
+                .minimum_amount_out(slippage_tolerance.clone(), None)?
+                .quotient(),
+        );
+        max_sandwich(
+            &route.pools,
+            token_in,
+            token_out,
+            victim_amount_in,
+            minimum_amount_out,
+        )
+    };
+
+    let sandwich = sandwich_at(slippage_tolerance)?;
+    let expected_amount_out = simulate_sandwich(
+        &route.pools,
+        token_in,
+        token_out,
+        U256::ZERO,
+        victim_amount_in,
+    )?
+    .ok_or(Error::InsufficientLiquidity)?
+    .victim_amount_out;
+
+    // The profit grows with the slippage tolerance, so bisect for the largest tolerance in bips
+    // at which the sandwich is unprofitable.
+    let recommended_slippage_tolerance = if sandwich.profit().is_zero() {
+        slippage_tolerance.clone()
+    } else {
+        let bips =
+            |percent: &Percent| percent.numerator * BigInt::from(10000) / percent.denominator;
+        let mut lo = BigInt::ZERO;
+        let mut hi = bips(slippage_tolerance);
+        while hi - lo > BigInt::ONE {
+            let mid = (lo + hi) / BigInt::from(2);
+            if sandwich_at(&Percent::new(mid, 10000))?.profit().is_zero() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        Percent::new(lo, 10000)
+    };
+
+    let amount = |token: &Token, amount: U256| {
+        CurrencyAmount::from_raw_amount(token.clone(), amount.to_big_int())
+    };
+    Ok(SandwichAnalysis {
+        front_run_amount_in: amount(token_in, sandwich.front_run_amount_in)?,
+        front_run_amount_out: amount(token_out, sandwich.front_run_amount_out)?,
+        back_run_amount_out: amount(token_in, sandwich.back_run_amount_out)?,
+        attacker_profit: amount(token_in, sandwich.profit())?,
+        expected_amount_out: amount(token_out, expected_amount_out)?,
+        sandwiched_amount_out: amount(token_out, sandwich.victim_amount_out)?,
+        user_loss: amount(
+            token_out,
+            expected_amount_out.saturating_sub(sandwich.victim_amount_out),
+        )?,
+        recommended_slippage_tolerance,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloc::vec;
+
+    fn trade(
+        pools: Vec<Pool<TickListDataProvider>>,
+        token_out: &Token,
+        amount_in: u64,
+    ) -> Trade<Token, Token, TickListDataProvider> {
+        Trade::from_route(
+            Route::new(pools, TOKEN0.clone(), token_out.clone()),
+            CurrencyAmount::from_raw_amount(TOKEN0.clone(), amount_in).unwrap(),
+            TradeType::ExactInput,
+        )
+        .unwrap()
+    }
+
+    fn assert_sandwich(
+        trade: &Trade<Token, Token, TickListDataProvider>,
+        slippage_tolerance: &Percent,
+    ) {
+        let analysis = analyze_sandwich(trade, slippage_tolerance).unwrap();
+        let minimum_amount_out = trade
+            .minimum_amount_out(slippage_tolerance.clone(), None)
+            .unwrap();
+        assert_eq!(
+            analysis.expected_amount_out.quotient(),
+            trade.output_amount().unwrap().quotient()
+        );
+        assert!(analysis.sandwiched_amount_out.quotient() >= minimum_amount_out.quotient());
+        assert!(analysis.front_run_amount_in.quotient() > BigInt::ZERO);
+        assert!(analysis.attacker_profit.quotient() > BigInt::ZERO);
+        assert_eq!(
+            analysis.user_loss.quotient(),
+            analysis.expected_amount_out.quotient() - analysis.sandwiched_amount_out.quotient()
+        );
+        assert!(analysis.recommended_slippage_tolerance < *slippage_tolerance);
+
+        let recommended =
+            analyze_sandwich(trade, &analysis.recommended_slippage_tolerance).unwrap();
+        assert!(recommended.attacker_profit.quotient().is_zero());
+        assert_eq!(
+            recommended.recommended_slippage_tolerance,
+            analysis.recommended_slippage_tolerance
+        );
+    }
+
+    #[test]
+    fn test_single_hop() {
+        let trade = trade(
+            vec![make_pool(TOKEN0.clone(), TOKEN1.clone())],
+            &TOKEN1,
+            10000,
+        );
+        assert_sandwich(&trade, &Percent::new(5, 100));
+    }
+
+    #[test]
+    fn test_multi_hop() {
+        let trade = trade(
+            vec![
+                make_pool(TOKEN0.clone(), TOKEN1.clone()),
+                make_pool(TOKEN1.clone(), TOKEN2.clone()),
+            ],
+            &TOKEN2,
+            10000,
+        );
+        assert_sandwich(&trade, &Percent::new(5, 100));
+    }
+
+    #[test]
+    fn test_zero_slippage_is_not_exposed() {
+        let trade = trade(
+            vec![make_pool(TOKEN0.clone(), TOKEN1.clone())],
+            &TOKEN1,
+            10000,
+        );
+        let analysis = analyze_sandwich(&trade, &Percent::default()).unwrap();
+        assert!(analysis.attacker_profit.quotient().is_zero());
+        assert!(analysis.user_loss.quotient().is_zero());
+        assert_eq!(analysis.recommended_slippage_tolerance, Percent::default());
+    }
+
+    #[test]
+    fn test_unsupported_trades() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let trade = Trade::from_route(
+            Route::new(vec![pool.clone()], TOKEN0.clone(), TOKEN1.clone()),
+            CurrencyAmount::from_raw_amount(TOKEN1.clone(), 10000).unwrap(),
+            TradeType::ExactOutput,
+        )
+        .unwrap();
+        assert!(matches!(
+            analyze_sandwich(&trade, &Percent::new(5, 100)),
+            Err(Error::UnsupportedTrade("EXACT_INPUT"))
+        ));
+
+        let route = Route::new(vec![pool.clone()], TOKEN0.clone(), TOKEN1.clone());
+        let trade = Trade::from_routes(
+            vec![
+                (
+                    CurrencyAmount::from_raw_amount(TOKEN0.clone(), 5000).unwrap(),
+                    route.clone(),
+                ),
+                (
+                    CurrencyAmount::from_raw_amount(TOKEN0.clone(), 5000).unwrap(),
+                    Route::new(
+                        vec![
+                            make_pool(TOKEN0.clone(), TOKEN2.clone()),
+                            make_pool(TOKEN1.clone(), TOKEN2.clone()),
+                        ],
+                        TOKEN0.clone(),
+                        TOKEN1.clone(),
+                    ),
+                ),
+            ],
+            TradeType::ExactInput,
+        )
+        .unwrap();
+        assert!(matches!(
+            analyze_sandwich(&trade, &Percent::new(5, 100)),
+            Err(Error::UnsupportedTrade("SINGLE_ROUTE"))
+        ));
+    }
+}
@@ -1,21 +1,206 @@
 //! ## State Overrides
-//! This module provides functions to generate state overrides for ERC20 tokens.
+//! This module provides functions to generate state overrides for ERC20 tokens, Permit2 allowances
+//! and native balances.
+//!
+//! The storage slots of ERC20 balances and allowances are discovered by probing the slots read by
+//! `balanceOf` and `allowance`, possibly in another contract than the token, and verifying each
+//! candidate with a follow-up `eth_call`. Slots of proxy implementations are skipped, and the
+//! standard Solidity and Vyper mapping layouts are probed if no candidate from the access list can
+//! be verified.
 
-use crate::prelude::Error;
+use crate::prelude::{is_revert, Error};
 use alloc::vec::Vec;
 use alloy::{
-    eips::eip2930::{AccessList, AccessListItem},
+    eips::{eip2930::AccessList, BlockId, BlockNumberOrTag},
     network::{Network, TransactionBuilder},
     providers::Provider,
     rpc::types::state::{AccountOverride, StateOverride},
+    sol,
 };
 use alloy_primitives::{
-    map::{B256HashMap, B256HashSet},
-    Address, B256, U256,
+    address,
+    aliases::{U160, U48},
+    b256, keccak256,
+    map::B256HashMap,
+    uint, Address, Bytes, B256, U256,
 };
-use alloy_sol_types::SolCall;
+use alloy_sol_types::{SolCall, SolValue};
 use uniswap_lens::bindings::ierc20::IERC20;
 
+/// The canonical `Permit2` address, deployed at the same address on all chains.
+pub const PERMIT2_ADDRESS: Address = address!("000000000022D473030F116dDEE9F6B43aC78BA3");
+
+/// The storage index of the `allowance` mapping of `Permit2`.
+const PERMIT2_ALLOWANCE_INDEX: U256 = uint!(1_U256);
+
+/// The value written to a candidate slot to verify it, small enough to fit packed balances.
+const PROBE_VALUE: U256 = uint!(0x1a2b3c4d5e6f7a8b9c_U256);
+
+/// The number of mapping indexes probed when no slot from the access list can be verified.
+const MAX_MAPPING_INDEX: u64 = 10;
+
+/// The slots holding the implementation, beacon and admin of EIP-1967 and legacy `ZeppelinOS`
+/// proxies, which are read by every proxied call.
+const PROXY_SLOTS: [B256; 4] = [
+    b256!("360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc"),
+    b256!("a3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50"),
+    b256!("b53127684a568b3173ae13b9f8a6016e243e63b6e8ee1178d6a717850b5d6103"),
+    b256!("7050c9e0f4ca769c69bd3a8ef740bc37934f8e2c036e5a723fd8ee048ed3f8c3"),
+];
+
+sol! {
+    #[sol(rpc)]
+    interface IPermit2 {
+        function allowance(address user, address token, address spender)
+            external
+            view
+            returns (uint160 amount, uint48 expiration, uint48 nonce);
+    }
+}
+
+/// Returns the slot of `key` in a Solidity mapping at `index`.
+fn solidity_mapping_slot(key: Address, index: U256) -> B256 {
+    keccak256((key, index).abi_encode())
+}
+
+/// Returns the slot of `key` in a Vyper mapping at `index`.
+fn vyper_mapping_slot(key: Address, index: U256) -> B256 {
+    keccak256((index, key).abi_encode())
+}
+
+/// Returns the candidate slots of a value in the standard Solidity and Vyper mapping layouts,
+/// keyed by `keys` in order.
+fn mapping_slot_candidates(keys: &[Address]) -> Vec<B256> {
+    (0..=MAX_MAPPING_INDEX)
+        .flat_map(|index| {
+            let index = U256::from(index);
+            let solidity = keys.iter().fold(B256::from(index), |slot, key| {
+                solidity_mapping_slot(*key, slot.into())
+            });
+            let vyper = keys.iter().fold(B256::from(index), |slot, key| {
+                vyper_mapping_slot(*key, slot.into())
+            });
+            [solidity, vyper]
+        })
+        .collect()
+}
+
+/// Returns the storage slots of an access list as candidates, skipping the slots of proxies.
+fn access_list_candidates(access_list: AccessList) -> Vec<(Address, B256)> {
+    access_list
+        .0
+        .into_iter()
+        .flat_map(|item| {
+            let address = item.address;
+            item.storage_keys
+                .into_iter()
+                .filter(|slot| !PROXY_SLOTS.contains(slot))
+                .map(move |slot| (address, slot))
+        })
+        .collect()
+}
+
+/// Returns a state override writing `value` to `slot` of `address`.
+fn slot_override(address: Address, slot: B256, value: U256) -> StateOverride {
+    StateOverride::from_iter([(
+        address,
+        AccountOverride {
+            state_diff: Some(B256HashMap::from_iter([(slot, B256::from(value))])),
+            ..Default::default()
+        },
+    )])
+}
+
+/// Finds the storage slot, in the token or in another contract, which the uint256 returned by the
+/// call of `input` on `token` is read from. Calls which revert only rule out a candidate, while
+/// other errors of the provider are returned.
+async fn find_storage_slot<N, P>(
+    token: Address,
+    input: Bytes,
+    keys: &[Address],
+    provider: &P,
+    block_id: BlockId,
+) -> Result<(Address, B256), Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let tx = N::TransactionRequest::default()
+        .with_to(token)
+        .with_gas_limit(0x11E1A300) // avoids "intrinsic gas too low" error
+        .with_input(input);
+    let access_list = match provider.create_access_list(&tx).block_id(block_id).await {
+        Ok(result) => result.access_list,
+        // the mapping layouts are still probed if the call reverts without overrides
+        Err(e) if is_revert(&e) => AccessList::default(),
+        Err(e) => return Err(e.into()),
+    };
+    let candidates = access_list_candidates(access_list).into_iter().chain(
+        mapping_slot_candidates(keys)
+            .into_iter()
+            .map(|slot| (token, slot)),
+    );
+    for (address, slot) in candidates {
+        // a candidate which breaks the call, such as a packed flag, is not the slot
+        let output = match provider
+            .call(tx.clone())
+            .overrides(slot_override(address, slot, PROBE_VALUE))
+            .block(block_id)
+            .await
+        {
+            Ok(output) => output,
+            Err(e) if is_revert(&e) => continue,
+            Err(e) => return Err(e.into()),
+        };
+        if U256::abi_decode(&output, true).is_ok_and(|value| value == PROBE_VALUE) {
+            return Ok((address, slot));
+        }
+    }
+    Err(Error::InvalidAccessList)
+}
+
+/// Merges `other` into `overrides`, combining the storage overrides of the same account.
+///
+/// ## Arguments
+///
+/// * `overrides`: The state overrides to merge into
+/// * `other`: The state overrides to merge, taking precedence over `overrides`
+#[inline]
+pub fn merge_state_overrides(overrides: &mut StateOverride, other: StateOverride) {
+    for (address, account) in other {
+        let existing = overrides.entry(address).or_default();
+        if let Some(state_diff) = account.state_diff {
+            existing
+                .state_diff
+                .get_or_insert_with(Default::default)
+                .extend(state_diff);
+        }
+        if account.balance.is_some() {
+            existing.balance = account.balance;
+        }
+        if account.nonce.is_some() {
+            existing.nonce = account.nonce;
+        }
+        if account.code.is_some() {
+            existing.code = account.code;
+        }
+        if account.state.is_some() {
+            existing.state = account.state;
+        }
+    }
+}
+
+/// Returns the state overrides setting the ERC20 balance of `owner` and its allowance to
+/// `spender` to `amount`, verifying the discovered slots with `eth_call`.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `owner`: The owner of the tokens
+/// * `spender`: The spender approved by the owner
+/// * `amount`: The balance and allowance to set
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to discover the slots at
 #[inline]
 pub async fn get_erc20_state_overrides<N, P>(
     token: Address,
@@ -23,69 +208,191 @@ pub async fn get_erc20_state_overrides<N, P>(
     spender: Address,
     amount: U256,
     provider: &P,
+    block_id: Option<BlockId>,
 ) -> Result<StateOverride, Error>
 where
     N: Network,
     P: Provider<N>,
 {
-    let balance_tx = N::TransactionRequest::default()
-        .with_to(token)
-        .with_gas_limit(0x11E1A300) // avoids "intrinsic gas too low" error
-        .with_input(IERC20::balanceOfCall { account: owner }.abi_encode());
-    let allowance_tx = N::TransactionRequest::default()
-        .with_to(token)
-        .with_gas_limit(0x11E1A300)
-        .with_input(IERC20::allowanceCall { owner, spender }.abi_encode());
-    let balance_access_list = provider.create_access_list(&balance_tx).await?.access_list;
-    let allowance_access_list = provider
-        .create_access_list(&allowance_tx)
-        .await?
-        .access_list;
-    // tokens on L2 and those with a proxy will have more than one access list entry
-    let filtered_balance_access_list = filter_access_list(balance_access_list, token);
-    let filtered_allowance_access_list = filter_access_list(allowance_access_list, token);
-    if filtered_balance_access_list.len() != 1 || filtered_allowance_access_list.len() != 1 {
-        return Err(Error::InvalidAccessList);
-    }
-    // get rid of the storage key of implementation address
-    let balance_slots_set = B256HashSet::from_iter(
-        filtered_balance_access_list
-            .into_iter()
-            .next()
-            .unwrap()
-            .storage_keys,
-    );
-    let allowance_slots_set = B256HashSet::from_iter(
-        filtered_allowance_access_list
-            .into_iter()
-            .next()
-            .unwrap()
-            .storage_keys,
-    );
-    let state_diff = B256HashMap::from_iter(
-        balance_slots_set
-            .symmetric_difference(&allowance_slots_set)
-            .cloned()
-            .map(|slot| (slot, B256::from(amount))),
-    );
-    if state_diff.len() != 2 {
-        return Err(Error::InvalidAccessList);
-    }
-    Ok(StateOverride::from_iter([(
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let (balance_address, balance_slot) = find_storage_slot(
+        token,
+        IERC20::balanceOfCall { account: owner }.abi_encode().into(),
+        &[owner],
+        provider,
+        block_id,
+    )
+    .await?;
+    let (allowance_address, allowance_slot) = find_storage_slot(
         token,
+        IERC20::allowanceCall { owner, spender }.abi_encode().into(),
+        &[owner, spender],
+        provider,
+        block_id,
+    )
+    .await?;
+    let mut overrides = slot_override(balance_address, balance_slot, amount);
+    merge_state_overrides(
+        &mut overrides,
+        slot_override(allowance_address, allowance_slot, amount),
+    );
+    Ok(overrides)
+}
+
+/// Returns the state override setting the native balance of `owner` to `amount`.
+///
+/// ## Arguments
+///
+/// * `owner`: The account to fund
+/// * `amount`: The native balance to set, in wei
+#[inline]
+#[must_use]
+pub fn get_native_balance_override(owner: Address, amount: U256) -> StateOverride {
+    StateOverride::from_iter([(
+        owner,
         AccountOverride {
-            state_diff: Some(state_diff),
+            balance: Some(amount),
             ..Default::default()
         },
-    )]))
+    )])
 }
 
-fn filter_access_list(access_list: AccessList, token: Address) -> Vec<AccessListItem> {
-    access_list
-        .0
+/// Returns the state overrides setting the ERC20 balance of `owner` and its allowance to
+/// [`PERMIT2_ADDRESS`] to `amount`, and its `Permit2` allowance to `spender` to `amount` without
+/// expiration.
+///
+/// ## Arguments
+///
+/// * `token`: The address of the token
+/// * `owner`: The owner of the tokens
+/// * `spender`: The spender approved by the owner through `Permit2`
+/// * `amount`: The balance and allowances to set, capped at `uint160` for `Permit2`
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to discover the slots at
+#[inline]
+pub async fn get_permit2_state_overrides<N, P>(
+    token: Address,
+    owner: Address,
+    spender: Address,
+    amount: U256,
+    provider: &P,
+    block_id: Option<BlockId>,
+) -> Result<StateOverride, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let mut overrides =
+        get_erc20_state_overrides(token, owner, PERMIT2_ADDRESS, amount, provider, block_id)
+            .await?;
+    merge_state_overrides(
+        &mut overrides,
+        get_permit2_allowance_override(token, owner, spender, amount),
+    );
+    Ok(overrides)
+}
+
+/// Returns the state override of the packed `Permit2` allowance of `owner` to `spender`, with the
+/// maximum expiration and a zero nonce.
+fn get_permit2_allowance_override(
+    token: Address,
+    owner: Address,
+    spender: Address,
+    amount: U256,
+) -> StateOverride {
+    let slot = [owner, token, spender]
         .into_iter()
-        .filter(|item| item.address == token)
-        .collect()
+        .fold(B256::from(PERMIT2_ALLOWANCE_INDEX), |slot, key| {
+            solidity_mapping_slot(key, slot.into())
+        });
+    let amount = amount.min(U256::from(U160::MAX));
+    let expiration = U256::from(U48::MAX) << 160;
+    slot_override(PERMIT2_ADDRESS, slot, amount | expiration)
+}
+
+/// A builder of the state overrides funding an account for a simulation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateOverridesBuilder {
+    owner: Address,
+    native_balance: Option<U256>,
+    erc20: Vec<(Address, Address, U256)>,
+    permit2: Vec<(Address, Address, U256)>,
+}
+
+impl StateOverridesBuilder {
+    /// Creates a builder of the state overrides funding `owner`.
+    #[inline]
+    #[must_use]
+    pub const fn new(owner: Address) -> Self {
+        Self {
+            owner,
+            native_balance: None,
+            erc20: Vec::new(),
+            permit2: Vec::new(),
+        }
+    }
+
+    /// Sets the native balance of the owner.
+    #[inline]
+    #[must_use]
+    pub const fn with_native_balance(mut self, amount: U256) -> Self {
+        self.native_balance = Some(amount);
+        self
+    }
+
+    /// Sets the ERC20 balance of the owner and its allowance to `spender`.
+    #[inline]
+    #[must_use]
+    pub fn with_erc20(mut self, token: Address, spender: Address, amount: U256) -> Self {
+        self.erc20.push((token, spender, amount));
+        self
+    }
+
+    /// Sets the ERC20 balance of the owner and its allowance to `spender` through `Permit2`.
+    #[inline]
+    #[must_use]
+    pub fn with_permit2(mut self, token: Address, spender: Address, amount: U256) -> Self {
+        self.permit2.push((token, spender, amount));
+        self
+    }
+
+    /// Discovers the storage slots through the provider and builds the state overrides.
+    ///
+    /// ## Arguments
+    ///
+    /// * `provider`: The alloy provider
+    /// * `block_id`: Optional block number to discover the slots at
+    #[inline]
+    pub async fn build<N, P>(
+        self,
+        provider: &P,
+        block_id: Option<BlockId>,
+    ) -> Result<StateOverride, Error>
+    where
+        N: Network,
+        P: Provider<N>,
+    {
+        let mut overrides = StateOverride::default();
+        if let Some(amount) = self.native_balance {
+            merge_state_overrides(
+                &mut overrides,
+                get_native_balance_override(self.owner, amount),
+            );
+        }
+        for (token, spender, amount) in self.erc20 {
+            let erc20 =
+                get_erc20_state_overrides(token, self.owner, spender, amount, provider, block_id)
+                    .await?;
+            merge_state_overrides(&mut overrides, erc20);
+        }
+        for (token, spender, amount) in self.permit2 {
+            let permit2 =
+                get_permit2_state_overrides(token, self.owner, spender, amount, provider, block_id)
+                    .await?;
+            merge_state_overrides(&mut overrides, permit2);
+        }
+        Ok(overrides)
+    }
 }
 
 #[cfg(test)]
@@ -95,15 +402,61 @@ mod tests {
     use alloy_primitives::{address, U256};
     use uniswap_sdk_core::prelude::{BaseCurrency, NONFUNGIBLE_POSITION_MANAGER_ADDRESSES};
 
+    #[test]
+    fn test_proxy_slots() {
+        let eip1967 =
+            |name: &str| B256::from(U256::from_be_bytes(keccak256(name).0) - U256::from(1));
+        assert_eq!(PROXY_SLOTS[0], eip1967("eip1967.proxy.implementation"));
+        assert_eq!(PROXY_SLOTS[1], eip1967("eip1967.proxy.beacon"));
+        assert_eq!(PROXY_SLOTS[2], eip1967("eip1967.proxy.admin"));
+        assert_eq!(
+            PROXY_SLOTS[3],
+            keccak256("org.zeppelinos.proxy.implementation")
+        );
+    }
+
+    #[test]
+    fn test_mapping_slot_candidates() {
+        let owner = address!("0000000000000000000000000000000000000001");
+        let spender = address!("0000000000000000000000000000000000000002");
+        let candidates = mapping_slot_candidates(&[owner, spender]);
+        assert_eq!(candidates.len(), 2 * (MAX_MAPPING_INDEX as usize + 1));
+        let index = U256::from(3);
+        assert!(candidates.contains(&keccak256(
+            (spender, keccak256((owner, index).abi_encode())).abi_encode()
+        )));
+        assert!(candidates.contains(&keccak256(
+            (keccak256((index, owner).abi_encode()), spender).abi_encode()
+        )));
+    }
+
+    #[test]
+    fn test_merge_state_overrides() {
+        let owner = address!("0000000000000000000000000000000000000001");
+        let mut overrides = get_native_balance_override(owner, U256::from(1));
+        merge_state_overrides(
+            &mut overrides,
+            slot_override(owner, B256::ZERO, U256::from(2)),
+        );
+        merge_state_overrides(
+            &mut overrides,
+            slot_override(owner, B256::with_last_byte(1), U256::from(3)),
+        );
+        let account = &overrides[&owner];
+        assert_eq!(account.balance, Some(U256::from(1)));
+        assert_eq!(account.state_diff.as_ref().unwrap().len(), 2);
+    }
+
     #[tokio::test]
     async fn test_get_erc20_overrides() {
         let provider = PROVIDER.clone();
         let owner = address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640");
         let npm = *NONFUNGIBLE_POSITION_MANAGER_ADDRESSES.get(&1).unwrap();
         let amount = U256::from(1_000_000);
-        let overrides = get_erc20_state_overrides(USDC.address(), owner, npm, amount, &provider)
-            .await
-            .unwrap();
+        let overrides =
+            get_erc20_state_overrides(USDC.address(), owner, npm, amount, &provider, None)
+                .await
+                .unwrap();
         let usdc = IERC20::new(USDC.address(), provider);
         let balance = usdc
             .balanceOf(owner)
@@ -122,4 +475,67 @@ mod tests {
             ._0;
         assert_eq!(allowance, amount);
     }
+
+    #[tokio::test]
+    async fn test_get_erc20_overrides_external_storage() {
+        let provider = PROVIDER.clone();
+        // SNX is a proxy whose balances and allowances are held by a separate token state contract
+        let snx = address!("C011a73ee8576Fb46F5E1c5751cA3B9Fe0af2a6F");
+        let owner = address!("0000000000000000000000000000000000000001");
+        let spender = address!("0000000000000000000000000000000000000002");
+        let amount = U256::from(1_000_000);
+        let overrides = get_erc20_state_overrides(snx, owner, spender, amount, &provider, None)
+            .await
+            .unwrap();
+        assert!(overrides.keys().any(|address| *address != snx));
+        let token = IERC20::new(snx, provider);
+        let balance = token
+            .balanceOf(owner)
+            .call()
+            .overrides(overrides.clone())
+            .await
+            .unwrap()
+            ._0;
+        assert_eq!(balance, amount);
+        let allowance = token
+            .allowance(owner, spender)
+            .call()
+            .overrides(overrides)
+            .await
+            .unwrap()
+            ._0;
+        assert_eq!(allowance, amount);
+    }
+
+    #[tokio::test]
+    async fn test_state_overrides_builder() {
+        let provider = PROVIDER.clone();
+        let owner = address!("0000000000000000000000000000000000000001");
+        let spender = address!("0000000000000000000000000000000000000002");
+        let amount = U256::from(1_000_000);
+        let overrides = StateOverridesBuilder::new(owner)
+            .with_native_balance(amount)
+            .with_erc20(DAI.address(), spender, amount)
+            .with_permit2(USDC.address(), spender, amount)
+            .build(&provider, None)
+            .await
+            .unwrap();
+        assert_eq!(overrides[&owner].balance, Some(amount));
+        let dai = IERC20::new(DAI.address(), provider.clone());
+        let allowance = dai
+            .allowance(owner, spender)
+            .call()
+            .overrides(overrides.clone())
+            .await
+            .unwrap()
+            ._0;
+        assert_eq!(allowance, amount);
+        let permit2 = IPermit2::new(PERMIT2_ADDRESS, provider)
+            .allowance(owner, USDC.address(), spender)
+            .call()
+            .overrides(overrides)
+            .await
+            .unwrap();
+        assert_eq!(U256::from(permit2.amount), amount);
+    }
 }
@@ -0,0 +1,573 @@
+//! ## Transaction
+//! This module provides functions to send [`MethodParameters`] to the periphery contract they are
+//! encoded for as an alloy transaction request, estimate its gas and simulate it with `eth_call`,
+//! decoding the return data into typed results and surfacing revert reasons.
+
+use crate::prelude::{Error, *};
+use alloc::{string::String, vec::Vec};
+use alloy::{
+    contract::Error as ContractError,
+    eips::{BlockId, BlockNumberOrTag},
+    network::{Network, TransactionBuilder},
+    providers::Provider,
+    rpc::{json_rpc::ErrorPayload, types::state::StateOverride},
+    transports::TransportError,
+};
+use alloy_primitives::{Address, Bytes, ChainId, U256};
+use alloy_sol_types::{decode_revert_reason, SolCall, SolInterface};
+
+/// The decoded return value of a call to a periphery contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CallResult {
+    /// An exact input swap
+    ExactInput { amount_out: U256 },
+    /// An exact output swap
+    ExactOutput { amount_in: U256 },
+    /// A position minted by the position manager
+    Mint {
+        token_id: U256,
+        liquidity: u128,
+        amount0: U256,
+        amount1: U256,
+    },
+    /// Liquidity added to a position
+    IncreaseLiquidity {
+        liquidity: u128,
+        amount0: U256,
+        amount1: U256,
+    },
+    /// Liquidity removed from a position
+    DecreaseLiquidity { amount0: U256, amount1: U256 },
+    /// Tokens collected from a position
+    Collect { amount0: U256, amount1: U256 },
+}
+
+/// The outcome of a simulated transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Simulation {
+    /// The estimated gas of the transaction
+    pub gas: u64,
+    /// The decoded results of the calls with a known return value, in call order
+    pub results: Vec<CallResult>,
+    /// The raw return data of the transaction
+    pub return_data: Bytes,
+}
+
+impl Simulation {
+    /// Returns the total output of the exact input swaps.
+    #[inline]
+    #[must_use]
+    pub fn amount_out(&self) -> U256 {
+        self.results
+            .iter()
+            .map(|result| match result {
+                CallResult::ExactInput { amount_out } => *amount_out,
+                _ => U256::ZERO,
+            })
+            .sum()
+    }
+
+    /// Returns the total input of the exact output swaps.
+    #[inline]
+    #[must_use]
+    pub fn amount_in(&self) -> U256 {
+        self.results
+            .iter()
+            .map(|result| match result {
+                CallResult::ExactOutput { amount_in } => *amount_in,
+                _ => U256::ZERO,
+            })
+            .sum()
+    }
+
+    /// Returns the token id of the minted position, if any.
+    #[inline]
+    #[must_use]
+    pub fn token_id(&self) -> Option<U256> {
+        self.results.iter().find_map(|result| match result {
+            CallResult::Mint { token_id, .. } => Some(*token_id),
+            _ => None,
+        })
+    }
+
+    /// Returns the liquidity added by a mint or an increase of liquidity, if any.
+    #[inline]
+    #[must_use]
+    pub fn liquidity(&self) -> Option<u128> {
+        self.results.iter().find_map(|result| match result {
+            CallResult::Mint { liquidity, .. }
+            | CallResult::IncreaseLiquidity { liquidity, .. } => Some(*liquidity),
+            _ => None,
+        })
+    }
+}
+
+impl MethodParameters {
+    /// Builds a transaction request sending the calldata and value from `from` to `to`.
+    ///
+    /// ## Arguments
+    ///
+    /// * `from`: The sender of the transaction
+    /// * `to`: The periphery contract to call, see [`get_call_target`]
+    #[inline]
+    #[must_use]
+    pub fn to_transaction_request<N: Network>(
+        &self,
+        from: Address,
+        to: Address,
+    ) -> N::TransactionRequest {
+        N::TransactionRequest::default()
+            .with_from(from)
+            .with_to(to)
+            .with_input(self.calldata.clone())
+            .with_value(self.value)
+    }
+
+    /// Returns the periphery contract of the [`Deployment`] on `chain_id` that the calldata is
+    /// encoded for, see [`get_call_target`].
+    ///
+    /// ## Arguments
+    ///
+    /// * `chain_id`: The chain id
+    #[inline]
+    pub fn call_target(&self, chain_id: ChainId) -> Result<Address, Error> {
+        get_call_target(&self.calldata, chain_id)
+            .map_err(ContractError::from)?
+            .ok_or(Error::UnknownDeployment(chain_id))
+    }
+}
+
+/// Returns the calls of a calldata, unwrapping a multicall.
+fn split_multicall(calldata: &[u8]) -> Result<Vec<Bytes>, alloy_sol_types::Error> {
+    if calldata.starts_with(&IMulticall::multicallCall::SELECTOR) {
+        decode_multicall(calldata)
+    } else {
+        Ok(vec![Bytes::copy_from_slice(calldata)])
+    }
+}
+
+/// Returns the periphery contract of the deployment on `chain_id` that the calldata is encoded
+/// for, either the `NonfungiblePositionManager` or `SwapRouter02`. Calls shared by both contracts,
+/// such as `selfPermit` or `refundETH`, and calls to the original `SwapRouter` are not attributed.
+///
+/// ## Arguments
+///
+/// * `calldata`: The calldata of [`MethodParameters`]
+/// * `chain_id`: The chain id
+#[inline]
+pub fn get_call_target(
+    calldata: &[u8],
+    chain_id: ChainId,
+) -> Result<Option<Address>, alloy_sol_types::Error> {
+    let Some(deployment) = get_deployment(chain_id) else {
+        return Ok(None);
+    };
+    for call in split_multicall(calldata)? {
+        let Some(selector) = call.get(..4).map(|selector| selector.try_into().unwrap()) else {
+            continue;
+        };
+        if INonfungiblePositionManager::INonfungiblePositionManagerCalls::valid_selector(selector)
+            || IERC721Permit::IERC721PermitCalls::valid_selector(selector)
+        {
+            return Ok(Some(deployment.nonfungible_position_manager));
+        }
+        if IV3SwapRouter::IV3SwapRouterCalls::valid_selector(selector)
+            || IApproveAndCall::IApproveAndCallCalls::valid_selector(selector)
+            || IPeripheryPaymentsExtended::IPeripheryPaymentsExtendedCalls::valid_selector(selector)
+        {
+            return Ok(deployment.swap_router02);
+        }
+    }
+    Ok(None)
+}
+
+/// Decodes the return value of a single call into `results`, skipping calls without a known return
+/// value.
+fn decode_call_result(
+    call: &[u8],
+    return_data: &[u8],
+    results: &mut Vec<CallResult>,
+) -> Result<(), alloy_sol_types::Error> {
+    let Some(selector) = call.get(..4) else {
+        return Ok(());
+    };
+    let selector: [u8; 4] = selector.try_into().unwrap();
+    let result = match selector {
+        IV3SwapRouter::exactInputSingleCall::SELECTOR
+        | IV3SwapRouter::exactInputCall::SELECTOR
+        | ISwapRouter::exactInputSingleCall::SELECTOR
+        | ISwapRouter::exactInputCall::SELECTOR => CallResult::ExactInput {
+            amount_out: IV3SwapRouter::exactInputCall::abi_decode_returns(return_data, true)?
+                .amountOut,
+        },
+        IV3SwapRouter::exactOutputSingleCall::SELECTOR
+        | IV3SwapRouter::exactOutputCall::SELECTOR
+        | ISwapRouter::exactOutputSingleCall::SELECTOR
+        | ISwapRouter::exactOutputCall::SELECTOR => CallResult::ExactOutput {
+            amount_in: IV3SwapRouter::exactOutputCall::abi_decode_returns(return_data, true)?
+                .amountIn,
+        },
+        INonfungiblePositionManager::mintCall::SELECTOR | IApproveAndCall::mintCall::SELECTOR => {
+            let return_data = if selector == IApproveAndCall::mintCall::SELECTOR {
+                IApproveAndCall::mintCall::abi_decode_returns(return_data, true)?.result
+            } else {
+                Bytes::copy_from_slice(return_data)
+            };
+            let mint =
+                INonfungiblePositionManager::mintCall::abi_decode_returns(&return_data, true)?;
+            CallResult::Mint {
+                token_id: mint.tokenId,
+                liquidity: mint.liquidity,
+                amount0: mint.amount0,
+                amount1: mint.amount1,
+            }
+        }
+        INonfungiblePositionManager::increaseLiquidityCall::SELECTOR
+        | IApproveAndCall::increaseLiquidityCall::SELECTOR => {
+            let return_data = if selector == IApproveAndCall::increaseLiquidityCall::SELECTOR {
+                IApproveAndCall::increaseLiquidityCall::abi_decode_returns(return_data, true)?
+                    .result
+            } else {
+                Bytes::copy_from_slice(return_data)
+            };
+            let increase = INonfungiblePositionManager::increaseLiquidityCall::abi_decode_returns(
+                &return_data,
+                true,
+            )?;
+            CallResult::IncreaseLiquidity {
+                liquidity: increase.liquidity,
+                amount0: increase.amount0,
+                amount1: increase.amount1,
+            }
+        }
+        INonfungiblePositionManager::decreaseLiquidityCall::SELECTOR => {
+            let decrease = INonfungiblePositionManager::decreaseLiquidityCall::abi_decode_returns(
+                return_data,
+                true,
+            )?;
+            CallResult::DecreaseLiquidity {
+                amount0: decrease.amount0,
+                amount1: decrease.amount1,
+            }
+        }
+        INonfungiblePositionManager::collectCall::SELECTOR => {
+            let collect =
+                INonfungiblePositionManager::collectCall::abi_decode_returns(return_data, true)?;
+            CallResult::Collect {
+                amount0: collect.amount0,
+                amount1: collect.amount1,
+            }
+        }
+        IApproveAndCall::callPositionManagerCall::SELECTOR => {
+            let data = IApproveAndCall::callPositionManagerCall::abi_decode(call, true)?.data;
+            let result =
+                IApproveAndCall::callPositionManagerCall::abi_decode_returns(return_data, true)?
+                    .result;
+            results.extend(decode_call_results(&data, &result)?);
+            return Ok(());
+        }
+        _ => return Ok(()),
+    };
+    results.push(result);
+    Ok(())
+}
+
+/// Decodes the return data of a transaction sent with the calldata of [`MethodParameters`] into
+/// the typed results of its calls, unwrapping a multicall and calls forwarded to the position
+/// manager by `SwapRouter02`.
+///
+/// ## Arguments
+///
+/// * `calldata`: The calldata of the transaction
+/// * `return_data`: The return data of the transaction
+#[inline]
+pub fn decode_call_results(
+    calldata: &[u8],
+    return_data: &[u8],
+) -> Result<Vec<CallResult>, alloy_sol_types::Error> {
+    let mut results = Vec::new();
+    if calldata.starts_with(&IMulticall::multicallCall::SELECTOR) {
+        let calls = decode_multicall::<Bytes, _>(calldata)?;
+        let returns = IMulticall::multicallCall::abi_decode_returns(return_data, true)?.results;
+        for (call, return_data) in calls.iter().zip(returns.iter()) {
+            decode_call_result(call, return_data, &mut results)?;
+        }
+    } else {
+        decode_call_result(calldata, return_data, &mut results)?;
+    }
+    Ok(results)
+}
+
+/// Returns whether a failed `eth_call` reverted, as opposed to failing in the transport or node.
+pub(crate) fn is_revert(error: &TransportError) -> bool {
+    error.as_error_resp().is_some_and(|payload| {
+        payload.message.contains("revert") || payload.as_revert_data().is_some()
+    })
+}
+
+/// Converts a failed `eth_call` into [`Error::Revert`] if the node returned revert data.
+fn revert_error(error: TransportError) -> Error {
+    match error.as_error_resp().and_then(ErrorPayload::as_revert_data) {
+        Some(data) => Error::Revert {
+            reason: decode_revert_reason(&data).unwrap_or_else(|| String::from("unknown reason")),
+            data,
+        },
+        None => error.into(),
+    }
+}
+
+/// Estimates the gas of the transaction sending [`MethodParameters`] to `to` and simulates it with
+/// `eth_call`, decoding its return data.
+///
+/// ## Arguments
+///
+/// * `parameters`: The calldata and value of the transaction
+/// * `from`: The sender of the transaction
+/// * `to`: The periphery contract to call, or `None` for the one of the [`Deployment`] on
+///   the chain of the provider that the calldata is encoded for, see [`get_call_target`]
+/// * `overrides`: Optional state overrides applied during the simulation
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to simulate at
+#[inline]
+pub async fn simulate_call<N, P>(
+    parameters: &MethodParameters,
+    from: Address,
+    to: impl Into<Option<Address>>,
+    overrides: Option<StateOverride>,
+    provider: &P,
+    block_id: Option<BlockId>,
+) -> Result<Simulation, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let block_id = block_id.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
+    let to = match to.into() {
+        Some(to) => to,
+        None => parameters.call_target(provider.get_chain_id().await?)?,
+    };
+    let tx = parameters.to_transaction_request::<N>(from, to);
+    let mut call = provider.call(tx.clone()).block(block_id);
+    let mut estimate = provider.estimate_gas(tx).block(block_id);
+    if let Some(overrides) = overrides {
+        call = call.overrides(overrides.clone());
+        estimate = estimate.overrides(overrides);
+    }
+    let return_data = call.await.map_err(revert_error)?;
+    let gas = estimate.await.map_err(revert_error)?;
+    Ok(Simulation {
+        gas,
+        results: decode_call_results(&parameters.calldata, &return_data)
+            .map_err(ContractError::from)?,
+        return_data,
+    })
+}
+
+/// Simulates the transaction sending [`MethodParameters`] to `to` as if `from` held and had
+/// approved `to` to spend the given amounts of tokens, and held the native value of the
+/// transaction, using a [`StateOverridesBuilder`].
+///
+/// ## Arguments
+///
+/// * `parameters`: The calldata and value of the transaction
+/// * `from`: The sender of the transaction
+/// * `to`: The periphery contract to call, or `None` for the one of the [`Deployment`] on
+///   the chain of the provider that the calldata is encoded for, see [`get_call_target`]
+/// * `approvals`: The tokens and amounts to give to `from` and approve to `to`
+/// * `provider`: The alloy provider
+/// * `block_id`: Optional block number to simulate at
+#[inline]
+pub async fn simulate_call_with_approvals<N, P>(
+    parameters: &MethodParameters,
+    from: Address,
+    to: impl Into<Option<Address>>,
+    approvals: &[(Address, U256)],
+    provider: &P,
+    block_id: Option<BlockId>,
+) -> Result<Simulation, Error>
+where
+    N: Network,
+    P: Provider<N>,
+{
+    let to = match to.into() {
+        Some(to) => to,
+        None => parameters.call_target(provider.get_chain_id().await?)?,
+    };
+    let mut builder = StateOverridesBuilder::new(from);
+    if parameters.value > U256::ZERO {
+        builder = builder.with_native_balance(parameters.value);
+    }
+    for &(token, amount) in approvals {
+        builder = builder.with_erc20(token, to, amount);
+    }
+    let overrides = builder.build(provider, block_id).await?;
+    simulate_call(parameters, from, to, Some(overrides), provider, block_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy::{providers::Provider, rpc::json_rpc::RpcError};
+    use alloy_primitives::{address, aliases::U24, uint};
+    use alloy_sol_types::{Revert, SolError};
+    use uniswap_sdk_core::prelude::{BaseCurrency, NONFUNGIBLE_POSITION_MANAGER_ADDRESSES};
+
+    const SENDER: Address = address!("0000000000000000000000000000000000000001");
+
+    fn exact_input_single(amount_out_minimum: U256) -> MethodParameters {
+        MethodParameters {
+            calldata: IV3SwapRouter::exactInputSingleCall {
+                params: IV3SwapRouter::ExactInputSingleParams {
+                    tokenIn: USDC.address(),
+                    tokenOut: WETH.address(),
+                    fee: U24::from(500),
+                    recipient: SENDER,
+                    amountIn: uint!(1_000_000_000_U256),
+                    amountOutMinimum: amount_out_minimum,
+                    sqrtPriceLimitX96: Default::default(),
+                },
+            }
+            .abi_encode()
+            .into(),
+            value: U256::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_get_call_target() {
+        let deployment = get_deployment(1).unwrap();
+        assert_eq!(
+            get_call_target(&exact_input_single(U256::ZERO).calldata, 1).unwrap(),
+            deployment.swap_router02
+        );
+        let collect = INonfungiblePositionManager::collectCall {
+            params: Default::default(),
+        }
+        .abi_encode();
+        let refund = IPeripheryPaymentsWithFee::refundETHCall {}.abi_encode();
+        assert_eq!(
+            get_call_target(&encode_multicall(vec![refund.clone(), collect]), 1).unwrap(),
+            Some(deployment.nonfungible_position_manager)
+        );
+        assert_eq!(get_call_target(&refund, 1).unwrap(), None);
+        assert_eq!(get_call_target(&refund, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_call_target() {
+        let parameters = exact_input_single(U256::ZERO);
+        assert_eq!(
+            parameters.call_target(1).unwrap(),
+            get_deployment(1).unwrap().swap_router02.unwrap()
+        );
+        assert!(matches!(
+            parameters.call_target(0),
+            Err(Error::UnknownDeployment(0))
+        ));
+    }
+
+    #[test]
+    fn test_to_transaction_request() {
+        let parameters = MethodParameters {
+            calldata: Bytes::from_static(&[1, 2, 3]),
+            value: uint!(5_U256),
+        };
+        let npm = *NONFUNGIBLE_POSITION_MANAGER_ADDRESSES.get(&1).unwrap();
+        let tx = parameters.to_transaction_request::<alloy::network::Ethereum>(SENDER, npm);
+        assert_eq!(tx.from, Some(SENDER));
+        assert_eq!(tx.to, Some(npm.into()));
+        assert_eq!(tx.input.input(), Some(&parameters.calldata));
+        assert_eq!(tx.value, Some(uint!(5_U256)));
+    }
+
+    #[test]
+    fn test_decode_call_results() {
+        let amount_out = uint!(123_U256);
+        let return_data = IV3SwapRouter::exactInputSingleCall::abi_encode_returns(&(amount_out,));
+        assert_eq!(
+            decode_call_results(&exact_input_single(U256::ZERO).calldata, &return_data).unwrap(),
+            vec![CallResult::ExactInput { amount_out }]
+        );
+
+        let mint = INonfungiblePositionManager::mintCall::abi_encode_returns(&(
+            uint!(7_U256),
+            100_u128,
+            uint!(1_U256),
+            uint!(2_U256),
+        ));
+        let calldata = encode_multicall(vec![
+            IApproveAndCall::approveMaxCall {
+                token: USDC.address(),
+            }
+            .abi_encode(),
+            IApproveAndCall::mintCall {
+                params: Default::default(),
+            }
+            .abi_encode(),
+        ]);
+        let return_data = IMulticall::multicallCall::abi_encode_returns(&(vec![
+            Bytes::new(),
+            IApproveAndCall::mintCall::abi_encode_returns(&(Bytes::from(mint),)).into(),
+        ],));
+        let results = decode_call_results(&calldata, &return_data).unwrap();
+        let simulation = Simulation {
+            gas: 0,
+            results,
+            return_data: return_data.into(),
+        };
+        assert_eq!(simulation.token_id(), Some(uint!(7_U256)));
+        assert_eq!(simulation.liquidity(), Some(100));
+        assert_eq!(simulation.amount_out(), U256::ZERO);
+        assert!(decode_call_results(&calldata, &[]).is_err());
+    }
+
+    #[test]
+    fn test_revert_error() {
+        let data = Bytes::from(Revert::from("Too little received").abi_encode()).to_string();
+        let error: TransportError = RpcError::ErrorResp(ErrorPayload {
+            code: 3,
+            message: "execution reverted".into(),
+            data: Some(serde_json::value::to_raw_value(&data).unwrap()),
+        });
+        assert!(is_revert(&error));
+        let Error::Revert { reason, .. } = revert_error(error) else {
+            panic!("expected a revert");
+        };
+        assert_eq!(reason, "revert: Too little received");
+    }
+
+    #[tokio::test]
+    async fn test_simulate_call_with_approvals() {
+        let provider = PROVIDER.clone();
+        let chain_id = provider.get_chain_id().await.unwrap();
+        let parameters = exact_input_single(U256::ZERO);
+        let router = get_call_target(&parameters.calldata, chain_id)
+            .unwrap()
+            .unwrap();
+        let approvals = [(USDC.address(), uint!(1_000_000_000_U256))];
+        let simulation = simulate_call_with_approvals(
+            &parameters,
+            SENDER,
+            router,
+            &approvals,
+            &provider,
+            *BLOCK_ID,
+        )
+        .await
+        .unwrap();
+        assert!(simulation.gas > 0);
+        assert!(simulation.amount_out() > U256::ZERO);
+
+        let result = simulate_call_with_approvals(
+            &exact_input_single(U256::MAX),
+            SENDER,
+            router,
+            &approvals,
+            &provider,
+            *BLOCK_ID,
+        )
+        .await;
+        assert!(matches!(result, Err(Error::Revert { .. })));
+    }
+}
@@ -0,0 +1,392 @@
+//! ## EIP-712 typed data
+//! Serializes [`NFTPermitData`], [`ERC20PermitData`] and any other [`SolStruct`] with its domain
+//! into the `eth_signTypedData_v4` JSON payload accepted by browser and hardware wallets, and
+//! parses signed payloads back into [`PermitOptions`] and [`NFTPermitOptions`].
+
+use crate::prelude::{Error, *};
+use alloc::string::{String, ToString};
+use alloy_dyn_abi::TypedData;
+use alloy_primitives::{Address, PrimitiveSignature, U256};
+use alloy_sol_types::{Eip712Domain, SolStruct};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+/// Returns the EIP-712 typed data of `values` to be signed under `domain`.
+///
+/// ## Arguments
+///
+/// * `values`: The struct to sign
+/// * `domain`: The EIP-712 domain of the verifying contract
+#[inline]
+#[must_use]
+pub fn get_typed_data<S: SolStruct + Serialize>(values: &S, domain: &Eip712Domain) -> TypedData {
+    TypedData::from_struct(values, Some(domain.clone()))
+}
+
+/// Serializes typed data into the JSON payload of `eth_signTypedData_v4`.
+///
+/// Unsigned integers are serialized as decimal strings, except for the chain id of the domain,
+/// which is serialized as a number.
+///
+/// ## Arguments
+///
+/// * `typed_data`: The typed data to sign
+#[inline]
+pub fn typed_data_to_json(typed_data: &TypedData) -> Result<String, Error> {
+    let mut json = serde_json::to_value(typed_data)?;
+    if let Value::Object(object) = &mut json {
+        let types = match object.get("types") {
+            Some(Value::Object(types)) => types.clone(),
+            _ => Map::new(),
+        };
+        if let Some(domain) = object.get_mut("domain") {
+            normalize_integers(&types, "EIP712Domain", domain);
+            if let Some(chain_id) = domain
+                .get("chainId")
+                .and_then(Value::as_str)
+                .and_then(|chain_id| chain_id.parse::<u64>().ok())
+            {
+                domain["chainId"] = chain_id.into();
+            }
+        }
+        if let Some(message) = object.get_mut("message") {
+            normalize_integers(&types, &typed_data.primary_type, message);
+        }
+    }
+    Ok(json.to_string())
+}
+
+/// Rewrites the unsigned integers of a value of type `ty` as decimal strings.
+fn normalize_integers(types: &Map<String, Value>, ty: &str, value: &mut Value) {
+    if let Some((element, _)) = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('[')) {
+        if let Value::Array(values) = value {
+            for value in values {
+                normalize_integers(types, element, value);
+            }
+        }
+    } else if ty.starts_with("uint") {
+        if let Some(integer) = value.as_str().and_then(|s| s.parse::<U256>().ok()) {
+            *value = integer.to_string().into();
+        }
+    } else if let Some(Value::Array(fields)) = types.get(ty) {
+        for field in fields {
+            if let (Some(name), Some(field_type)) = (
+                field.get("name").and_then(Value::as_str),
+                field.get("type").and_then(Value::as_str),
+            ) {
+                if let Some(value) = value.get_mut(name) {
+                    normalize_integers(types, field_type, value);
+                }
+            }
+        }
+    }
+}
+
+/// Parses the JSON payload of `eth_signTypedData_v4`.
+///
+/// ## Arguments
+///
+/// * `json`: The typed data as JSON
+#[inline]
+pub fn parse_typed_data(json: &str) -> Result<TypedData, Error> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Returns the message of typed data as `S` if its primary type is exactly the type of `S`.
+fn typed_data_values<S: SolStruct + DeserializeOwned>(typed_data: &TypedData) -> Result<S, Error> {
+    match typed_data.encode_type() {
+        Ok(encode_type) if encode_type == S::eip712_encode_type() => {
+            Ok(serde_json::from_value(typed_data.message.clone())?)
+        }
+        _ => Err(Error::InvalidTypedData),
+    }
+}
+
+/// Recovers the signer of typed data from a signature.
+///
+/// ## Arguments
+///
+/// * `typed_data`: The signed typed data
+/// * `signature`: The signature returned by the wallet, as 65 hex encoded bytes
+#[inline]
+pub fn recover_typed_data_signer(
+    typed_data: &TypedData,
+    signature: &str,
+) -> Result<Address, Error> {
+    let signature: PrimitiveSignature = signature.parse()?;
+    let hash = typed_data
+        .eip712_signing_hash()
+        .map_err(|_| Error::InvalidTypedData)?;
+    Ok(signature.recover_address_from_prehash(&hash)?)
+}
+
+/// Parses a signed EIP-2612 or DAI permit into the [`PermitOptions`] of a `selfPermit` or
+/// `selfPermitAllowed` call, verifying that the owner of the tokens signed it.
+///
+/// ## Arguments
+///
+/// * `typed_data`: The typed data of [`ERC20PermitData`] as JSON
+/// * `signature`: The signature returned by the wallet, as 65 hex encoded bytes
+#[inline]
+pub fn parse_permit_options(typed_data: &str, signature: &str) -> Result<PermitOptions, Error> {
+    let typed_data = parse_typed_data(typed_data)?;
+    let signer = recover_typed_data_signer(&typed_data, signature)?;
+    let signature: PrimitiveSignature = signature.parse()?;
+    if let Ok(permit) = typed_data_values::<IERC20Permit::Permit>(&typed_data) {
+        if permit.owner != signer {
+            return Err(Error::InvalidSignature);
+        }
+        Ok(PermitOptions::Standard(StandardPermitArguments {
+            signature,
+            amount: permit.value,
+            deadline: permit.deadline,
+        }))
+    } else {
+        let permit = typed_data_values::<IDaiPermit::Permit>(&typed_data)?;
+        if permit.holder != signer {
+            return Err(Error::InvalidSignature);
+        }
+        if !permit.allowed {
+            return Err(Error::InvalidTypedData);
+        }
+        Ok(PermitOptions::Allowed(AllowedPermitArguments {
+            signature,
+            nonce: permit.nonce,
+            expiry: permit.expiry,
+        }))
+    }
+}
+
+/// Parses a signed position NFT permit into [`NFTPermitOptions`].
+///
+/// The signer must own or be approved for the position, which is checked by the position manager
+/// and can be checked ahead with [`recover_typed_data_signer`].
+///
+/// ## Arguments
+///
+/// * `typed_data`: The typed data of [`NFTPermitData`] as JSON
+/// * `signature`: The signature returned by the wallet, as 65 hex encoded bytes
+#[inline]
+pub fn parse_nft_permit_options(
+    typed_data: &str,
+    signature: &str,
+) -> Result<NFTPermitOptions, Error> {
+    let permit = typed_data_values::<NFTPermitValues>(&parse_typed_data(typed_data)?)?;
+    Ok(NFTPermitOptions {
+        signature: signature.parse()?,
+        deadline: permit.deadline,
+        spender: permit.spender,
+    })
+}
+
+impl NFTPermitData {
+    /// Returns the EIP-712 typed data of the permit.
+    #[inline]
+    #[must_use]
+    pub fn typed_data(&self) -> TypedData {
+        get_typed_data(&self.values, &self.domain)
+    }
+
+    /// Serializes the permit into the JSON payload of `eth_signTypedData_v4`.
+    #[inline]
+    pub fn to_typed_data_json(&self) -> Result<String, Error> {
+        typed_data_to_json(&self.typed_data())
+    }
+}
+
+impl<P: SolStruct + Serialize> ERC20PermitData<P> {
+    /// Returns the EIP-712 typed data of the permit.
+    #[inline]
+    #[must_use]
+    pub fn typed_data(&self) -> TypedData {
+        get_typed_data(&self.values, &self.domain)
+    }
+
+    /// Serializes the permit into the JSON payload of `eth_signTypedData_v4`.
+    #[inline]
+    pub fn to_typed_data_json(&self) -> Result<String, Error> {
+        typed_data_to_json(&self.typed_data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+    use alloy_primitives::{address, uint};
+
+    const POSITION_MANAGER: Address = address!("C36442b4a4522E871399CD717aBDD847Ab11FE88");
+    const TOKEN: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const SPENDER: Address = address!("0000000000000000000000000000000000000002");
+
+    fn nft_permit_data() -> NFTPermitData {
+        get_permit_data(
+            NFTPermitValues {
+                spender: SPENDER,
+                tokenId: uint!(1_U256),
+                nonce: uint!(0_U256),
+                deadline: uint!(123_U256),
+            },
+            POSITION_MANAGER,
+            1,
+        )
+    }
+
+    fn sign(signer: &PrivateKeySigner, json: &str) -> String {
+        let hash = parse_typed_data(json)
+            .unwrap()
+            .eip712_signing_hash()
+            .unwrap();
+        signer.sign_hash_sync(&hash).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_nft_permit_json() {
+        let data = nft_permit_data();
+        let json = data.to_typed_data_json().unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["primaryType"], "Permit");
+        assert_eq!(
+            value["types"]["Permit"],
+            serde_json::json!([
+                { "name": "spender", "type": "address" },
+                { "name": "tokenId", "type": "uint256" },
+                { "name": "nonce", "type": "uint256" },
+                { "name": "deadline", "type": "uint256" },
+            ])
+        );
+        assert_eq!(
+            value["types"]["EIP712Domain"],
+            serde_json::json!([
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" },
+            ])
+        );
+        assert_eq!(
+            value["domain"],
+            serde_json::json!({
+                "name": "Uniswap V3 Positions NFT-V1",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xc36442b4a4522e871399cd717abdd847ab11fe88",
+            })
+        );
+        assert_eq!(
+            value["message"],
+            serde_json::json!({
+                "spender": "0x0000000000000000000000000000000000000002",
+                "tokenId": "1",
+                "nonce": "0",
+                "deadline": "123",
+            })
+        );
+        assert_eq!(
+            parse_typed_data(&json)
+                .unwrap()
+                .eip712_signing_hash()
+                .unwrap(),
+            data.eip712_signing_hash()
+        );
+    }
+
+    #[test]
+    fn test_parse_nft_permit_options() {
+        let signer = PrivateKeySigner::random();
+        let json = nft_permit_data().to_typed_data_json().unwrap();
+        let signature = sign(&signer, &json);
+        let options = parse_nft_permit_options(&json, &signature).unwrap();
+        assert_eq!(options.spender, SPENDER);
+        assert_eq!(options.deadline, uint!(123_U256));
+        assert_eq!(options.signature, signature.parse().unwrap());
+        assert_eq!(
+            recover_typed_data_signer(&parse_typed_data(&json).unwrap(), &signature).unwrap(),
+            signer.address()
+        );
+    }
+
+    #[test]
+    fn test_parse_standard_permit_options() {
+        let signer = PrivateKeySigner::random();
+        let data = get_erc20_permit_data(
+            IERC20Permit::Permit {
+                owner: signer.address(),
+                spender: SPENDER,
+                value: uint!(1000_U256),
+                nonce: uint!(0_U256),
+                deadline: uint!(123_U256),
+            },
+            "USD Coin",
+            "2",
+            TOKEN,
+            1,
+        );
+        let json = data.to_typed_data_json().unwrap();
+        assert_eq!(
+            parse_typed_data(&json)
+                .unwrap()
+                .eip712_signing_hash()
+                .unwrap(),
+            data.eip712_signing_hash()
+        );
+        let signature = sign(&signer, &json);
+        assert_eq!(
+            parse_permit_options(&json, &signature).unwrap(),
+            PermitOptions::Standard(StandardPermitArguments {
+                signature: signature.parse().unwrap(),
+                amount: uint!(1000_U256),
+                deadline: uint!(123_U256),
+            })
+        );
+        let other_signature = sign(&PrivateKeySigner::random(), &json);
+        assert!(matches!(
+            parse_permit_options(&json, &other_signature),
+            Err(Error::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_parse_allowed_permit_options() {
+        let signer = PrivateKeySigner::random();
+        let json = get_erc20_permit_data(
+            IDaiPermit::Permit {
+                holder: signer.address(),
+                spender: SPENDER,
+                nonce: uint!(1_U256),
+                expiry: uint!(123_U256),
+                allowed: true,
+            },
+            "Dai Stablecoin",
+            "1",
+            TOKEN,
+            1,
+        )
+        .to_typed_data_json()
+        .unwrap();
+        let signature = sign(&signer, &json);
+        assert_eq!(
+            parse_permit_options(&json, &signature).unwrap(),
+            PermitOptions::Allowed(AllowedPermitArguments {
+                signature: signature.parse().unwrap(),
+                nonce: uint!(1_U256),
+                expiry: uint!(123_U256),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_mismatched_permit() {
+        let signer = PrivateKeySigner::random();
+        let json = nft_permit_data().to_typed_data_json().unwrap();
+        let signature = sign(&signer, &json);
+        assert!(matches!(
+            parse_permit_options(&json, &signature),
+            Err(Error::InvalidTypedData)
+        ));
+        assert!(matches!(
+            parse_nft_permit_options(&json, "0x1234"),
+            Err(Error::SignatureError(_))
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+//! ## Zap
+//! This module provides a solver that swaps arbitrary token0/token1 balances into the ratio
+//! required by a target range, accounting for the price impact of the swap on the same pool.
+
+use crate::prelude::{Error, *};
+use alloc::vec;
+use alloy_primitives::{U160, U256};
+use uniswap_sdk_core::prelude::*;
+
+/// The swap and the resulting position of a zap into a range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZapResult<TP>
+where
+    TP: TickDataProvider,
+{
+    /// The swap to execute before minting, or `None` if the balances already fit the range
+    pub trade: Option<Trade<Token, Token, TP>>,
+    /// The position minted with the balances after the swap, in the pool state after the swap
+    pub position: Position<TP>,
+    /// The amount of token0 left over after minting the position
+    pub leftover0: CurrencyAmount<Token>,
+    /// The amount of token1 left over after minting the position
+    pub leftover1: CurrencyAmount<Token>,
+}
+
+/// Returns whether swapping more in the given direction brings the balances closer to the ratio
+/// required by the range at the given price.
+fn input_in_excess(
+    zero_for_one: bool,
+    sqrt_ratio_x96: U160,
+    sqrt_ratio_a_x96: U160,
+    sqrt_ratio_b_x96: U160,
+    amount0: U256,
+    amount1: U256,
+) -> bool {
+    if sqrt_ratio_x96 <= sqrt_ratio_a_x96 {
+        // only token0 is required below the range
+        !zero_for_one
+    } else if sqrt_ratio_x96 >= sqrt_ratio_b_x96 {
+        // only token1 is required above the range
+        zero_for_one
+    } else {
+        let liquidity0 =
+            max_liquidity_for_amount0_precise(sqrt_ratio_x96, sqrt_ratio_b_x96, amount0);
+        let liquidity1 = max_liquidity_for_amount1(sqrt_ratio_a_x96, sqrt_ratio_x96, amount1);
+        if zero_for_one {
+            liquidity0 > liquidity1
+        } else {
+            liquidity1 > liquidity0
+        }
+    }
+}
+
+/// The pool state after a simulated swap and the output amount
+type SimulatedSwap<TP> = (Pool<TP>, CurrencyAmount<Token>);
+
+/// Simulates swapping `amount_in` of the input token through the pool, returning the pool state
+/// after the swap and the output amount, or `None` if the pool cannot absorb the swap.
+fn simulate_swap<TP>(
+    pool: &Pool<TP>,
+    zero_for_one: bool,
+    amount_in: U256,
+) -> Result<Option<SimulatedSwap<TP>>, Error>
+where
+    TP: Clone + TickDataProvider,
+{
+    let token_in = if zero_for_one {
+        pool.token0.clone()
+    } else {
+        pool.token1.clone()
+    };
+    let mut pool_after = pool.clone();
+    match pool_after.get_output_amount_mut(
+        &CurrencyAmount::from_raw_amount(token_in, amount_in.to_big_int())?,
+        None,
+    ) {
+        Ok(amount_out) => Ok(Some((pool_after, amount_out))),
+        Err(e) if matches!(e.root_cause(), Error::InsufficientLiquidity) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Finds the swap that converts the given balances into the ratio required by the target range
+/// with minimal leftover, taking into account that the swap moves the price of the same pool.
+///
+/// Unlike [`get_rebalanced_position`], the swap is simulated with
+/// [`Pool::get_output_amount_mut`] and the position is minted at the post-swap price, so large
+/// deposits neither leave significant dust nor exceed the balances.
+///
+/// ## Arguments
+///
+/// * `pool`: The pool to swap in and to mint the position in
+/// * `amount0`: The available balance of token0
+/// * `amount1`: The available balance of token1
+/// * `tick_lower`: The lower tick of the target range
+/// * `tick_upper`: The upper tick of the target range
+#[inline]
+pub fn get_zap_position<TP>(
+    pool: Pool<TP>,
+    amount0: U256,
+    amount1: U256,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+) -> Result<ZapResult<TP>, Error>
+where
+    TP: Clone + TickDataProvider,
+{
+    let sqrt_ratio_a_x96 = get_sqrt_ratio_at_tick(tick_lower.to_i24())?;
+    let sqrt_ratio_b_x96 = get_sqrt_ratio_at_tick(tick_upper.to_i24())?;
+    let excess = |zero_for_one: bool, sqrt_ratio_x96: U160, amount0: U256, amount1: U256| {
+        input_in_excess(
+            zero_for_one,
+            sqrt_ratio_x96,
+            sqrt_ratio_a_x96,
+            sqrt_ratio_b_x96,
+            amount0,
+            amount1,
+        )
+    };
+    let sqrt_ratio_x96 = pool.sqrt_ratio_x96;
+    let zero_for_one = if !amount0.is_zero() && excess(true, sqrt_ratio_x96, amount0, amount1) {
+        true
+    } else if !amount1.is_zero() && excess(false, sqrt_ratio_x96, amount0, amount1) {
+        false
+    } else {
+        return finalize(pool, None, amount0, amount1, tick_lower, tick_upper);
+    };
+    let balance_in = if zero_for_one { amount0 } else { amount1 };
+    let balances_after = |amount_in: U256, amount_out: &CurrencyAmount<Token>| {
+        let amount_out = U256::from_big_int(amount_out.quotient());
+        if zero_for_one {
+            (amount0 - amount_in, amount1 + amount_out)
+        } else {
+            (amount0 + amount_out, amount1 - amount_in)
+        }
+    };
+    let in_excess_after = |amount_in: U256| -> Result<_, Error> {
+        Ok(match simulate_swap(&pool, zero_for_one, amount_in)? {
+            Some((pool_after, amount_out)) => {
+                let (amount0, amount1) = balances_after(amount_in, &amount_out);
+                excess(zero_for_one, pool_after.sqrt_ratio_x96, amount0, amount1)
+                    .then_some((amount_in, pool_after, amount_out))
+            }
+            None => None,
+        })
+    };
+    // Swapping more lowers the input balance and moves the price against the input token, so the
+    // input token being in excess after the swap is monotonic in the swap amount. The input token
+    // is in excess when swapping `lo` and not when swapping `hi`.
+    let mut best = in_excess_after(balance_in)?;
+    if best.is_none() {
+        let mut lo = U256::ZERO;
+        let mut hi = balance_in;
+        while hi - lo > U256::from(1) {
+            let mid = lo + ((hi - lo) >> 1);
+            match in_excess_after(mid)? {
+                Some(swap) => {
+                    lo = mid;
+                    best = Some(swap);
+                }
+                None => hi = mid,
+            }
+        }
+    }
+    let Some((amount_in, pool_after, amount_out)) = best else {
+        return finalize(pool, None, amount0, amount1, tick_lower, tick_upper);
+    };
+    let (amount0, amount1) = balances_after(amount_in, &amount_out);
+    let (token_in, token_out) = if zero_for_one {
+        (pool.token0.clone(), pool.token1.clone())
+    } else {
+        (pool.token1.clone(), pool.token0.clone())
+    };
+    let trade = Trade::create_unchecked_trade(
+        Route::new(vec![pool], token_in.clone(), token_out),
+        CurrencyAmount::from_raw_amount(token_in, amount_in.to_big_int())?,
+        amount_out,
+        TradeType::ExactInput,
+    )?;
+    finalize(
+        pool_after,
+        Some(trade),
+        amount0,
+        amount1,
+        tick_lower,
+        tick_upper,
+    )
+}
+
+/// Mints the position with the given balances and computes the leftovers.
+fn finalize<TP>(
+    pool: Pool<TP>,
+    trade: Option<Trade<Token, Token, TP>>,
+    amount0: U256,
+    amount1: U256,
+    tick_lower: TP::Index,
+    tick_upper: TP::Index,
+) -> Result<ZapResult<TP>, Error>
+where
+    TP: TickDataProvider,
+{
+    let position = Position::from_amounts(pool, tick_lower, tick_upper, amount0, amount1, true)?;
+    let MintAmounts {
+        amount0: used0,
+        amount1: used1,
+    } = position.mint_amounts()?;
+    let leftover0 = CurrencyAmount::from_raw_amount(
+        position.pool.token0.clone(),
+        amount0.saturating_sub(used0).to_big_int(),
+    )?;
+    let leftover1 = CurrencyAmount::from_raw_amount(
+        position.pool.token1.clone(),
+        amount1.saturating_sub(used1).to_big_int(),
+    )?;
+    Ok(ZapResult {
+        trade,
+        position,
+        leftover0,
+        leftover1,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::uint;
+
+    const ONE_ETHER: U256 = uint!(1_000_000_000_000_000_000_U256);
+
+    fn make_deep_pool() -> Pool<TickListDataProvider> {
+        let liquidity = 1_000_000_000_000_000_000_u128;
+        let tick_spacing = FeeAmount::MEDIUM.tick_spacing();
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::MEDIUM,
+            encode_sqrt_ratio_x96(1, 1),
+            liquidity,
+            TickListDataProvider::new(
+                vec![
+                    Tick::new(
+                        nearest_usable_tick(MIN_TICK, tick_spacing).as_i32(),
+                        liquidity,
+                        liquidity as i128,
+                    ),
+                    Tick::new(
+                        nearest_usable_tick(MAX_TICK, tick_spacing).as_i32(),
+                        liquidity,
+                        -(liquidity as i128),
+                    ),
+                ],
+                tick_spacing.as_i32(),
+            ),
+        )
+        .unwrap()
+    }
+
+    fn assert_negligible_leftover(zap: &ZapResult<TickListDataProvider>, deposit: U256) {
+        let dust = deposit / U256::from(1_000_000);
+        assert!(U256::from_big_int(zap.leftover0.quotient()) <= dust);
+        assert!(U256::from_big_int(zap.leftover1.quotient()) <= dust);
+    }
+
+    #[test]
+    fn test_already_balanced() {
+        let pool = make_deep_pool();
+        let zap = get_zap_position(pool, ONE_ETHER, ONE_ETHER, -600, 600).unwrap();
+        assert!(zap.trade.is_none());
+        assert_negligible_leftover(&zap, ONE_ETHER);
+    }
+
+    #[test]
+    fn test_large_single_sided_deposit() {
+        let pool = make_deep_pool();
+        let deposit = ONE_ETHER * U256::from(10);
+        let zap = get_zap_position(pool.clone(), deposit, U256::ZERO, -6000, 6000).unwrap();
+        let trade = zap.trade.as_ref().unwrap();
+        assert!(trade.input_currency().equals(&pool.token0));
+        let mut pool_after = pool;
+        pool_after
+            .get_output_amount_mut(&trade.input_amount().unwrap(), None)
+            .unwrap();
+        assert_eq!(zap.position.pool.sqrt_ratio_x96, pool_after.sqrt_ratio_x96);
+        assert_negligible_leftover(&zap, deposit);
+        let MintAmounts { amount0, amount1 } = zap.position.mint_amounts().unwrap();
+        let amount_in = U256::from_big_int(trade.input_amount().unwrap().quotient());
+        let amount_out = U256::from_big_int(trade.output_amount().unwrap().quotient());
+        assert!(amount0 + amount_in <= deposit);
+        assert!(amount1 <= amount_out);
+    }
+
+    #[test]
+    fn test_range_below_current_price() {
+        let pool = make_deep_pool();
+        let deposit = ONE_ETHER;
+        let zap = get_zap_position(pool.clone(), deposit, U256::ZERO, -30000, -24000).unwrap();
+        let trade = zap.trade.as_ref().unwrap();
+        // the price stays above the range after swapping everything, so all token0 is swapped
+        assert_eq!(
+            U256::from_big_int(trade.input_amount().unwrap().quotient()),
+            deposit
+        );
+        assert!(zap.leftover0.quotient().is_zero());
+        assert_negligible_leftover(&zap, deposit);
+    }
+}
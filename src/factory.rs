@@ -0,0 +1,260 @@
+use crate::prelude::{Error, *};
+use alloc::vec::Vec;
+use alloy_primitives::{aliases::U24, Address, ChainId, U256};
+use alloy_sol_types::SolCall;
+
+#[cfg(feature = "std")]
+static FEE_TIERS: std::sync::RwLock<alloc::collections::BTreeMap<(ChainId, U24), FeeAmount>> =
+    std::sync::RwLock::new(alloc::collections::BTreeMap::new());
+
+/// Returns the fee tiers of the [`Deployment`] on a chain, preferring a registered deployment over
+/// the built-in one.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+#[inline]
+#[must_use]
+pub fn default_fee_tiers(chain_id: ChainId) -> Vec<FeeAmount> {
+    get_deployment(chain_id).map_or_else(Vec::new, |deployment| deployment.fee_tiers.to_vec())
+}
+
+/// Registers a fee tier enabled on the factory of a chain, e.g. one enabled by governance after
+/// deployment or loaded from `feeAmountTickSpacing`.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `fee_amount`: The fee tier with its tick spacing
+#[cfg(feature = "std")]
+#[inline]
+pub fn register_fee_tier(chain_id: ChainId, fee_amount: FeeAmount) {
+    FEE_TIERS
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert((chain_id, fee_amount.into()), fee_amount);
+}
+
+/// Returns the fee tiers enabled on the factory of a chain, including registered fee tiers.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+#[inline]
+#[must_use]
+pub fn get_fee_tiers(chain_id: ChainId) -> Vec<FeeAmount> {
+    #[allow(unused_mut)]
+    let mut fee_tiers = default_fee_tiers(chain_id);
+    #[cfg(feature = "std")]
+    for (&(_, fee), &fee_amount) in FEE_TIERS
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .range((chain_id, U24::ZERO)..=(chain_id, U24::MAX))
+    {
+        fee_tiers.retain(|&fee_tier| Into::<U24>::into(fee_tier) != fee);
+        fee_tiers.push(fee_amount);
+    }
+    fee_tiers
+}
+
+/// Returns the fee tier registered for a fee if it has the same tick spacing on every chain it is
+/// registered on, which backs the `TryFrom` conversions of [`FeeAmount`].
+pub(crate) fn find_registered_fee_amount(fee: U24) -> Option<FeeAmount> {
+    #[cfg(feature = "std")]
+    {
+        let fee_tiers = FEE_TIERS
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let mut fee_amounts = fee_tiers
+            .iter()
+            .filter_map(|(&(_, registered_fee), &fee_amount)| {
+                (registered_fee == fee).then_some(fee_amount)
+            });
+        let fee_amount = fee_amounts.next()?;
+        fee_amounts
+            .all(|other| other.tick_spacing() == fee_amount.tick_spacing())
+            .then_some(fee_amount)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = fee;
+        None
+    }
+}
+
+/// Resolves a fee to the fee tier enabled on the factory of a chain with its tick spacing.
+///
+/// Unlike the `TryFrom` conversions of [`FeeAmount`], which only know the fee tiers of the enum
+/// and the fee tiers registered alike on every chain, this resolves the fee tiers of the chain.
+///
+/// ## Arguments
+///
+/// * `chain_id`: The chain id
+/// * `fee`: The fee in hundredths of bips
+#[inline]
+pub fn get_fee_amount(chain_id: ChainId, fee: U24) -> Result<FeeAmount, Error> {
+    get_fee_tiers(chain_id)
+        .into_iter()
+        .find(|&fee_tier| Into::<U24>::into(fee_tier) == fee)
+        .ok_or(Error::UnknownFeeTier(fee))
+}
+
+/// Produces the calldata for creating a pool on the factory.
+///
+/// ## Arguments
+///
+/// * `token_a`: One of the tokens of the pool
+/// * `token_b`: The other token of the pool
+/// * `fee`: The fee tier of the pool
+#[inline]
+#[must_use]
+pub fn create_pool_call_parameters(
+    token_a: Address,
+    token_b: Address,
+    fee: FeeAmount,
+) -> MethodParameters {
+    assert_ne!(token_a, token_b, "ADDRESSES");
+    MethodParameters {
+        calldata: IUniswapV3Factory::createPoolCall {
+            tokenA: token_a,
+            tokenB: token_b,
+            fee: fee.into(),
+        }
+        .abi_encode()
+        .into(),
+        value: U256::ZERO,
+    }
+}
+
+/// Produces the calldata for enabling a fee tier on the factory, which only the owner may call.
+///
+/// ## Arguments
+///
+/// * `fee_amount`: The fee tier to enable with its tick spacing
+#[inline]
+#[must_use]
+pub fn enable_fee_amount_call_parameters(fee_amount: FeeAmount) -> MethodParameters {
+    MethodParameters {
+        calldata: IUniswapV3Factory::enableFeeAmountCall {
+            fee: fee_amount.into(),
+            tickSpacing: fee_amount.tick_spacing(),
+        }
+        .abi_encode()
+        .into(),
+        value: U256::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex};
+
+    #[test]
+    fn test_get_fee_amount() {
+        assert_eq!(
+            get_fee_amount(1, U24::from(3000)).unwrap(),
+            FeeAmount::MEDIUM
+        );
+        assert!(matches!(
+            get_fee_amount(1, U24::from(2500)),
+            Err(Error::UnknownFeeTier(fee)) if fee == U24::from(2500)
+        ));
+        assert!(get_fee_tiers(ChainId::MAX).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_register_fee_tier() {
+        const CHAIN_ID: ChainId = ChainId::MAX - 2;
+        register_deployment(CHAIN_ID, default_deployment(1).unwrap());
+        assert_eq!(
+            get_fee_amount(CHAIN_ID, U24::from(3000)).unwrap(),
+            FeeAmount::MEDIUM
+        );
+        register_fee_tier(CHAIN_ID, FeeAmount::new_custom(2500, 50));
+        register_fee_tier(CHAIN_ID, FeeAmount::new_custom(3000, 50));
+        assert_eq!(
+            get_fee_amount(CHAIN_ID, U24::from(2500))
+                .unwrap()
+                .tick_spacing()
+                .as_i32(),
+            50
+        );
+        // a registered fee tier overrides the tick spacing of a default one
+        assert_eq!(
+            get_fee_amount(CHAIN_ID, U24::from(3000)).unwrap(),
+            FeeAmount::new_custom(3000, 50)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_registered_deployment_fee_tiers() {
+        const CHAIN_ID: ChainId = ChainId::MAX - 3;
+        const FEE_TIERS: [FeeAmount; 2] = [FeeAmount::LOWEST, FeeAmount::LOW_200];
+        assert!(get_fee_tiers(CHAIN_ID).is_empty());
+        // fee tiers known only at runtime are owned by the deployment
+        register_deployment(
+            CHAIN_ID,
+            Deployment {
+                fee_tiers: FEE_TIERS.to_vec().into(),
+                ..default_deployment(1).unwrap()
+            },
+        );
+        assert_eq!(get_fee_tiers(CHAIN_ID), FEE_TIERS);
+        assert_eq!(
+            get_fee_amount(CHAIN_ID, U24::from(200)).unwrap(),
+            FeeAmount::LOW_200
+        );
+        assert!(get_fee_amount(CHAIN_ID, U24::from(3000)).is_err());
+    }
+
+    #[test]
+    fn test_fee_amount_try_from() {
+        assert_eq!(FeeAmount::try_from(3000_u32).unwrap(), FeeAmount::MEDIUM);
+        assert_eq!(
+            FeeAmount::try_from(U24::from(200)).unwrap(),
+            FeeAmount::LOW_200
+        );
+        assert!(matches!(
+            FeeAmount::try_from(1234_u32),
+            Err(Error::UnknownFeeTier(fee)) if fee == U24::from(1234)
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_fee_amount_try_from_registered() {
+        const CHAIN_ID: ChainId = ChainId::MAX - 4;
+        register_fee_tier(CHAIN_ID, FeeAmount::new_custom(4321, 7));
+        register_fee_tier(CHAIN_ID - 1, FeeAmount::new_custom(4321, 7));
+        assert_eq!(
+            FeeAmount::try_from(4321_u32).unwrap(),
+            FeeAmount::new_custom(4321, 7)
+        );
+        // the tick spacing of a fee registered differently on two chains is ambiguous
+        register_fee_tier(CHAIN_ID, FeeAmount::new_custom(4322, 7));
+        register_fee_tier(CHAIN_ID - 1, FeeAmount::new_custom(4322, 8));
+        assert!(FeeAmount::try_from(4322_u32).is_err());
+    }
+
+    #[test]
+    fn test_create_pool_call_parameters() {
+        let MethodParameters { calldata, value } = create_pool_call_parameters(
+            address!("0000000000000000000000000000000000000001"),
+            address!("0000000000000000000000000000000000000002"),
+            FeeAmount::MEDIUM,
+        );
+        assert_eq!(calldata.to_vec(), hex!("a1671295000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000bb8"));
+        assert_eq!(value, U256::ZERO);
+    }
+
+    #[test]
+    fn test_enable_fee_amount_call_parameters() {
+        let MethodParameters { calldata, value } =
+            enable_fee_amount_call_parameters(FeeAmount::new_custom(2500, 50));
+        assert_eq!(calldata.to_vec(), hex!("8a7c195f00000000000000000000000000000000000000000000000000000000000009c40000000000000000000000000000000000000000000000000000000000000032"));
+        assert_eq!(value, U256::ZERO);
+    }
+}
@@ -25,14 +25,24 @@
 
 extern crate alloc;
 
+// only used by the `uniswap-v3` binary
+#[cfg(feature = "cli")]
+use clap as _;
+
 pub mod abi;
+pub mod approve_and_call;
 pub mod constants;
+pub mod deployments;
+pub mod dex_config;
 pub mod entities;
 pub mod error;
+pub mod factory;
 pub mod multicall;
 pub mod nonfungible_position_manager;
 pub mod payments;
+pub mod payments_extended;
 pub mod quoter;
+pub mod range_order;
 pub mod self_permit;
 pub mod staker;
 pub mod swap_router;
@@ -41,13 +51,18 @@ pub mod utils;
 #[cfg(feature = "extensions")]
 pub mod extensions;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 #[cfg(test)]
 mod tests;
 
 pub mod prelude {
     pub use crate::{
-        abi::*, constants::*, entities::*, error::*, multicall::*, nonfungible_position_manager::*,
-        payments::*, quoter::*, self_permit::*, staker::*, swap_router::*, utils::*,
+        abi::*, approve_and_call::*, constants::*, deployments::*, dex_config::*, entities::*,
+        error::*, factory::*, multicall::*, nonfungible_position_manager::*, payments::*,
+        payments_extended::*, quoter::*, range_order::*, self_permit::*, staker::*, swap_router::*,
+        utils::*,
     };
 
     pub use uniswap_sdk_core as sdk_core;
@@ -0,0 +1,89 @@
+use crate::prelude::IPeripheryPaymentsExtended;
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::SolCall;
+
+/// Encodes a call that unwraps the router's WETH9 balance and sends the ether to `msg.sender`.
+#[inline]
+#[must_use]
+pub fn encode_unwrap_weth9_to_sender(amount_minimum: U256) -> Bytes {
+    IPeripheryPaymentsExtended::unwrapWETH9Call {
+        amountMinimum: amount_minimum,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Encodes a call that sweeps the router's balance of `token` to `msg.sender`.
+#[inline]
+#[must_use]
+pub fn encode_sweep_token_to_sender(token: Address, amount_minimum: U256) -> Bytes {
+    IPeripheryPaymentsExtended::sweepTokenCall {
+        token,
+        amountMinimum: amount_minimum,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Encodes a call that transfers `value` of `token` from `msg.sender` to the router.
+#[inline]
+#[must_use]
+pub fn encode_pull(token: Address, value: U256) -> Bytes {
+    IPeripheryPaymentsExtended::pullCall { token, value }
+        .abi_encode()
+        .into()
+}
+
+/// Encodes a call that wraps `value` of the ether held by the router into WETH9.
+#[inline]
+#[must_use]
+pub fn encode_wrap_eth(value: U256) -> Bytes {
+    IPeripheryPaymentsExtended::wrapETHCall { value }
+        .abi_encode()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, hex, uint};
+
+    const AMOUNT: U256 = uint!(123_U256);
+    const TOKEN: Address = address!("0000000000000000000000000000000000000001");
+
+    #[test]
+    fn test_encode_unwrap_weth9_to_sender() {
+        let calldata = encode_unwrap_weth9_to_sender(AMOUNT);
+        assert_eq!(
+            calldata.to_vec(),
+            hex!("49616997000000000000000000000000000000000000000000000000000000000000007b")
+        );
+    }
+
+    #[test]
+    fn test_encode_sweep_token_to_sender() {
+        let calldata = encode_sweep_token_to_sender(TOKEN, AMOUNT);
+        assert_eq!(
+            calldata.to_vec(),
+            hex!("e90a182f0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000007b")
+        );
+    }
+
+    #[test]
+    fn test_encode_pull() {
+        let calldata = encode_pull(TOKEN, AMOUNT);
+        assert_eq!(
+            calldata.to_vec(),
+            hex!("f2d5d56b0000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000007b")
+        );
+    }
+
+    #[test]
+    fn test_encode_wrap_eth() {
+        let calldata = encode_wrap_eth(AMOUNT);
+        assert_eq!(
+            calldata.to_vec(),
+            hex!("1c58db4f000000000000000000000000000000000000000000000000000000000000007b")
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::prelude::*;
+use alloc::{vec, vec::Vec};
 use alloy_primitives::{U160, U256};
 use alloy_sol_types::SolCall;
 use uniswap_sdk_core::prelude::*;
@@ -12,6 +13,22 @@ pub struct QuoteOptions {
     pub use_quoter_v2: bool,
 }
 
+/// The decoded return data of a quote.
+///
+/// The per-pool lists are ordered like the pools of the quoted [`Route`], regardless of the trade
+/// type, and are empty for the v1 quoter.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QuoteResult {
+    /// The amount out for an exact input quote, or the amount in for an exact output quote
+    pub amount: U256,
+    /// The square root price of each pool after the swap
+    pub sqrt_price_x96_after_list: Vec<U160>,
+    /// The number of initialized ticks crossed in each pool
+    pub initialized_ticks_crossed_list: Vec<u32>,
+    /// The estimated gas used by the swap, or `None` for the v1 quoter
+    pub gas_estimate: Option<U256>,
+}
+
 /// Produces the on-chain method name of the appropriate function within QuoterV2,
 /// and the relevant hex encoded parameters.
 ///
@@ -91,13 +108,23 @@ where
             "MULTIHOP_PRICE_LIMIT"
         );
         let path = encode_route_to_path(route, trade_type == TradeType::ExactOutput);
-        match trade_type {
-            TradeType::ExactInput => IQuoter::quoteExactInputCall {
+        match (trade_type, options.use_quoter_v2) {
+            (TradeType::ExactInput, true) => IQuoterV2::quoteExactInputCall {
+                path,
+                amountIn: quote_amount,
+            }
+            .abi_encode(),
+            (TradeType::ExactInput, false) => IQuoter::quoteExactInputCall {
                 path,
                 amountIn: quote_amount,
             }
             .abi_encode(),
-            TradeType::ExactOutput => IQuoter::quoteExactOutputCall {
+            (TradeType::ExactOutput, true) => IQuoterV2::quoteExactOutputCall {
+                path,
+                amountOut: quote_amount,
+            }
+            .abi_encode(),
+            (TradeType::ExactOutput, false) => IQuoter::quoteExactOutputCall {
                 path,
                 amountOut: quote_amount,
             }
@@ -110,6 +137,102 @@ where
     }
 }
 
+/// Decodes the return data of the quoter call produced by [`quote_call_parameters`] with the same
+/// arguments.
+///
+/// ## Arguments
+///
+/// * `route`: The swap route that was quoted
+/// * `trade_type`: The trade type, either exact input or exact output
+/// * `options`: The options used to produce the quote call
+/// * `data`: The return data of the quoter call
+#[inline]
+pub fn decode_quote_result<TInput, TOutput, TP>(
+    route: &Route<TInput, TOutput, TP>,
+    trade_type: TradeType,
+    options: Option<QuoteOptions>,
+    data: &[u8],
+) -> Result<QuoteResult, alloy_sol_types::Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    let options = options.unwrap_or_default();
+    let single_hop = route.pools.len() == 1;
+    if !options.use_quoter_v2 {
+        let amount = match (trade_type, single_hop) {
+            (TradeType::ExactInput, true) => {
+                IQuoter::quoteExactInputSingleCall::abi_decode_returns(data, true)?.amountOut
+            }
+            (TradeType::ExactInput, false) => {
+                IQuoter::quoteExactInputCall::abi_decode_returns(data, true)?.amountOut
+            }
+            (TradeType::ExactOutput, true) => {
+                IQuoter::quoteExactOutputSingleCall::abi_decode_returns(data, true)?.amountIn
+            }
+            (TradeType::ExactOutput, false) => {
+                IQuoter::quoteExactOutputCall::abi_decode_returns(data, true)?.amountIn
+            }
+        };
+        return Ok(QuoteResult {
+            amount,
+            ..Default::default()
+        });
+    }
+    let mut result = match (trade_type, single_hop) {
+        (TradeType::ExactInput, true) => {
+            let decoded = IQuoterV2::quoteExactInputSingleCall::abi_decode_returns(data, true)?;
+            QuoteResult {
+                amount: decoded.amountOut,
+                sqrt_price_x96_after_list: vec![decoded.sqrtPriceX96After],
+                initialized_ticks_crossed_list: vec![decoded.initializedTicksCrossed],
+                gas_estimate: Some(decoded.gasEstimate),
+            }
+        }
+        (TradeType::ExactInput, false) => {
+            let decoded = IQuoterV2::quoteExactInputCall::abi_decode_returns(data, true)?;
+            QuoteResult {
+                amount: decoded.amountOut,
+                sqrt_price_x96_after_list: decoded.sqrtPriceX96AfterList,
+                initialized_ticks_crossed_list: decoded.initializedTicksCrossedList,
+                gas_estimate: Some(decoded.gasEstimate),
+            }
+        }
+        (TradeType::ExactOutput, true) => {
+            let decoded = IQuoterV2::quoteExactOutputSingleCall::abi_decode_returns(data, true)?;
+            QuoteResult {
+                amount: decoded.amountIn,
+                sqrt_price_x96_after_list: vec![decoded.sqrtPriceX96After],
+                initialized_ticks_crossed_list: vec![decoded.initializedTicksCrossed],
+                gas_estimate: Some(decoded.gasEstimate),
+            }
+        }
+        (TradeType::ExactOutput, false) => {
+            let decoded = IQuoterV2::quoteExactOutputCall::abi_decode_returns(data, true)?;
+            QuoteResult {
+                amount: decoded.amountIn,
+                sqrt_price_x96_after_list: decoded.sqrtPriceX96AfterList,
+                initialized_ticks_crossed_list: decoded.initializedTicksCrossedList,
+                gas_estimate: Some(decoded.gasEstimate),
+            }
+        }
+    };
+    if result.sqrt_price_x96_after_list.len() != route.pools.len()
+        || result.initialized_ticks_crossed_list.len() != route.pools.len()
+    {
+        return Err(alloy_sol_types::Error::custom(
+            "quote result does not match the number of pools in the route",
+        ));
+    }
+    // exact output paths are encoded in reverse
+    if trade_type == TradeType::ExactOutput {
+        result.sqrt_price_x96_after_list.reverse();
+        result.initialized_ticks_crossed_list.reverse();
+    }
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -292,4 +415,121 @@ mod tests {
             );
         }
     }
+
+    mod multi_hop_using_quoter_v2 {
+        use super::*;
+        use alloy_primitives::uint;
+
+        static ROUTE: Lazy<Route<Token, Ether, TickListDataProvider>> = Lazy::new(|| {
+            Route::new(
+                vec![POOL_0_1.clone(), POOL_1_WETH.clone()],
+                TOKEN0.clone(),
+                ETHER.clone(),
+            )
+        });
+        const QUOTER_V2: Option<QuoteOptions> = Some(QuoteOptions {
+            sqrt_price_limit_x96: U160::ZERO,
+            use_quoter_v2: true,
+        });
+
+        #[test]
+        fn multi_hop_exact_input() {
+            let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap();
+            let params = quote_call_parameters(&ROUTE, &amount, TradeType::ExactInput, QUOTER_V2);
+            let decoded =
+                IQuoterV2::quoteExactInputCall::abi_decode(&params.calldata, true).unwrap();
+            assert_eq!(decoded.path, encode_route_to_path(&ROUTE, false));
+            assert_eq!(decoded.amountIn, uint!(100_U256));
+        }
+
+        #[test]
+        fn multi_hop_exact_output() {
+            let amount = CurrencyAmount::from_raw_amount(ETHER.clone(), 100).unwrap();
+            let params = quote_call_parameters(&ROUTE, &amount, TradeType::ExactOutput, QUOTER_V2);
+            let decoded =
+                IQuoterV2::quoteExactOutputCall::abi_decode(&params.calldata, true).unwrap();
+            assert_eq!(decoded.path, encode_route_to_path(&ROUTE, true));
+            assert_eq!(decoded.amountOut, uint!(100_U256));
+        }
+
+        #[test]
+        fn decode_exact_input() {
+            let data = IQuoterV2::quoteExactInputCall::abi_encode_returns(&(
+                uint!(90_U256),
+                vec![uint!(1_U160), uint!(2_U160)],
+                vec![3_u32, 4],
+                uint!(100000_U256),
+            ));
+            let result =
+                decode_quote_result(&ROUTE, TradeType::ExactInput, QUOTER_V2, &data).unwrap();
+            assert_eq!(
+                result,
+                QuoteResult {
+                    amount: uint!(90_U256),
+                    sqrt_price_x96_after_list: vec![uint!(1_U160), uint!(2_U160)],
+                    initialized_ticks_crossed_list: vec![3, 4],
+                    gas_estimate: Some(uint!(100000_U256)),
+                }
+            );
+        }
+
+        #[test]
+        fn decode_exact_output_in_route_order() {
+            // the quoter reports the pools of a reversed path
+            let data = IQuoterV2::quoteExactOutputCall::abi_encode_returns(&(
+                uint!(110_U256),
+                vec![uint!(2_U160), uint!(1_U160)],
+                vec![4_u32, 3],
+                uint!(100000_U256),
+            ));
+            let result =
+                decode_quote_result(&ROUTE, TradeType::ExactOutput, QUOTER_V2, &data).unwrap();
+            assert_eq!(result.amount, uint!(110_U256));
+            assert_eq!(
+                result.sqrt_price_x96_after_list,
+                vec![uint!(1_U160), uint!(2_U160)]
+            );
+            assert_eq!(result.initialized_ticks_crossed_list, vec![3, 4]);
+        }
+
+        #[test]
+        fn decode_mismatched_pools() {
+            let data = IQuoterV2::quoteExactInputCall::abi_encode_returns(&(
+                uint!(90_U256),
+                vec![uint!(1_U160)],
+                vec![3_u32],
+                uint!(100000_U256),
+            ));
+            assert!(decode_quote_result(&ROUTE, TradeType::ExactInput, QUOTER_V2, &data).is_err());
+        }
+
+        #[test]
+        fn decode_single_hop() {
+            let route = Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone());
+            let data = IQuoterV2::quoteExactInputSingleCall::abi_encode_returns(&(
+                uint!(90_U256),
+                uint!(1_U160),
+                3_u32,
+                uint!(100000_U256),
+            ));
+            let result =
+                decode_quote_result(&route, TradeType::ExactInput, QUOTER_V2, &data).unwrap();
+            assert_eq!(result.amount, uint!(90_U256));
+            assert_eq!(result.sqrt_price_x96_after_list, vec![uint!(1_U160)]);
+            assert_eq!(result.initialized_ticks_crossed_list, vec![3]);
+        }
+
+        #[test]
+        fn decode_quoter_v1() {
+            let data = IQuoter::quoteExactOutputCall::abi_encode_returns(&(uint!(110_U256),));
+            let result = decode_quote_result(&ROUTE, TradeType::ExactOutput, None, &data).unwrap();
+            assert_eq!(
+                result,
+                QuoteResult {
+                    amount: uint!(110_U256),
+                    ..Default::default()
+                }
+            );
+        }
+    }
 }
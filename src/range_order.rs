@@ -0,0 +1,376 @@
+//! ## Range Orders
+//! A range order is a single-sided position one tick spacing wide that behaves like a limit order:
+//! the deposited token is converted into the other token as the pool price crosses the range.
+
+use crate::prelude::{Error, *};
+use alloy_primitives::U256;
+use uniswap_sdk_core::prelude::*;
+
+/// The fill status of a [`RangeOrder`] given the current pool price
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RangeOrderStatus {
+    /// The pool price has not entered the range, the position holds only the input token.
+    Open,
+    /// The pool price is within the range, the position holds both tokens.
+    PartiallyFilled,
+    /// The pool price has fully crossed the range, the position holds only the output token.
+    Filled,
+}
+
+/// A single-sided position one tick spacing wide used as a limit order
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeOrder<TP = NoTickDataProvider>
+where
+    TP: TickDataProvider,
+{
+    /// The underlying position
+    pub position: Position<TP>,
+    /// Whether the order sells token0 for token1
+    pub zero_for_one: bool,
+}
+
+impl<TP: TickDataProvider> RangeOrder<TP> {
+    /// Constructs a range order selling the input token at or better than the target price
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The pool in which the order is placed
+    /// * `amount_in`: The amount of the token to sell
+    /// * `target_price`: The minimum price of the input token in terms of the output token
+    ///
+    /// ## Returns
+    ///
+    /// The range order, [`Error::InvalidToken`] if the input token is not one of the pool's tokens,
+    /// or [`Error::InvalidPrice`] if the adjacent usable range is not entirely on the far side of
+    /// the current pool price
+    #[inline]
+    pub fn new(
+        pool: Pool<TP>,
+        amount_in: &CurrencyAmount<Token>,
+        target_price: &Price<Token, Token>,
+    ) -> Result<Self, Error> {
+        if !pool.involves_token(&amount_in.currency)
+            || !target_price.base_currency.equals(&amount_in.currency)
+            || !pool.involves_token(&target_price.quote_currency)
+        {
+            return Err(Error::InvalidToken);
+        }
+        let zero_for_one = amount_in.currency.equals(&pool.token0);
+        let tick_spacing = pool.tick_spacing();
+        let target_tick = TP::Index::from_i24(price_to_closest_tick(target_price)?);
+        let floor = target_tick.compress(tick_spacing) * tick_spacing;
+        let amount_in = U256::from_big_int(amount_in.quotient());
+        let position = if zero_for_one {
+            // token0 is sold as the price rises, so the whole range must be at or above the target
+            let tick_lower = if floor == target_tick {
+                floor
+            } else {
+                floor + tick_spacing
+            };
+            if pool.tick_current >= tick_lower {
+                return Err(Error::InvalidPrice);
+            }
+            Position::from_amount0(pool, tick_lower, tick_lower + tick_spacing, amount_in, true)?
+        } else {
+            // token1 is sold as the price falls, so the whole range must be at or below the target
+            let tick_upper = floor;
+            if pool.tick_current < tick_upper {
+                return Err(Error::InvalidPrice);
+            }
+            Position::from_amount1(pool, tick_upper - tick_spacing, tick_upper, amount_in)?
+        };
+        Ok(Self {
+            position,
+            zero_for_one,
+        })
+    }
+
+    /// Returns the pool price of token0 at which the order is completely filled
+    #[inline]
+    pub fn fill_price(&self) -> Result<Price<Token, Token>, Error> {
+        if self.zero_for_one {
+            self.position.token0_price_upper()
+        } else {
+            self.position.token0_price_lower()
+        }
+    }
+
+    /// Returns the fill status of the order given the current pool state
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The current state of the pool in which the order is placed
+    #[inline]
+    pub fn status<TP2: TickDataProvider<Index = TP::Index>>(
+        &self,
+        pool: &Pool<TP2>,
+    ) -> RangeOrderStatus {
+        let tick_lower = self.position.tick_lower;
+        let tick_upper = self.position.tick_upper;
+        if pool.tick_current < tick_lower {
+            if self.zero_for_one {
+                RangeOrderStatus::Open
+            } else {
+                RangeOrderStatus::Filled
+            }
+        } else if pool.tick_current < tick_upper {
+            RangeOrderStatus::PartiallyFilled
+        } else if self.zero_for_one {
+            RangeOrderStatus::Filled
+        } else {
+            RangeOrderStatus::Open
+        }
+    }
+
+    /// Produces the calldata for minting the order
+    ///
+    /// ## Arguments
+    ///
+    /// * `options`: Additional information necessary for generating the calldata
+    #[inline]
+    pub fn mint_call_parameters(
+        &mut self,
+        options: AddLiquidityOptions,
+    ) -> Result<MethodParameters, Error> {
+        add_call_parameters(&mut self.position, options)
+    }
+
+    /// Produces the calldata for removing the liquidity of a filled order and collecting the
+    /// output tokens
+    ///
+    /// ## Arguments
+    ///
+    /// * `pool`: The current state of the pool in which the order is placed
+    /// * `token_id`: The ID of the position minted for the order
+    /// * `recipient`: The account that should receive the tokens
+    /// * `deadline`: When the transaction expires, in epoch seconds
+    /// * `burn_token`: Whether the NFT should be burned
+    ///
+    /// ## Returns
+    ///
+    /// The calldata, or [`Error::RangeOrderNotFilled`] if the order is not filled at the current
+    /// pool price
+    #[inline]
+    pub fn remove_call_parameters<TP2: TickDataProvider<Index = TP::Index>>(
+        &self,
+        pool: &Pool<TP2>,
+        token_id: U256,
+        recipient: Address,
+        deadline: U256,
+        burn_token: bool,
+    ) -> Result<MethodParameters, Error> {
+        if self.status(pool) != RangeOrderStatus::Filled {
+            return Err(Error::RangeOrderNotFilled);
+        }
+        let tick_lower = self.position.tick_lower;
+        let tick_upper = self.position.tick_upper;
+        let position = Position::new(
+            Pool::new(
+                pool.token0.clone(),
+                pool.token1.clone(),
+                pool.fee,
+                pool.sqrt_ratio_x96,
+                pool.liquidity,
+            )?,
+            self.position.liquidity,
+            tick_lower
+                .try_into()
+                .map_err(|_| Error::InvalidTick(tick_lower.to_i24()))?,
+            tick_upper
+                .try_into()
+                .map_err(|_| Error::InvalidTick(tick_upper.to_i24()))?,
+        );
+        remove_call_parameters(
+            &position,
+            RemoveLiquidityOptions {
+                token_id,
+                liquidity_percentage: Percent::new(1, 1),
+                slippage_tolerance: Percent::new(0, 1),
+                deadline,
+                burn_token,
+                permit: None,
+                collect_options: CollectOptions {
+                    token_id,
+                    expected_currency_owed0: CurrencyAmount::from_raw_amount(
+                        pool.token0.clone(),
+                        0,
+                    )?,
+                    expected_currency_owed1: CurrencyAmount::from_raw_amount(
+                        pool.token1.clone(),
+                        0,
+                    )?,
+                    recipient,
+                },
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloy_primitives::{address, aliases::I24, uint, Bytes};
+    use alloy_sol_types::SolCall;
+
+    const RECIPIENT: Address = address!("0000000000000000000000000000000000000003");
+    const DEADLINE: U256 = uint!(123_U256);
+
+    fn pool_at_tick(tick: i32) -> Pool {
+        Pool::new(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            FeeAmount::MEDIUM,
+            get_sqrt_ratio_at_tick(I24::unchecked_from(tick)).unwrap(),
+            0,
+        )
+        .unwrap()
+    }
+
+    fn target_price(base: &Token, quote: &Token, tick: i32) -> Price<Token, Token> {
+        tick_to_price(base.clone(), quote.clone(), I24::unchecked_from(tick)).unwrap()
+    }
+
+    #[test]
+    fn test_sell_token0_above_current_price() {
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000).unwrap();
+        let order = RangeOrder::new(
+            POOL_0_1.clone(),
+            &amount,
+            &target_price(&TOKEN0, &TOKEN1, 100),
+        )
+        .unwrap();
+        assert!(order.zero_for_one);
+        assert_eq!(order.position.tick_lower, 120);
+        assert_eq!(order.position.tick_upper, 180);
+        assert!(order.position.amount1().unwrap().quotient().is_zero());
+        assert_eq!(
+            order.fill_price().unwrap(),
+            target_price(&TOKEN0, &TOKEN1, 180)
+        );
+    }
+
+    #[test]
+    fn test_sell_token1_below_current_price() {
+        let amount = CurrencyAmount::from_raw_amount(TOKEN1.clone(), 1_000_000).unwrap();
+        let order = RangeOrder::new(
+            POOL_0_1.clone(),
+            &amount,
+            &target_price(&TOKEN1, &TOKEN0, -100),
+        )
+        .unwrap();
+        assert!(!order.zero_for_one);
+        assert_eq!(order.position.tick_lower, -180);
+        assert_eq!(order.position.tick_upper, -120);
+        assert!(order.position.amount0().unwrap().quotient().is_zero());
+    }
+
+    #[test]
+    fn test_target_on_wrong_side_of_price() {
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000).unwrap();
+        assert!(matches!(
+            RangeOrder::new(
+                POOL_0_1.clone(),
+                &amount,
+                &target_price(&TOKEN0, &TOKEN1, -100)
+            ),
+            Err(Error::InvalidPrice)
+        ));
+    }
+
+    #[test]
+    fn test_status() {
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000).unwrap();
+        let order = RangeOrder::new(
+            POOL_0_1.clone(),
+            &amount,
+            &target_price(&TOKEN0, &TOKEN1, 100),
+        )
+        .unwrap();
+        assert_eq!(order.status(&POOL_0_1), RangeOrderStatus::Open);
+        assert_eq!(
+            order.status(&pool_at_tick(150)),
+            RangeOrderStatus::PartiallyFilled
+        );
+        assert_eq!(order.status(&pool_at_tick(180)), RangeOrderStatus::Filled);
+    }
+
+    #[test]
+    fn test_mint_and_remove_call_parameters() {
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000).unwrap();
+        let mut order = RangeOrder::new(
+            POOL_0_1.clone(),
+            &amount,
+            &target_price(&TOKEN0, &TOKEN1, 100),
+        )
+        .unwrap();
+        let MethodParameters { calldata, value } = order
+            .mint_call_parameters(AddLiquidityOptions {
+                slippage_tolerance: Percent::new(1, 100),
+                deadline: DEADLINE,
+                use_native: None,
+                token0_permit: None,
+                token1_permit: None,
+                specific_opts: AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+                    recipient: RECIPIENT,
+                    create_pool: false,
+                }),
+            })
+            .unwrap();
+        assert_eq!(value, U256::ZERO);
+        let mint = INonfungiblePositionManager::mintCall::abi_decode(&calldata, true)
+            .unwrap()
+            .params;
+        assert_eq!(mint.tickLower.as_i32(), 120);
+        assert_eq!(mint.tickUpper.as_i32(), 180);
+        assert_eq!(mint.amount1Desired, U256::ZERO);
+
+        let MethodParameters { calldata, .. } = order
+            .remove_call_parameters(&pool_at_tick(200), uint!(1_U256), RECIPIENT, DEADLINE, true)
+            .unwrap();
+        let calls = Vec::<Bytes>::decode_multicall(&calldata).unwrap();
+        // decreaseLiquidity, collect, burn
+        assert_eq!(calls.len(), 3);
+        let decrease =
+            INonfungiblePositionManager::decreaseLiquidityCall::abi_decode(&calls[0], true)
+                .unwrap()
+                .params;
+        assert_eq!(decrease.liquidity, order.position.liquidity);
+        assert_eq!(decrease.amount0Min, U256::ZERO);
+        assert!(decrease.amount1Min > U256::ZERO);
+    }
+
+    #[test]
+    fn test_remove_call_parameters_not_filled() {
+        let amount = CurrencyAmount::from_raw_amount(TOKEN0.clone(), 1_000_000).unwrap();
+        let order = RangeOrder::new(
+            POOL_0_1.clone(),
+            &amount,
+            &target_price(&TOKEN0, &TOKEN1, 100),
+        )
+        .unwrap();
+        assert!(matches!(
+            order.remove_call_parameters(
+                &pool_at_tick(150),
+                uint!(1_U256),
+                RECIPIENT,
+                DEADLINE,
+                true
+            ),
+            Err(Error::RangeOrderNotFilled)
+        ));
+    }
+
+    #[test]
+    fn test_foreign_input_token() {
+        let amount = CurrencyAmount::from_raw_amount(TOKEN2.clone(), 1_000_000).unwrap();
+        assert!(matches!(
+            RangeOrder::new(
+                POOL_0_1.clone(),
+                &amount,
+                &target_price(&TOKEN2, &TOKEN1, 100)
+            ),
+            Err(Error::InvalidToken)
+        ));
+    }
+}
@@ -1,7 +1,7 @@
 use crate::prelude::*;
-use alloc::vec::Vec;
-use alloy_primitives::{Address, Bytes, U256};
-use alloy_sol_types::{SolCall, SolValue};
+use alloc::{vec, vec::Vec};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U160, U256};
+use alloy_sol_types::{SolCall, SolType, SolValue};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FullWithdrawOptions {
@@ -175,6 +175,216 @@ pub fn encode_deposit<TP: TickDataProvider>(incentive_keys: &[IncentiveKey<TP>])
     .into()
 }
 
+/// Decodes the incentive keys from the `data` passed to `safeTransferFrom` when depositing a
+/// position, as encoded by [`encode_deposit`].
+#[inline]
+pub fn decode_deposit(
+    data: &[u8],
+) -> Result<Vec<IUniswapV3Staker::IncentiveKey>, alloy_sol_types::Error> {
+    if Some(data.len()) == <IUniswapV3Staker::IncentiveKey as SolType>::ENCODED_SIZE {
+        Ok(vec![
+            <IUniswapV3Staker::IncentiveKey as SolValue>::abi_decode(data, true)?,
+        ])
+    } else {
+        <Vec<IUniswapV3Staker::IncentiveKey> as SolValue>::abi_decode(data, true)
+    }
+}
+
+/// Computes the ID of an incentive as `keccak256(abi.encode(key))`, the key of the `incentives`
+/// and `stakes` mappings of the staker.
+#[inline]
+#[must_use]
+pub fn compute_incentive_id<TP: TickDataProvider>(incentive_key: &IncentiveKey<TP>) -> B256 {
+    keccak256(encode_incentive_key(incentive_key).abi_encode())
+}
+
+/// Decodes the IDs of the incentives a position is staked in from the `data` passed to
+/// `safeTransferFrom` when depositing it.
+#[inline]
+pub fn decode_incentive_ids(data: &[u8]) -> Result<Vec<B256>, alloy_sol_types::Error> {
+    Ok(decode_deposit(data)?
+        .iter()
+        .map(|key| keccak256(key.abi_encode()))
+        .collect())
+}
+
+/// Produces the calldata for creating an incentive. The staker must be approved to transfer
+/// `reward` of the reward token beforehand.
+///
+/// ## Arguments
+///
+/// * `incentive_key`: The key of the incentive to create
+/// * `reward`: The amount of reward tokens to be distributed
+#[inline]
+pub fn create_incentive_call_parameters<TP: TickDataProvider>(
+    incentive_key: &IncentiveKey<TP>,
+    reward: U256,
+) -> MethodParameters {
+    assert!(!reward.is_zero(), "ZERO_REWARD");
+    assert!(
+        incentive_key.start_time < incentive_key.end_time,
+        "START_TIME_AFTER_END_TIME"
+    );
+    MethodParameters {
+        calldata: IUniswapV3Staker::createIncentiveCall {
+            key: encode_incentive_key(incentive_key),
+            reward,
+        }
+        .abi_encode()
+        .into(),
+        value: U256::ZERO,
+    }
+}
+
+/// Produces the calldata for ending incentives after their end time, refunding the unclaimed
+/// rewards to the refundee of each incentive.
+///
+/// ## Arguments
+///
+/// * `incentive_keys`: The keys of the incentives to end
+#[inline]
+pub fn end_incentive_call_parameters<TP: TickDataProvider>(
+    incentive_keys: &[IncentiveKey<TP>],
+) -> MethodParameters {
+    MethodParameters {
+        calldata: encode_multicall(
+            incentive_keys
+                .iter()
+                .map(|incentive_key| {
+                    IUniswapV3Staker::endIncentiveCall {
+                        key: encode_incentive_key(incentive_key),
+                    }
+                    .abi_encode()
+                })
+                .collect(),
+        ),
+        value: U256::ZERO,
+    }
+}
+
+/// Produces the calldata for transferring ownership of a deposited position.
+///
+/// ## Arguments
+///
+/// * `token_id`: The id of the deposited NFT
+/// * `to`: The new owner of the deposit
+#[inline]
+#[must_use]
+pub fn transfer_deposit_call_parameters(token_id: U256, to: Address) -> MethodParameters {
+    MethodParameters {
+        calldata: IUniswapV3Staker::transferDepositCall {
+            tokenId: token_id,
+            to,
+        }
+        .abi_encode()
+        .into(),
+        value: U256::ZERO,
+    }
+}
+
+/// The reward accrued by a staked position, as returned by `getRewardInfo`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RewardInfo {
+    /// The amount of reward tokens accrued by the position
+    pub reward: U256,
+    /// The seconds the position's liquidity has been in range for the incentive, as a Q128
+    pub seconds_inside_x128: U160,
+}
+
+/// Encodes the calldata of `getRewardInfo` for a staked position.
+///
+/// ## Arguments
+///
+/// * `incentive_key`: The key of the incentive the position is staked in
+/// * `token_id`: The id of the staked NFT
+#[inline]
+#[must_use]
+pub fn encode_get_reward_info<TP: TickDataProvider>(
+    incentive_key: &IncentiveKey<TP>,
+    token_id: U256,
+) -> Bytes {
+    IUniswapV3Staker::getRewardInfoCall {
+        key: encode_incentive_key(incentive_key),
+        tokenId: token_id,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Decodes the return data of `getRewardInfo`.
+#[inline]
+pub fn decode_get_reward_info(data: &[u8]) -> Result<RewardInfo, alloy_sol_types::Error> {
+    let IUniswapV3Staker::getRewardInfoReturn {
+        reward,
+        secondsInsideX128,
+    } = IUniswapV3Staker::getRewardInfoCall::abi_decode_returns(data, true)?;
+    Ok(RewardInfo {
+        reward,
+        seconds_inside_x128: secondsInsideX128,
+    })
+}
+
+/// The state of an incentive as stored in the `incentives` mapping of the staker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IncentiveState {
+    /// The amount of reward tokens not yet claimed
+    pub total_reward_unclaimed: U256,
+    /// The total seconds already claimed by unstaked positions, as a Q128
+    pub total_seconds_claimed_x128: U160,
+}
+
+/// The state of a stake as stored in the `stakes` mapping of the staker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StakeSnapshot {
+    /// The liquidity of the staked position
+    pub liquidity: u128,
+    /// The seconds per liquidity inside the position's range when it was staked
+    pub seconds_per_liquidity_inside_initial_x128: U160,
+}
+
+/// Estimates the reward accrued by a staked position without a node, mirroring
+/// [`RewardMath.computeRewardAmount`](https://github.com/Uniswap/v3-staker/blob/main/contracts/libraries/RewardMath.sol).
+///
+/// ## Arguments
+///
+/// * `incentive_key`: The key of the incentive the position is staked in
+/// * `incentive`: The current state of the incentive
+/// * `stake`: The state of the stake
+/// * `seconds_per_liquidity_inside_x128`: The current seconds per liquidity inside the position's
+///   range, as returned by `snapshotCumulativesInside` of the pool
+/// * `current_time`: The current block timestamp
+#[inline]
+pub fn compute_reward_amount<TP: TickDataProvider>(
+    incentive_key: &IncentiveKey<TP>,
+    incentive: IncentiveState,
+    stake: StakeSnapshot,
+    seconds_per_liquidity_inside_x128: U160,
+    current_time: U256,
+) -> Result<RewardInfo, Error> {
+    // the staker performs this computation in unchecked uint160 arithmetic
+    let seconds_inside_x128 = (seconds_per_liquidity_inside_x128
+        - stake.seconds_per_liquidity_inside_initial_x128)
+        * U160::from(stake.liquidity);
+    // no reward accrues before the incentive starts
+    if current_time < incentive_key.start_time {
+        return Ok(RewardInfo {
+            reward: U256::ZERO,
+            seconds_inside_x128,
+        });
+    }
+    let total_seconds_unclaimed_x128 =
+        ((incentive_key.end_time.max(current_time) - incentive_key.start_time) << 128)
+            - U256::from(incentive.total_seconds_claimed_x128);
+    Ok(RewardInfo {
+        reward: mul_div(
+            incentive.total_reward_unclaimed,
+            U256::from(seconds_inside_x128),
+            total_seconds_unclaimed_x128,
+        )?,
+        seconds_inside_x128,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -331,4 +541,146 @@ mod tests {
             hex!("b88d4fde000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000030000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000008000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000001f9840a85d5af5bf1d1762f925bdaddc4201f9840000000000000000000000004fa63b0dea87d2cd519f3b67a5ddb145779b7bd2000000000000000000000000000000000000000000000000000000000000006400000000000000000000000000000000000000000000000000000000000000c80000000000000000000000000000000000000000000000000000000000000001")
         );
     }
+
+    #[test]
+    fn test_compute_incentive_id() {
+        assert_eq!(
+            compute_incentive_id(&INCENTIVE_KEY),
+            keccak256(encode_deposit(&[INCENTIVE_KEY.clone()]))
+        );
+        assert_ne!(
+            compute_incentive_id(&INCENTIVE_KEYS[0]),
+            compute_incentive_id(&INCENTIVE_KEYS[1])
+        );
+    }
+
+    #[test]
+    fn test_decode_deposit_single_key() {
+        let keys = decode_deposit(&encode_deposit(&[INCENTIVE_KEY.clone()])).unwrap();
+        assert_eq!(keys, vec![encode_incentive_key(&INCENTIVE_KEY)]);
+        assert_eq!(
+            decode_incentive_ids(&encode_deposit(&[INCENTIVE_KEY.clone()])).unwrap(),
+            vec![compute_incentive_id(&INCENTIVE_KEY)]
+        );
+    }
+
+    #[test]
+    fn test_decode_deposit_multiple_keys() {
+        let keys = decode_deposit(&encode_deposit(&INCENTIVE_KEYS)).unwrap();
+        assert_eq!(
+            keys,
+            INCENTIVE_KEYS
+                .iter()
+                .map(encode_incentive_key)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            decode_incentive_ids(&encode_deposit(&INCENTIVE_KEYS)).unwrap(),
+            INCENTIVE_KEYS
+                .iter()
+                .map(compute_incentive_id)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_create_incentive() {
+        let MethodParameters { calldata, value } =
+            create_incentive_call_parameters(&INCENTIVE_KEY, uint!(1000_U256));
+        assert_eq!(value, U256::ZERO);
+        let decoded = IUniswapV3Staker::createIncentiveCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(decoded.key, encode_incentive_key(&INCENTIVE_KEY));
+        assert_eq!(decoded.reward, uint!(1000_U256));
+    }
+
+    #[test]
+    #[should_panic(expected = "ZERO_REWARD")]
+    fn test_create_incentive_zero_reward() {
+        create_incentive_call_parameters(&INCENTIVE_KEY, U256::ZERO);
+    }
+
+    #[test]
+    fn test_end_incentive() {
+        let MethodParameters { calldata, value } = end_incentive_call_parameters(&INCENTIVE_KEYS);
+        assert_eq!(value, U256::ZERO);
+        let calls = Vec::<Bytes>::decode_multicall(&calldata).unwrap();
+        assert_eq!(calls.len(), 2);
+        for (call, key) in calls.iter().zip(INCENTIVE_KEYS.iter()) {
+            let decoded = IUniswapV3Staker::endIncentiveCall::abi_decode(call, true).unwrap();
+            assert_eq!(decoded.key, encode_incentive_key(key));
+        }
+    }
+
+    #[test]
+    fn test_transfer_deposit() {
+        let MethodParameters { calldata, value } =
+            transfer_deposit_call_parameters(TOKEN_ID, RECIPIENT);
+        assert_eq!(value, U256::ZERO);
+        let decoded = IUniswapV3Staker::transferDepositCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(decoded.tokenId, TOKEN_ID);
+        assert_eq!(decoded.to, RECIPIENT);
+    }
+
+    #[test]
+    fn test_get_reward_info() {
+        let calldata = encode_get_reward_info(&INCENTIVE_KEY, TOKEN_ID);
+        let decoded = IUniswapV3Staker::getRewardInfoCall::abi_decode(&calldata, true).unwrap();
+        assert_eq!(decoded.key, encode_incentive_key(&INCENTIVE_KEY));
+        assert_eq!(decoded.tokenId, TOKEN_ID);
+
+        let data = IUniswapV3Staker::getRewardInfoCall::abi_encode_returns(&(
+            uint!(500_U256),
+            uint!(1234_U160),
+        ));
+        assert_eq!(
+            decode_get_reward_info(&data).unwrap(),
+            RewardInfo {
+                reward: uint!(500_U256),
+                seconds_inside_x128: uint!(1234_U160),
+            }
+        );
+    }
+
+    #[test]
+    fn test_compute_reward_amount() {
+        let incentive = IncentiveState {
+            total_reward_unclaimed: uint!(1000_U256),
+            total_seconds_claimed_x128: U160::ZERO,
+        };
+        // in range for 50 seconds with liquidity 2
+        let stake = StakeSnapshot {
+            liquidity: 2,
+            seconds_per_liquidity_inside_initial_x128: uint!(7_U160) << 128,
+        };
+        let seconds_per_liquidity_inside_x128 = (uint!(7_U160) << 128) + (uint!(25_U160) << 128);
+        let during = compute_reward_amount(
+            &INCENTIVE_KEY,
+            incentive,
+            stake,
+            seconds_per_liquidity_inside_x128,
+            uint!(150_U256),
+        )
+        .unwrap();
+        assert_eq!(during.seconds_inside_x128, uint!(50_U160) << 128);
+        assert_eq!(during.reward, uint!(500_U256));
+        // the reward rate decays after the end time
+        let after = compute_reward_amount(
+            &INCENTIVE_KEY,
+            incentive,
+            stake,
+            seconds_per_liquidity_inside_x128,
+            uint!(250_U256),
+        )
+        .unwrap();
+        assert_eq!(after.reward, uint!(333_U256));
+        let before = compute_reward_amount(
+            &INCENTIVE_KEY,
+            incentive,
+            stake,
+            seconds_per_liquidity_inside_x128,
+            uint!(50_U256),
+        )
+        .unwrap();
+        assert_eq!(before.reward, U256::ZERO);
+    }
 }
@@ -18,6 +18,178 @@ pub struct SwapOptions {
     pub fee: Option<FeeOptions>,
 }
 
+/// Options for producing the arguments to swap and add liquidity in a single call to the router.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapAndAddOptions {
+    /// How much the execution price is allowed to move unfavorably for the trade execution price.
+    pub slippage_tolerance: Percent,
+    /// How much the pool price is allowed to move when adding liquidity.
+    pub add_liquidity_slippage_tolerance: Percent,
+    /// The optional permit parameters for spending the input.
+    pub input_token_permit: Option<PermitOptions>,
+    /// The optional permit parameters for pulling in the remaining output token.
+    pub output_token_permit: Option<PermitOptions>,
+    /// The optional price limit for the trade.
+    pub sqrt_price_limit_x96: Option<U160>,
+    /// The approval the router must grant the position manager to spend the input token.
+    pub token_in_approval_type: ApprovalType,
+    /// The approval the router must grant the position manager to spend the output token.
+    pub token_out_approval_type: ApprovalType,
+}
+
+/// Encodes the swaps of the given trades, sending the output of each swap to `recipient`.
+///
+/// The swaps are encoded for `SwapRouter02` if `deadline` is `None`, or for the original
+/// `SwapRouter` whose parameters include the deadline otherwise.
+fn encode_swaps<TInput, TOutput, TP>(
+    trades: &[Trade<TInput, TOutput, TP>],
+    slippage_tolerance: &Percent,
+    recipient: Address,
+    sqrt_price_limit_x96: Option<U160>,
+    deadline: Option<U256>,
+    calldatas: &mut Vec<Bytes>,
+) -> Result<(), Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    for trade in trades {
+        for Swap {
+            route,
+            input_amount,
+            output_amount,
+        } in &trade.swaps
+        {
+            let amount_in = U256::from_big_int(
+                trade
+                    .maximum_amount_in(slippage_tolerance.clone(), Some(input_amount.clone()))?
+                    .quotient(),
+            );
+            let amount_out = U256::from_big_int(
+                trade
+                    .minimum_amount_out(slippage_tolerance.clone(), Some(output_amount.clone()))?
+                    .quotient(),
+            );
+
+            if route.pools.len() == 1 {
+                let token_in = route.input.wrapped().address();
+                let token_out = route.output.wrapped().address();
+                let fee = route.pools[0].fee.into();
+                let sqrt_price_limit_x96 = sqrt_price_limit_x96.unwrap_or_default();
+                calldatas.push(match (trade.trade_type, deadline) {
+                    (TradeType::ExactInput, None) => IV3SwapRouter::exactInputSingleCall {
+                        params: IV3SwapRouter::ExactInputSingleParams {
+                            tokenIn: token_in,
+                            tokenOut: token_out,
+                            fee,
+                            recipient,
+                            amountIn: amount_in,
+                            amountOutMinimum: amount_out,
+                            sqrtPriceLimitX96: sqrt_price_limit_x96,
+                        },
+                    }
+                    .abi_encode()
+                    .into(),
+                    (TradeType::ExactOutput, None) => IV3SwapRouter::exactOutputSingleCall {
+                        params: IV3SwapRouter::ExactOutputSingleParams {
+                            tokenIn: token_in,
+                            tokenOut: token_out,
+                            fee,
+                            recipient,
+                            amountOut: amount_out,
+                            amountInMaximum: amount_in,
+                            sqrtPriceLimitX96: sqrt_price_limit_x96,
+                        },
+                    }
+                    .abi_encode()
+                    .into(),
+                    (TradeType::ExactInput, Some(deadline)) => ISwapRouter::exactInputSingleCall {
+                        params: ISwapRouter::ExactInputSingleParams {
+                            tokenIn: token_in,
+                            tokenOut: token_out,
+                            fee,
+                            recipient,
+                            deadline,
+                            amountIn: amount_in,
+                            amountOutMinimum: amount_out,
+                            sqrtPriceLimitX96: sqrt_price_limit_x96,
+                        },
+                    }
+                    .abi_encode()
+                    .into(),
+                    (TradeType::ExactOutput, Some(deadline)) => {
+                        ISwapRouter::exactOutputSingleCall {
+                            params: ISwapRouter::ExactOutputSingleParams {
+                                tokenIn: token_in,
+                                tokenOut: token_out,
+                                fee,
+                                recipient,
+                                deadline,
+                                amountOut: amount_out,
+                                amountInMaximum: amount_in,
+                                sqrtPriceLimitX96: sqrt_price_limit_x96,
+                            },
+                        }
+                        .abi_encode()
+                        .into()
+                    }
+                });
+            } else {
+                assert!(sqrt_price_limit_x96.is_none(), "MULTIHOP_PRICE_LIMIT");
+
+                let path = encode_route_to_path(route, trade.trade_type == TradeType::ExactOutput);
+
+                calldatas.push(match (trade.trade_type, deadline) {
+                    (TradeType::ExactInput, None) => IV3SwapRouter::exactInputCall {
+                        params: IV3SwapRouter::ExactInputParams {
+                            path,
+                            recipient,
+                            amountIn: amount_in,
+                            amountOutMinimum: amount_out,
+                        },
+                    }
+                    .abi_encode()
+                    .into(),
+                    (TradeType::ExactOutput, None) => IV3SwapRouter::exactOutputCall {
+                        params: IV3SwapRouter::ExactOutputParams {
+                            path,
+                            recipient,
+                            amountOut: amount_out,
+                            amountInMaximum: amount_in,
+                        },
+                    }
+                    .abi_encode()
+                    .into(),
+                    (TradeType::ExactInput, Some(deadline)) => ISwapRouter::exactInputCall {
+                        params: ISwapRouter::ExactInputParams {
+                            path,
+                            recipient,
+                            deadline,
+                            amountIn: amount_in,
+                            amountOutMinimum: amount_out,
+                        },
+                    }
+                    .abi_encode()
+                    .into(),
+                    (TradeType::ExactOutput, Some(deadline)) => ISwapRouter::exactOutputCall {
+                        params: ISwapRouter::ExactOutputParams {
+                            path,
+                            recipient,
+                            deadline,
+                            amountOut: amount_out,
+                            amountInMaximum: amount_in,
+                        },
+                    }
+                    .abi_encode()
+                    .into(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Produces the on-chain method name to call and the hex encoded parameters to pass as arguments
 /// for a given trade on [`SwapRouter02`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/SwapRouter02.sol).
 ///
@@ -34,6 +206,63 @@ pub fn swap_call_parameters<TInput, TOutput, TP>(
     trades: &mut [Trade<TInput, TOutput, TP>],
     options: SwapOptions,
 ) -> Result<MethodParameters, Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    encode_swap_call_parameters(trades, options, None)
+}
+
+/// Produces the calldata for the given trades on the swap router of a DEX, which may be a fork of
+/// Uniswap V3 with a different router ABI.
+///
+/// ## Arguments
+///
+/// * `trades`: trades to produce call parameters for
+/// * `options`: options for the call parameters
+/// * `config`: the configuration of the DEX the trades are executed on
+/// * `deadline`: when the transaction expires, in epoch seconds. Only encoded if the router of
+///   the DEX is [`SwapRouterFlavor::SwapRouter`], as `SwapRouter02` checks the deadline in
+///   `multicall` instead
+///
+/// ## Returns
+///
+/// The call parameters, or [`Error::UnknownFeeTier`] if a pool of the trades has a fee tier that is
+/// not enabled on the DEX
+#[inline]
+pub fn swap_call_parameters_with_config<TInput, TOutput, TP>(
+    trades: &mut [Trade<TInput, TOutput, TP>],
+    options: SwapOptions,
+    config: &DexConfig,
+    deadline: U256,
+) -> Result<MethodParameters, Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+{
+    for trade in trades.iter() {
+        for swap in &trade.swaps {
+            for pool in &swap.route.pools {
+                if !config.supports_fee(pool.fee) {
+                    return Err(Error::UnknownFeeTier(pool.fee.into()));
+                }
+            }
+        }
+    }
+    let deadline = match config.swap_router_flavor {
+        SwapRouterFlavor::SwapRouter02 => None,
+        SwapRouterFlavor::SwapRouter => Some(deadline),
+    };
+    encode_swap_call_parameters(trades, options, deadline)
+}
+
+fn encode_swap_call_parameters<TInput, TOutput, TP>(
+    trades: &mut [Trade<TInput, TOutput, TP>],
+    options: SwapOptions,
+    deadline: Option<U256>,
+) -> Result<MethodParameters, Error>
 where
     TInput: BaseCurrency,
     TOutput: BaseCurrency,
@@ -105,83 +334,14 @@ where
         recipient
     };
 
-    for trade in trades.iter() {
-        for Swap {
-            route,
-            input_amount,
-            output_amount,
-        } in &trade.swaps
-        {
-            let amount_in = U256::from_big_int(
-                trade
-                    .maximum_amount_in(slippage_tolerance.clone(), Some(input_amount.clone()))?
-                    .quotient(),
-            );
-            let amount_out = U256::from_big_int(
-                trade
-                    .minimum_amount_out(slippage_tolerance.clone(), Some(output_amount.clone()))?
-                    .quotient(),
-            );
-
-            if route.pools.len() == 1 {
-                calldatas.push(match trade.trade_type {
-                    TradeType::ExactInput => IV3SwapRouter::exactInputSingleCall {
-                        params: IV3SwapRouter::ExactInputSingleParams {
-                            tokenIn: route.input.wrapped().address(),
-                            tokenOut: route.output.wrapped().address(),
-                            fee: route.pools[0].fee.into(),
-                            recipient: intermediate_recipient,
-                            amountIn: amount_in,
-                            amountOutMinimum: amount_out,
-                            sqrtPriceLimitX96: sqrt_price_limit_x96.unwrap_or_default(),
-                        },
-                    }
-                    .abi_encode()
-                    .into(),
-                    TradeType::ExactOutput => IV3SwapRouter::exactOutputSingleCall {
-                        params: IV3SwapRouter::ExactOutputSingleParams {
-                            tokenIn: route.input.wrapped().address(),
-                            tokenOut: route.output.wrapped().address(),
-                            fee: route.pools[0].fee.into(),
-                            recipient: intermediate_recipient,
-                            amountOut: amount_out,
-                            amountInMaximum: amount_in,
-                            sqrtPriceLimitX96: sqrt_price_limit_x96.unwrap_or_default(),
-                        },
-                    }
-                    .abi_encode()
-                    .into(),
-                });
-            } else {
-                assert!(sqrt_price_limit_x96.is_none(), "MULTIHOP_PRICE_LIMIT");
-
-                let path = encode_route_to_path(route, trade.trade_type == TradeType::ExactOutput);
-
-                calldatas.push(match trade.trade_type {
-                    TradeType::ExactInput => IV3SwapRouter::exactInputCall {
-                        params: IV3SwapRouter::ExactInputParams {
-                            path,
-                            recipient: intermediate_recipient,
-                            amountIn: amount_in,
-                            amountOutMinimum: amount_out,
-                        },
-                    }
-                    .abi_encode()
-                    .into(),
-                    TradeType::ExactOutput => IV3SwapRouter::exactOutputCall {
-                        params: IV3SwapRouter::ExactOutputParams {
-                            path,
-                            recipient: intermediate_recipient,
-                            amountOut: amount_out,
-                            amountInMaximum: amount_in,
-                        },
-                    }
-                    .abi_encode()
-                    .into(),
-                });
-            }
-        }
-    }
+    encode_swaps(
+        trades,
+        &slippage_tolerance,
+        intermediate_recipient,
+        sqrt_price_limit_x96,
+        deadline,
+        &mut calldatas,
+    )?;
 
     // unwrap
     if router_must_custody {
@@ -208,6 +368,189 @@ where
     })
 }
 
+/// Produces the calldata for swapping part of the input token into the output token and adding
+/// liquidity to a position with both in a single multicall on
+/// [`SwapRouter02`](https://github.com/Uniswap/swap-router-contracts/blob/main/contracts/base/ApproveAndCall.sol).
+///
+/// The swap output is kept by the router, the rest of the position amounts are pulled from the
+/// sender, the router approves the position manager and any remaining tokens are swept back to
+/// the sender.
+///
+/// ## Arguments
+///
+/// * `trades`: trades to produce call parameters for
+/// * `options`: options for the call parameters
+/// * `position`: the position to add liquidity to, in the pool state expected after the swap
+/// * `add_liquidity_options`: whether to mint a new position or increase an existing one
+#[inline]
+pub fn swap_and_add_call_parameters<TInput, TOutput, TP, TP2>(
+    trades: &mut [Trade<TInput, TOutput, TP>],
+    options: SwapAndAddOptions,
+    position: &mut Position<TP2>,
+    add_liquidity_options: CondensedAddLiquidityOptions,
+) -> Result<MethodParameters, Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+    TP2: Clone + TickDataProvider,
+{
+    let SwapAndAddOptions {
+        slippage_tolerance,
+        add_liquidity_slippage_tolerance,
+        input_token_permit,
+        output_token_permit,
+        sqrt_price_limit_x96,
+        token_in_approval_type,
+        token_out_approval_type,
+    } = options;
+    let sample_trade = &trades[0];
+    let input_is_native = sample_trade.input_currency().is_native();
+    let output_is_native = sample_trade.output_currency().is_native();
+    let token_in = sample_trade.input_currency().wrapped().clone();
+    let token_out = sample_trade.output_currency().wrapped().clone();
+
+    // All trades should have the same starting and ending token.
+    for trade in trades.iter() {
+        assert!(
+            trade.input_currency().wrapped().equals(&token_in),
+            "TOKEN_IN_DIFF"
+        );
+        assert!(
+            trade.output_currency().wrapped().equals(&token_out),
+            "TOKEN_OUT_DIFF"
+        );
+    }
+    assert!(
+        position.pool.involves_token(&token_in) && position.pool.involves_token(&token_out),
+        "POSITION_TOKENS"
+    );
+
+    let num_swaps = trades.iter().map(|trade| trade.swaps.len()).sum::<usize>();
+
+    let mut calldatas: Vec<Bytes> = Vec::with_capacity(num_swaps + 9);
+
+    // encode permit if necessary
+    if let Some(input_token_permit) = input_token_permit {
+        assert!(!input_is_native, "NON_TOKEN_PERMIT");
+        calldatas.push(encode_permit(&token_in, input_token_permit));
+    }
+
+    // the router keeps the swap output for adding liquidity
+    encode_swaps(
+        trades,
+        &slippage_tolerance,
+        ADDRESS_THIS,
+        sqrt_price_limit_x96,
+        None,
+        &mut calldatas,
+    )?;
+
+    // encode output token permit if necessary
+    if let Some(output_token_permit) = output_token_permit {
+        assert!(!output_is_native, "NON_TOKEN_PERMIT");
+        calldatas.push(encode_permit(&token_out, output_token_permit));
+    }
+
+    let mut total_amount_in = BigInt::ZERO;
+    let mut quote_amount_out = BigInt::ZERO;
+    let mut minimum_amount_out = BigInt::ZERO;
+    for trade in trades.iter_mut() {
+        total_amount_in += trade
+            .maximum_amount_in_cached(slippage_tolerance.clone(), None)?
+            .quotient();
+        quote_amount_out += trade.output_amount_cached()?.quotient();
+        minimum_amount_out += trade
+            .minimum_amount_out_cached(slippage_tolerance.clone(), None)?
+            .quotient();
+    }
+    let quote_amount_out = U256::from_big_int(quote_amount_out);
+    let minimum_amount_out = U256::from_big_int(minimum_amount_out);
+
+    let zero_for_one = position.pool.token0.equals(&token_in);
+    let MintAmounts { amount0, amount1 } = position.mint_amounts_cached()?;
+    let (position_amount_in, position_amount_out) = if zero_for_one {
+        (amount0, amount1)
+    } else {
+        (amount1, amount0)
+    };
+
+    // if the swap output does not make up the whole position amount, pull in the remainder, or
+    // wrap it if it is sent as native value
+    let amount_out_remaining = position_amount_out.saturating_sub(quote_amount_out);
+    if !amount_out_remaining.is_zero() {
+        calldatas.push(if output_is_native {
+            encode_wrap_eth(amount_out_remaining)
+        } else {
+            encode_pull(token_out.address(), amount_out_remaining)
+        });
+    }
+    calldatas.push(if input_is_native {
+        encode_wrap_eth(position_amount_in)
+    } else {
+        encode_pull(token_in.address(), position_amount_in)
+    });
+
+    // approve token balances to the position manager
+    if token_in_approval_type != ApprovalType::NotRequired {
+        calldatas.push(encode_approve(token_in.address(), token_in_approval_type)?);
+    }
+    if token_out_approval_type != ApprovalType::NotRequired {
+        calldatas.push(encode_approve(
+            token_out.address(),
+            token_out_approval_type,
+        )?);
+    }
+
+    // the position resulting from the swap with maximum slippage, hence the minimal amount out
+    let position_amount0 = U256::from_big_int(position.amount0_cached()?.quotient());
+    let position_amount1 = U256::from_big_int(position.amount1_cached()?.quotient());
+    let mut minimal_position = Position::from_amounts(
+        position.pool.clone(),
+        position.tick_lower,
+        position.tick_upper,
+        if zero_for_one {
+            position_amount0
+        } else {
+            minimum_amount_out
+        },
+        if zero_for_one {
+            minimum_amount_out
+        } else {
+            position_amount1
+        },
+        false,
+    )?;
+    calldatas.push(encode_add_liquidity(
+        position,
+        &mut minimal_position,
+        add_liquidity_options,
+        &add_liquidity_slippage_tolerance,
+    )?);
+
+    // sweep remaining tokens
+    for (token, is_native) in [(&token_in, input_is_native), (&token_out, output_is_native)] {
+        calldatas.push(if is_native {
+            encode_unwrap_weth9_to_sender(U256::ZERO)
+        } else {
+            encode_sweep_token_to_sender(token.address(), U256::ZERO)
+        });
+    }
+
+    let value = if input_is_native {
+        U256::from_big_int(total_amount_in) + position_amount_in
+    } else if output_is_native {
+        amount_out_remaining
+    } else {
+        U256::ZERO
+    };
+
+    Ok(MethodParameters {
+        calldata: encode_multicall(calldatas),
+        value,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1085,4 +1428,197 @@ mod tests {
             assert_eq!(value, U256::ZERO);
         }
     }
+
+    mod swap_and_add {
+        use super::*;
+
+        static SWAP_AND_ADD_OPTIONS: Lazy<SwapAndAddOptions> = Lazy::new(|| SwapAndAddOptions {
+            slippage_tolerance: SLIPPAGE_TOLERANCE.clone(),
+            add_liquidity_slippage_tolerance: SLIPPAGE_TOLERANCE.clone(),
+            input_token_permit: None,
+            output_token_permit: None,
+            sqrt_price_limit_x96: None,
+            token_in_approval_type: ApprovalType::Max,
+            token_out_approval_type: ApprovalType::Max,
+        });
+
+        fn selectors(calldata: &Bytes) -> Vec<[u8; 4]> {
+            decode_multicall::<Bytes, _>(calldata)
+                .unwrap()
+                .iter()
+                .map(|call| call[..4].try_into().unwrap())
+                .collect()
+        }
+
+        #[test]
+        fn swap_and_mint() {
+            let trade = Trade::from_route(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let mut position = Position::from_amounts(
+                POOL_0_1.clone(),
+                -60,
+                60,
+                uint!(200_U256),
+                uint!(200_U256),
+                false,
+            )
+            .unwrap();
+            let MethodParameters { calldata, value } = swap_and_add_call_parameters(
+                &mut [trade],
+                SWAP_AND_ADD_OPTIONS.clone(),
+                &mut position,
+                CondensedAddLiquidityOptions::Mint {
+                    recipient: RECIPIENT,
+                },
+            )
+            .unwrap();
+            assert_eq!(value, U256::ZERO);
+            assert_eq!(
+                selectors(&calldata),
+                [
+                    IV3SwapRouter::exactInputSingleCall::SELECTOR,
+                    IPeripheryPaymentsExtended::pullCall::SELECTOR,
+                    IPeripheryPaymentsExtended::pullCall::SELECTOR,
+                    IApproveAndCall::approveMaxCall::SELECTOR,
+                    IApproveAndCall::approveMaxCall::SELECTOR,
+                    IApproveAndCall::mintCall::SELECTOR,
+                    IPeripheryPaymentsExtended::sweepTokenCall::SELECTOR,
+                    IPeripheryPaymentsExtended::sweepTokenCall::SELECTOR,
+                ]
+            );
+            let calls = decode_multicall::<Bytes, _>(&calldata).unwrap();
+            let swap = IV3SwapRouter::exactInputSingleCall::abi_decode(&calls[0], true)
+                .unwrap()
+                .params;
+            assert_eq!(swap.recipient, ADDRESS_THIS);
+            let pull_out =
+                IPeripheryPaymentsExtended::pullCall::abi_decode(&calls[1], true).unwrap();
+            assert_eq!(pull_out.token, TOKEN1.address());
+            let pull_in =
+                IPeripheryPaymentsExtended::pullCall::abi_decode(&calls[2], true).unwrap();
+            assert_eq!(pull_in.token, TOKEN0.address());
+            assert_eq!(pull_in.value, position.mint_amounts().unwrap().amount0);
+            let mint = IApproveAndCall::mintCall::abi_decode(&calls[5], true)
+                .unwrap()
+                .params;
+            assert_eq!(mint.recipient, RECIPIENT);
+            assert_eq!(mint.tickLower.as_i32(), -60);
+            assert_eq!(mint.tickUpper.as_i32(), 60);
+        }
+
+        #[test]
+        fn eth_in_swap_and_increase() {
+            let trade = Trade::from_route(
+                Route::new(vec![POOL_1_WETH.clone()], ETHER.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(ETHER.clone(), 100).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap();
+            let mut position = Position::from_amounts(
+                POOL_1_WETH.clone(),
+                -60,
+                60,
+                uint!(200_U256),
+                uint!(200_U256),
+                false,
+            )
+            .unwrap();
+            let MethodParameters { calldata, value } = swap_and_add_call_parameters(
+                &mut [trade],
+                SwapAndAddOptions {
+                    token_in_approval_type: ApprovalType::NotRequired,
+                    ..SWAP_AND_ADD_OPTIONS.clone()
+                },
+                &mut position,
+                CondensedAddLiquidityOptions::Increase {
+                    token_id: uint!(1_U256),
+                },
+            )
+            .unwrap();
+            let weth_in_position = if position.pool.token0.equals(&*WETH) {
+                position.mint_amounts().unwrap().amount0
+            } else {
+                position.mint_amounts().unwrap().amount1
+            };
+            assert_eq!(value, uint!(100_U256) + weth_in_position);
+            let selectors = selectors(&calldata);
+            assert_eq!(selectors[0], IV3SwapRouter::exactInputSingleCall::SELECTOR);
+            assert!(selectors.contains(&IPeripheryPaymentsExtended::wrapETHCall::SELECTOR));
+            assert!(selectors.contains(&IApproveAndCall::increaseLiquidityCall::SELECTOR));
+            assert_eq!(
+                selectors[selectors.len() - 2],
+                IPeripheryPaymentsExtended::unwrapWETH9Call::SELECTOR
+            );
+        }
+    }
+
+    mod with_config {
+        use super::*;
+
+        const DEADLINE: U256 = uint!(123_U256);
+
+        fn single_hop_trade() -> Trade<Token, Token, TickListDataProvider> {
+            Trade::from_route(
+                Route::new(vec![POOL_0_1.clone()], TOKEN0.clone(), TOKEN1.clone()),
+                CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+                TradeType::ExactInput,
+            )
+            .unwrap()
+        }
+
+        #[test]
+        fn swap_router02_matches_swap_call_parameters() {
+            let config = DexConfig::uniswap(1).unwrap();
+            assert_eq!(
+                swap_call_parameters_with_config(
+                    &mut [single_hop_trade()],
+                    SWAP_OPTIONS.clone(),
+                    &config,
+                    DEADLINE,
+                )
+                .unwrap(),
+                swap_call_parameters(&mut [single_hop_trade()], SWAP_OPTIONS.clone()).unwrap()
+            );
+        }
+
+        #[test]
+        fn swap_router_encodes_deadline() {
+            let config = DexConfig {
+                swap_router_flavor: SwapRouterFlavor::SwapRouter,
+                ..DexConfig::uniswap(1).unwrap()
+            };
+            let MethodParameters { calldata, .. } = swap_call_parameters_with_config(
+                &mut [single_hop_trade()],
+                SWAP_OPTIONS.clone(),
+                &config,
+                DEADLINE,
+            )
+            .unwrap();
+            let params = ISwapRouter::exactInputSingleCall::abi_decode(&calldata, true)
+                .unwrap()
+                .params;
+            assert_eq!(params.tokenIn, TOKEN0.address());
+            assert_eq!(params.recipient, RECIPIENT);
+            assert_eq!(params.deadline, DEADLINE);
+            assert_eq!(params.amountIn, uint!(100_U256));
+            assert_eq!(params.amountOutMinimum, uint!(97_U256));
+        }
+
+        #[test]
+        fn unsupported_fee_tier() {
+            assert!(matches!(
+                swap_call_parameters_with_config(
+                    &mut [single_hop_trade()],
+                    SWAP_OPTIONS.clone(),
+                    &DexConfig::pancakeswap(1),
+                    DEADLINE,
+                ),
+                Err(Error::UnknownFeeTier(_))
+            ));
+        }
+    }
 }
@@ -1,7 +1,7 @@
-use crate::prelude::*;
-use alloy_primitives::{aliases::U24, Bytes};
+use crate::prelude::{Error, *};
+use alloy_primitives::{aliases::U24, Address, Bytes};
 use alloy_sol_types::SolValue;
-use uniswap_sdk_core::prelude::*;
+use uniswap_sdk_core::{error::Error as CoreError, prelude::*};
 
 #[inline]
 fn encode_leg<'a, TP: TickDataProvider>(
@@ -56,6 +56,117 @@ where
     path.into()
 }
 
+const ADDRESS_LENGTH: usize = 20;
+const FEE_LENGTH: usize = 3;
+const HOP_LENGTH: usize = ADDRESS_LENGTH + FEE_LENGTH;
+
+/// Decodes a packed path into its tokens and the raw fees of the hops between them, the inverse
+/// of [`encode_route_to_path`].
+///
+/// The fees are returned as encoded, since the tick spacing of a fee tier depends on the factory
+/// of the pool, e.g. resolve them with [`get_fee_amount`].
+///
+/// ## Arguments
+///
+/// * `path`: The packed path, in the order of the swap for exact input paths or in reverse for
+///   exact output paths
+///
+/// ## Returns
+///
+/// The token addresses in the order of the path and the fee of every hop, or
+/// [`Error::InvalidPathLength`] if the path is not a token followed by one or more hops, or
+/// [`Error::InvalidFee`] if a fee is not less than 100%.
+#[inline]
+pub fn decode_path(path: &[u8]) -> Result<(Vec<Address>, Vec<U24>), Error> {
+    if path.len() < ADDRESS_LENGTH + HOP_LENGTH
+        || !(path.len() - ADDRESS_LENGTH).is_multiple_of(HOP_LENGTH)
+    {
+        return Err(Error::InvalidPathLength(path.len()));
+    }
+    let hops = (path.len() - ADDRESS_LENGTH) / HOP_LENGTH;
+    let mut tokens = Vec::with_capacity(hops + 1);
+    let mut fees = Vec::with_capacity(hops);
+    for hop in path[..path.len() - ADDRESS_LENGTH].chunks_exact(HOP_LENGTH) {
+        let (token, fee) = hop.split_at(ADDRESS_LENGTH);
+        let fee = U24::from_be_slice(fee);
+        if fee >= U24::from_limbs([1_000_000]) {
+            return Err(Error::InvalidFee(fee));
+        }
+        tokens.push(Address::from_slice(token));
+        fees.push(fee);
+    }
+    tokens.push(Address::from_slice(&path[path.len() - ADDRESS_LENGTH..]));
+    Ok((tokens, fees))
+}
+
+/// Decodes a packed path into a route, looking up the pool of every hop.
+///
+/// ## Arguments
+///
+/// * `path`: The packed path
+/// * `exact_output`: Whether the path is encoded in reverse, as for exact output swaps
+/// * `input`: The input currency of the route, whose wrapped token must begin the swap
+/// * `output`: The output currency of the route, whose wrapped token must end the swap
+/// * `get_pool`: Returns the pool of a hop given its input token, output token and raw fee, in the
+///   order of the swap
+///
+/// ## Returns
+///
+/// The route through the pools in the order of the swap, [`Error::InvalidToken`] if the
+/// currencies or the pools do not match the path, or a chain id mismatch if they are not all on
+/// the chain of the input currency.
+#[inline]
+pub fn decode_route<TInput, TOutput, TP, F>(
+    path: &[u8],
+    exact_output: bool,
+    input: TInput,
+    output: TOutput,
+    mut get_pool: F,
+) -> Result<Route<TInput, TOutput, TP>, Error>
+where
+    TInput: BaseCurrency,
+    TOutput: BaseCurrency,
+    TP: TickDataProvider,
+    F: FnMut(Address, Address, U24) -> Result<Pool<TP>, Error>,
+{
+    let (mut tokens, mut fees) = decode_path(path)?;
+    if exact_output {
+        tokens.reverse();
+        fees.reverse();
+    }
+    if tokens[0] != input.wrapped().address()
+        || tokens[tokens.len() - 1] != output.wrapped().address()
+    {
+        return Err(Error::InvalidToken);
+    }
+    let chain_id = input.chain_id();
+    let check_chain_id = |other: u64| {
+        if other == chain_id {
+            Ok(())
+        } else {
+            Err(Error::Core(CoreError::ChainIdMismatch(chain_id, other)))
+        }
+    };
+    check_chain_id(output.chain_id())?;
+    let pools = tokens
+        .windows(2)
+        .zip(fees)
+        .map(|(pair, fee)| {
+            let pool = get_pool(pair[0], pair[1], fee)?;
+            check_chain_id(pool.chain_id())?;
+            let involves = |address: Address| {
+                pool.token0.address() == address || pool.token1.address() == address
+            };
+            if involves(pair[0]) && involves(pair[1]) && pair[0] != pair[1] {
+                Ok(pool)
+            } else {
+                Err(Error::InvalidToken)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Route::new(pools, input, output))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +309,165 @@ mod tests {
             hex!("c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2000bb80000000000000000000000000000000000000002000bb80000000000000000000000000000000000000001")
         );
     }
+
+    fn get_pool(token_in: Address, token_out: Address, fee: U24) -> Result<Pool, Error> {
+        [&*POOL_0_1, &*POOL_1_2_LOW, &*POOL_0_WETH, &*POOL_1_WETH]
+            .into_iter()
+            .find(|pool| {
+                Into::<U24>::into(pool.fee) == fee
+                    && (pool.token0.address, pool.token1.address) == sorted(token_in, token_out)
+            })
+            .cloned()
+            .ok_or(Error::InvalidToken)
+    }
+
+    fn assert_route<TInput, TOutput>(
+        route: &Route<TInput, TOutput, NoTickDataProvider>,
+        expected: &Route<TInput, TOutput, NoTickDataProvider>,
+    ) where
+        TInput: BaseCurrency,
+        TOutput: BaseCurrency,
+    {
+        assert!(route.input.equals(&expected.input));
+        assert!(route.output.equals(&expected.output));
+        assert_eq!(
+            route
+                .pools
+                .iter()
+                .map(|pool| pool.address(None, None))
+                .collect::<Vec<_>>(),
+            expected
+                .pools
+                .iter()
+                .map(|pool| pool.address(None, None))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    fn sorted(a: Address, b: Address) -> (Address, Address) {
+        if a < b {
+            (a, b)
+        } else {
+            (b, a)
+        }
+    }
+
+    #[test]
+    fn decode_exact_input_multihop() {
+        let path = encode_route_to_path(&ROUTE_0_1_2, false);
+        assert_eq!(
+            decode_path(&path).unwrap(),
+            (
+                vec![TOKEN0.address, TOKEN1.address, TOKEN2.address],
+                vec![FeeAmount::MEDIUM.into(), FeeAmount::LOW.into()]
+            )
+        );
+    }
+
+    #[test]
+    fn decode_exact_output_multihop() {
+        let path = encode_route_to_path(&ROUTE_0_1_2, true);
+        assert_eq!(
+            decode_path(&path).unwrap(),
+            (
+                vec![TOKEN2.address, TOKEN1.address, TOKEN0.address],
+                vec![FeeAmount::LOW.into(), FeeAmount::MEDIUM.into()]
+            )
+        );
+    }
+
+    #[test]
+    fn decode_custom_fee() {
+        let path = hex!("0000000000000000000000000000000000000001000064000000000000000000000000000000000000000200009c0000000000000000000000000000000000000003");
+        // the tick spacing of a fee which is not a default fee tier is left to the caller
+        assert_eq!(
+            decode_path(&path).unwrap().1,
+            vec![U24::from_limbs([100]), U24::from_limbs([156])]
+        );
+    }
+
+    #[test]
+    fn decode_invalid_length() {
+        let path = encode_route_to_path(&ROUTE_0_1, false);
+        assert!(matches!(
+            decode_path(&path[..path.len() - 1]),
+            Err(Error::InvalidPathLength(42))
+        ));
+        assert!(matches!(
+            decode_path(&path[..20]),
+            Err(Error::InvalidPathLength(20))
+        ));
+        assert!(matches!(decode_path(&[]), Err(Error::InvalidPathLength(0))));
+    }
+
+    #[test]
+    fn decode_invalid_fee() {
+        let path = hex!("00000000000000000000000000000000000000010f42400000000000000000000000000000000000000002");
+        assert!(matches!(
+            decode_path(&path),
+            Err(Error::InvalidFee(fee)) if fee == U24::from_limbs([1_000_000])
+        ));
+    }
+
+    #[test]
+    fn decode_route_exact_input() {
+        let path = encode_route_to_path(&ROUTE_0_1_2, false);
+        let route = decode_route(&path, false, TOKEN0.clone(), TOKEN2.clone(), get_pool).unwrap();
+        assert_route(&route, &ROUTE_0_1_2);
+    }
+
+    #[test]
+    fn decode_route_exact_output() {
+        let path = encode_route_to_path(&ROUTE_0_1_2, true);
+        let route = decode_route(&path, true, TOKEN0.clone(), TOKEN2.clone(), get_pool).unwrap();
+        assert_route(&route, &ROUTE_0_1_2);
+    }
+
+    #[test]
+    fn decode_route_wrapped_ether() {
+        let path = encode_route_to_path(&ROUTE_WETH_0_1, true);
+        let route = decode_route(&path, true, ETHER.clone(), TOKEN1.clone(), get_pool).unwrap();
+        assert_route(&route, &ROUTE_WETH_0_1);
+        assert_eq!(encode_route_to_path(&route, true), path);
+    }
+
+    #[test]
+    fn decode_route_mismatched_currencies() {
+        let path = encode_route_to_path(&ROUTE_0_1_2, false);
+        assert!(matches!(
+            decode_route(&path, true, TOKEN0.clone(), TOKEN2.clone(), get_pool),
+            Err(Error::InvalidToken)
+        ));
+        assert!(matches!(
+            decode_route(&path, false, TOKEN0.clone(), TOKEN2.clone(), |_, _, _| Ok(
+                POOL_0_WETH.clone()
+            )),
+            Err(Error::InvalidToken)
+        ));
+    }
+
+    #[test]
+    fn decode_route_mismatched_chain_ids() {
+        let path = encode_route_to_path(&ROUTE_0_1, false);
+        let token1 =
+            uniswap_sdk_core::token!(2, "0000000000000000000000000000000000000002", 18, "t1");
+        assert!(matches!(
+            decode_route(&path, false, TOKEN0.clone(), token1.clone(), get_pool),
+            Err(Error::Core(CoreError::ChainIdMismatch(1, 2)))
+        ));
+        let pool = Pool::new(
+            uniswap_sdk_core::token!(2, "0000000000000000000000000000000000000001", 18, "t0"),
+            token1,
+            FeeAmount::MEDIUM,
+            encode_sqrt_ratio_x96(1, 1),
+            0,
+        )
+        .unwrap();
+        assert!(matches!(
+            decode_route(&path, false, TOKEN0.clone(), TOKEN1.clone(), |_, _, _| Ok(
+                pool.clone()
+            )),
+            Err(Error::Core(CoreError::ChainIdMismatch(1, 2)))
+        ));
+    }
 }
@@ -16,7 +16,7 @@ pub mod tick_math;
 
 pub use bit_math::*;
 pub use compute_pool_address::compute_pool_address;
-pub use encode_route_to_path::encode_route_to_path;
+pub use encode_route_to_path::{decode_path, decode_route, encode_route_to_path};
 pub use encode_sqrt_ratio_x96::encode_sqrt_ratio_x96;
 pub use full_math::*;
 pub use get_fee_growth_inside::*;
@@ -153,6 +153,18 @@ pub fn compute_swap_step<const BITS: usize, const LIMBS: usize>(
     Ok((sqrt_ratio_next_x96, amount_in, amount_out, fee_amount))
 }
 
+/// A step of a swap within a single word of the tick bitmap, as passed to the callback of
+/// [`v3_swap_with_callback`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SwapStep<I = i32> {
+    /// The liquidity in range during the step
+    pub liquidity: u128,
+    /// The fee taken from the input amount during the step
+    pub fee_amount: U256,
+    /// The initialized tick crossed at the end of the step, if any
+    pub crossed_tick: Option<I>,
+}
+
 #[inline]
 #[allow(clippy::too_many_arguments)]
 pub fn v3_swap<TP: TickDataProvider>(
@@ -165,6 +177,36 @@ pub fn v3_swap<TP: TickDataProvider>(
     zero_for_one: bool,
     amount_specified: I256,
     sqrt_price_limit_x96: Option<U160>,
+) -> Result<SwapState<TP::Index>, Error> {
+    v3_swap_with_callback(
+        fee,
+        sqrt_price_x96,
+        tick_current,
+        liquidity,
+        tick_spacing,
+        tick_data_provider,
+        zero_for_one,
+        amount_specified,
+        sqrt_price_limit_x96,
+        |_| Ok(()),
+    )
+}
+
+/// Simulates a swap like [`v3_swap`], calling `on_step` after every step before the liquidity
+/// of a crossed tick is applied, e.g. to accrue fee growth, and stopping at its first error
+#[inline]
+#[allow(clippy::too_many_arguments)]
+pub fn v3_swap_with_callback<TP: TickDataProvider>(
+    fee: U24,
+    sqrt_price_x96: U160,
+    tick_current: TP::Index,
+    liquidity: u128,
+    tick_spacing: TP::Index,
+    tick_data_provider: &TP,
+    zero_for_one: bool,
+    amount_specified: I256,
+    sqrt_price_limit_x96: Option<U160>,
+    mut on_step: impl FnMut(SwapStep<TP::Index>) -> Result<(), Error>,
 ) -> Result<SwapState<TP::Index>, Error> {
     let sqrt_price_limit_x96 = sqrt_price_limit_x96.unwrap_or(if zero_for_one {
         MIN_SQRT_RATIO + ONE
@@ -199,19 +241,26 @@ pub fn v3_swap<TP: TickDataProvider>(
             sqrt_price_start_x96: state.sqrt_price_x96,
             ..Default::default()
         };
+        // attach the tick at which a step fails to the error
+        let tick_current = state.tick_current.to_i24();
+        let with_tick = |e: Error| {
+            e.with_swap_context(SwapErrorContext {
+                zero_for_one,
+                tick: Some(tick_current),
+                ..Default::default()
+            })
+        };
 
         // because each iteration of the while loop rounds, we can't optimize this code
         // (relative to the smart contract) by simply traversing to the next available tick, we
         // instead need to exactly replicate
         (step.tick_next, step.initialized) = tick_data_provider
-            .next_initialized_tick_within_one_word(
-                state.tick_current,
-                zero_for_one,
-                tick_spacing,
-            )?;
+            .next_initialized_tick_within_one_word(state.tick_current, zero_for_one, tick_spacing)
+            .map_err(with_tick)?;
 
         step.tick_next = TP::Index::from_i24(step.tick_next.to_i24().clamp(MIN_TICK, MAX_TICK));
-        step.sqrt_price_next_x96 = get_sqrt_ratio_at_tick(step.tick_next.to_i24())?;
+        step.sqrt_price_next_x96 =
+            get_sqrt_ratio_at_tick(step.tick_next.to_i24()).map_err(with_tick)?;
 
         (
             state.sqrt_price_x96,
@@ -228,7 +277,8 @@ pub fn v3_swap<TP: TickDataProvider>(
             state.liquidity,
             state.amount_specified_remaining,
             fee,
-        )?;
+        )
+        .map_err(with_tick)?;
 
         if exact_input {
             state.amount_specified_remaining = I256::from_raw(
@@ -244,16 +294,26 @@ pub fn v3_swap<TP: TickDataProvider>(
             );
         }
 
-        if state.sqrt_price_x96 == step.sqrt_price_next_x96 {
+        let crossed = state.sqrt_price_x96 == step.sqrt_price_next_x96;
+        on_step(SwapStep {
+            liquidity: state.liquidity,
+            fee_amount: step.fee_amount,
+            crossed_tick: (crossed && step.initialized).then_some(step.tick_next),
+        })?;
+
+        if crossed {
             // if the tick is initialized, run the tick transition
             if step.initialized {
-                let mut liquidity_net = tick_data_provider.get_tick(step.tick_next)?.liquidity_net;
+                let mut liquidity_net = tick_data_provider
+                    .get_tick(step.tick_next)
+                    .map_err(with_tick)?
+                    .liquidity_net;
                 // if we're moving leftward, we interpret liquidityNet as the opposite sign
                 // safe because liquidityNet cannot be type(int128).min
                 if zero_for_one {
                     liquidity_net = -liquidity_net;
                 }
-                state.liquidity = add_delta(state.liquidity, liquidity_net)?;
+                state.liquidity = add_delta(state.liquidity, liquidity_net).map_err(with_tick)?;
             }
             state.tick_current = if zero_for_one {
                 step.tick_next - TP::Index::ONE
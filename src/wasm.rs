@@ -0,0 +1,651 @@
+//! ## WebAssembly
+//! This module exports the tick math, pool quoting, position amounts and calldata builders of the
+//! core library with `wasm-bindgen`. Pools, positions and options are passed as JSON with
+//! `camelCase` keys, and integers cross the boundary as decimal or `0x` prefixed hex strings.
+
+use crate::prelude::{Error, *};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use alloy_primitives::{aliases::U24, Address, I128, U128, U160, U256};
+use core::{fmt::Display, str::FromStr};
+use serde::{Deserialize, Serialize};
+use uniswap_sdk_core::prelude::*;
+use wasm_bindgen::prelude::*;
+
+type Result<T, E = String> = core::result::Result<T, E>;
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenState {
+    address: Address,
+    decimals: u8,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TickState {
+    index: i32,
+    liquidity_gross: U128,
+    liquidity_net: I128,
+}
+
+/// The state of a pool with its initialized ticks
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PoolState {
+    chain_id: u64,
+    token0: TokenState,
+    token1: TokenState,
+    fee: u32,
+    /// The tick spacing, defaulting to that of the fee tier
+    tick_spacing: Option<i32>,
+    sqrt_price_x96: U160,
+    liquidity: U128,
+    #[serde(default)]
+    ticks: Vec<TickState>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionState {
+    pool: PoolState,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: U128,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum TradeKind {
+    ExactInput,
+    ExactOutput,
+}
+
+/// A trade through a single route
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TradeState {
+    pools: Vec<PoolState>,
+    token_in: Address,
+    token_out: Address,
+    /// The input amount of an exact input trade or the output amount of an exact output trade
+    amount: U256,
+    trade_type: TradeKind,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwapOptionsState {
+    slippage_tolerance_bips: u32,
+    recipient: Address,
+    sqrt_price_limit_x96: Option<U160>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AddLiquidityOptionsState {
+    slippage_tolerance_bips: u32,
+    deadline: U256,
+    /// The recipient of a new position, mutually exclusive with `token_id`
+    recipient: Option<Address>,
+    /// The token id of an existing position to increase
+    token_id: Option<U256>,
+    #[serde(default)]
+    create_pool: bool,
+    /// Whether to pay with ether, if one of the pool tokens is the wrapped native token
+    #[serde(default)]
+    use_native: bool,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuoteResult {
+    amount: String,
+    sqrt_price_x96_after: String,
+    tick_after: i32,
+    liquidity_after: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionAmounts {
+    liquidity: String,
+    amount0: String,
+    amount1: String,
+    mint_amount0: String,
+    mint_amount1: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CallParameters {
+    calldata: String,
+    value: String,
+}
+
+impl From<MethodParameters> for CallParameters {
+    fn from(MethodParameters { calldata, value }: MethodParameters) -> Self {
+        Self {
+            calldata: calldata.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+fn to_string(e: impl Display) -> String {
+    e.to_string()
+}
+
+fn parse<T>(value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value.parse().map_err(to_string)
+}
+
+fn from_json<'a, T: Deserialize<'a>>(json: &'a str) -> Result<T> {
+    serde_json::from_str(json).map_err(to_string)
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<String> {
+    serde_json::to_string(value).map_err(to_string)
+}
+
+impl PoolState {
+    const fn token(&self, token: &TokenState) -> Token {
+        Token::new(
+            self.chain_id,
+            token.address,
+            token.decimals,
+            None,
+            None,
+            0,
+            0,
+        )
+    }
+
+    fn into_pool(self) -> Result<Pool<TickListDataProvider>> {
+        let fee = match self.tick_spacing {
+            Some(tick_spacing) if tick_spacing > 0 => FeeAmount::new_custom(self.fee, tick_spacing),
+            Some(tick_spacing) => return Err(format!("invalid tick spacing: {tick_spacing}")),
+            None => get_fee_amount(self.chain_id, U24::from(self.fee)).map_err(to_string)?,
+        };
+        let ticks = self
+            .ticks
+            .iter()
+            .map(|tick| {
+                Ok(Tick::new(
+                    tick.index,
+                    tick.liquidity_gross.to(),
+                    tick.liquidity_net.try_into().map_err(to_string)?,
+                ))
+            })
+            .collect::<Result<_>>()?;
+        Pool::new_with_tick_data_provider(
+            self.token(&self.token0),
+            self.token(&self.token1),
+            fee,
+            self.sqrt_price_x96,
+            self.liquidity.to(),
+            TickListDataProvider::new(ticks, fee.tick_spacing().as_i32()),
+        )
+        .map_err(to_string)
+    }
+}
+
+/// Returns the pool token at `address`.
+fn pool_token(pool: &Pool<TickListDataProvider>, address: Address) -> Result<Token> {
+    if address == pool.token0.address {
+        Ok(pool.token0.clone())
+    } else if address == pool.token1.address {
+        Ok(pool.token1.clone())
+    } else {
+        Err(Error::InvalidToken.to_string())
+    }
+}
+
+fn quote(pool: &str, token: &str, amount: &str, exact_input: bool) -> Result<String> {
+    let mut pool = from_json::<PoolState>(pool)?.into_pool()?;
+    let token = pool_token(&pool, parse(token)?)?;
+    let amount = CurrencyAmount::from_raw_amount(token, parse::<U256>(amount)?.to_big_int())
+        .map_err(to_string)?;
+    let amount = if exact_input {
+        pool.get_output_amount_mut(&amount, None)
+    } else {
+        pool.get_input_amount_mut(&amount, None)
+    }
+    .map_err(to_string)?;
+    to_json(&QuoteResult {
+        amount: amount.quotient().to_string(),
+        sqrt_price_x96_after: pool.sqrt_ratio_x96.to_string(),
+        tick_after: pool.tick_current,
+        liquidity_after: pool.liquidity.to_string(),
+    })
+}
+
+fn position_amounts(position: &Position<TickListDataProvider>) -> Result<String> {
+    let MintAmounts { amount0, amount1 } = position.mint_amounts().map_err(to_string)?;
+    to_json(&PositionAmounts {
+        liquidity: position.liquidity.to_string(),
+        amount0: position
+            .amount0()
+            .map_err(to_string)?
+            .quotient()
+            .to_string(),
+        amount1: position
+            .amount1()
+            .map_err(to_string)?
+            .quotient()
+            .to_string(),
+        mint_amount0: amount0.to_string(),
+        mint_amount1: amount1.to_string(),
+    })
+}
+
+fn build_position(pool: &str, tick_lower: i32, tick_upper: i32, liquidity: &str) -> Result<String> {
+    let pool = from_json::<PoolState>(pool)?.into_pool()?;
+    position_amounts(
+        &Position::try_new(pool, parse(liquidity)?, tick_lower, tick_upper).map_err(to_string)?,
+    )
+}
+
+fn build_position_from_amounts(
+    pool: &str,
+    tick_lower: i32,
+    tick_upper: i32,
+    amount0: &str,
+    amount1: &str,
+) -> Result<String> {
+    let pool = from_json::<PoolState>(pool)?.into_pool()?;
+    let position = Position::from_amounts(
+        pool,
+        tick_lower,
+        tick_upper,
+        parse(amount0)?,
+        parse(amount1)?,
+        true,
+    )
+    .map_err(to_string)?;
+    position_amounts(&position)
+}
+
+fn build_swap_call_parameters(trade: &str, options: &str) -> Result<String> {
+    let trade = from_json::<TradeState>(trade)?;
+    let options = from_json::<SwapOptionsState>(options)?;
+    let pools = trade
+        .pools
+        .into_iter()
+        .map(PoolState::into_pool)
+        .collect::<Result<Vec<_>>>()?;
+    let (Some(first), Some(last)) = (pools.first(), pools.last()) else {
+        return Err("pools must not be empty".to_string());
+    };
+    if pools.iter().any(|pool| pool.chain_id() != first.chain_id()) {
+        return Err("pools must be on the same chain".to_string());
+    }
+    let token_in = pool_token(first, trade.token_in)?;
+    let token_out = pool_token(last, trade.token_out)?;
+    let mut current_input_token = trade.token_in;
+    for pool in &pools {
+        current_input_token = if current_input_token == pool.token0.address {
+            pool.token1.address
+        } else if current_input_token == pool.token1.address {
+            pool.token0.address
+        } else {
+            return Err("pools must connect tokenIn to tokenOut".to_string());
+        };
+    }
+    if current_input_token != trade.token_out {
+        return Err("pools must connect tokenIn to tokenOut".to_string());
+    }
+    let (amount_token, trade_type) = match trade.trade_type {
+        TradeKind::ExactInput => (token_in.clone(), TradeType::ExactInput),
+        TradeKind::ExactOutput => (token_out.clone(), TradeType::ExactOutput),
+    };
+    let amount = CurrencyAmount::from_raw_amount(amount_token, trade.amount.to_big_int())
+        .map_err(to_string)?;
+    let trade = Trade::from_route(Route::new(pools, token_in, token_out), amount, trade_type)
+        .map_err(to_string)?;
+    let parameters = swap_call_parameters(
+        &mut [trade],
+        SwapOptions {
+            slippage_tolerance: Percent::new(options.slippage_tolerance_bips, 10000),
+            recipient: options.recipient,
+            sqrt_price_limit_x96: options.sqrt_price_limit_x96,
+            ..Default::default()
+        },
+    )
+    .map_err(to_string)?;
+    to_json(&CallParameters::from(parameters))
+}
+
+fn build_add_call_parameters(position: &str, options: &str) -> Result<String> {
+    let position = from_json::<PositionState>(position)?;
+    let options = from_json::<AddLiquidityOptionsState>(options)?;
+    let chain_id = position.pool.chain_id;
+    let mut position = Position::try_new(
+        position.pool.into_pool()?,
+        position.liquidity.to(),
+        position.tick_lower,
+        position.tick_upper,
+    )
+    .map_err(to_string)?;
+    let specific_opts = match (options.recipient, options.token_id) {
+        (Some(recipient), None) => AddLiquiditySpecificOptions::Mint(MintSpecificOptions {
+            recipient,
+            create_pool: options.create_pool,
+        }),
+        (None, Some(token_id)) => {
+            AddLiquiditySpecificOptions::Increase(IncreaseSpecificOptions { token_id })
+        }
+        _ => return Err("exactly one of recipient and tokenId must be given".to_string()),
+    };
+    let parameters = add_call_parameters(
+        &mut position,
+        AddLiquidityOptions {
+            slippage_tolerance: Percent::new(options.slippage_tolerance_bips, 10000),
+            deadline: options.deadline,
+            use_native: options.use_native.then(|| Ether::on_chain(chain_id)),
+            token0_permit: None,
+            token1_permit: None,
+            specific_opts,
+        },
+    )
+    .map_err(to_string)?;
+    to_json(&CallParameters::from(parameters))
+}
+
+/// Returns the sqrt ratio as a Q64.96 for the given tick as a decimal string.
+#[inline]
+#[wasm_bindgen(js_name = getSqrtRatioAtTick)]
+pub fn get_sqrt_ratio_at_tick_js(tick: i32) -> Result<String, JsError> {
+    if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+        return Err(JsError::new("TICK_BOUND"));
+    }
+    get_sqrt_ratio_at_tick(tick.to_i24())
+        .map(|sqrt_ratio_x96| sqrt_ratio_x96.to_string())
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Returns the greatest tick whose sqrt ratio is at most the given sqrt ratio.
+#[inline]
+#[wasm_bindgen(js_name = getTickAtSqrtRatio)]
+pub fn get_tick_at_sqrt_ratio_js(sqrt_ratio_x96: &str) -> Result<i32, JsError> {
+    parse::<U160>(sqrt_ratio_x96)
+        .and_then(|sqrt_ratio_x96| get_tick_at_sqrt_ratio(sqrt_ratio_x96).map_err(to_string))
+        .map(|tick| tick.as_i32())
+        .map_err(|e| JsError::new(&e))
+}
+
+/// Returns the closest tick to `tick` that is a multiple of `tickSpacing`.
+#[inline]
+#[wasm_bindgen(js_name = nearestUsableTick)]
+pub fn nearest_usable_tick_js(tick: i32, tick_spacing: i32) -> Result<i32, JsError> {
+    if tick_spacing <= 0 {
+        return Err(JsError::new("TICK_SPACING"));
+    }
+    if !(MIN_TICK_I32..=MAX_TICK_I32).contains(&tick) {
+        return Err(JsError::new("TICK_BOUND"));
+    }
+    Ok(nearest_usable_tick(tick, tick_spacing))
+}
+
+/// Returns the sqrt ratio as a Q64.96 corresponding to a given ratio of `amount1` and `amount0`.
+#[inline]
+#[wasm_bindgen(js_name = encodeSqrtRatioX96)]
+pub fn encode_sqrt_ratio_x96_js(amount1: &str, amount0: &str) -> Result<String, JsError> {
+    let amount1 = parse::<U256>(amount1).map_err(|e| JsError::new(&e))?;
+    let amount0 = parse::<U256>(amount0).map_err(|e| JsError::new(&e))?;
+    if amount0.is_zero() {
+        return Err(JsError::new("amount0 must be positive"));
+    }
+    Ok(encode_sqrt_ratio_x96::<160, 3>(amount1.to_big_int(), amount0.to_big_int()).to_string())
+}
+
+/// Quotes an exact input swap against a pool, returning the output amount and the pool state
+/// after the swap as JSON.
+#[inline]
+#[wasm_bindgen(js_name = poolGetOutputAmount)]
+pub fn pool_get_output_amount(
+    pool: &str,
+    token_in: &str,
+    amount_in: &str,
+) -> Result<String, JsError> {
+    quote(pool, token_in, amount_in, true).map_err(|e| JsError::new(&e))
+}
+
+/// Quotes an exact output swap against a pool, returning the input amount and the pool state
+/// after the swap as JSON.
+#[inline]
+#[wasm_bindgen(js_name = poolGetInputAmount)]
+pub fn pool_get_input_amount(
+    pool: &str,
+    token_out: &str,
+    amount_out: &str,
+) -> Result<String, JsError> {
+    quote(pool, token_out, amount_out, false).map_err(|e| JsError::new(&e))
+}
+
+/// Returns the liquidity, amounts and mint amounts of a position with the given liquidity as JSON.
+#[inline]
+#[wasm_bindgen(js_name = positionAmounts)]
+pub fn position_amounts_js(
+    pool: &str,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: &str,
+) -> Result<String, JsError> {
+    build_position(pool, tick_lower, tick_upper, liquidity).map_err(|e| JsError::new(&e))
+}
+
+/// Returns the liquidity, amounts and mint amounts of the largest position that can be minted with
+/// the given amounts as JSON.
+#[inline]
+#[wasm_bindgen(js_name = positionFromAmounts)]
+pub fn position_from_amounts_js(
+    pool: &str,
+    tick_lower: i32,
+    tick_upper: i32,
+    amount0: &str,
+    amount1: &str,
+) -> Result<String, JsError> {
+    build_position_from_amounts(pool, tick_lower, tick_upper, amount0, amount1)
+        .map_err(|e| JsError::new(&e))
+}
+
+/// Produces the calldata and value for a trade through a single route on `SwapRouter02` as JSON.
+#[inline]
+#[wasm_bindgen(js_name = swapCallParameters)]
+pub fn swap_call_parameters_js(trade: &str, options: &str) -> Result<String, JsError> {
+    build_swap_call_parameters(trade, options).map_err(|e| JsError::new(&e))
+}
+
+/// Produces the calldata and value for minting or increasing a position on the
+/// `NonfungiblePositionManager` as JSON.
+#[inline]
+#[wasm_bindgen(js_name = addCallParameters)]
+pub fn add_call_parameters_js(position: &str, options: &str) -> Result<String, JsError> {
+    build_add_call_parameters(position, options).map_err(|e| JsError::new(&e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::*;
+    use alloc::format;
+
+    fn pool_json() -> String {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let ticks = pool
+            .tick_data_provider
+            .iter()
+            .map(|tick| {
+                format!(
+                    r#"{{"index":{},"liquidityGross":"{}","liquidityNet":"{}"}}"#,
+                    tick.index, tick.liquidity_gross, tick.liquidity_net
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        format!(
+            r#"{{"chainId":1,"token0":{{"address":"{}","decimals":18}},"token1":{{"address":"{}","decimals":18}},"fee":3000,"sqrtPriceX96":"{}","liquidity":"{}","ticks":[{ticks}]}}"#,
+            pool.token0.address, pool.token1.address, pool.sqrt_ratio_x96, pool.liquidity
+        )
+    }
+
+    #[test]
+    fn test_quote() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let amount_out = pool
+            .get_output_amount(
+                &CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+                None,
+            )
+            .unwrap();
+        let result = quote(&pool_json(), &TOKEN0.address.to_string(), "100", true).unwrap();
+        assert!(result.starts_with(&format!(r#"{{"amount":"{}","#, amount_out.quotient())));
+        assert!(quote(&pool_json(), &TOKEN2.address.to_string(), "100", true).is_err());
+    }
+
+    #[test]
+    fn test_unknown_fee_tier() {
+        let pool_json = pool_json().replace(r#""fee":3000"#, r#""fee":25"#);
+        assert!(quote(&pool_json, &TOKEN0.address.to_string(), "100", true).is_err());
+        let pool_json = pool_json.replace(r#""fee":25"#, r#""fee":25,"tickSpacing":0"#);
+        assert!(quote(&pool_json, &TOKEN0.address.to_string(), "100", true).is_err());
+    }
+
+    #[test]
+    fn test_position() {
+        let pool = make_pool(TOKEN0.clone(), TOKEN1.clone());
+        let position = Position::new(pool, 1_000_000, -120, 120);
+        let MintAmounts { amount0, amount1 } = position.mint_amounts().unwrap();
+        assert_eq!(
+            build_position(&pool_json(), -120, 120, "1000000").unwrap(),
+            format!(
+                r#"{{"liquidity":"1000000","amount0":"{}","amount1":"{}","mintAmount0":"{amount0}","mintAmount1":"{amount1}"}}"#,
+                position.amount0().unwrap().quotient(),
+                position.amount1().unwrap().quotient(),
+            )
+        );
+        let from_amounts =
+            build_position_from_amounts(&pool_json(), -120, 120, &amount0.to_string(), "0x10000")
+                .unwrap();
+        assert!(from_amounts.contains(r#""liquidity":""#));
+    }
+
+    #[test]
+    fn test_invalid_position_ticks() {
+        // out of order, misaligned with the tick spacing of 60 and out of bounds
+        for (tick_lower, tick_upper) in [(120, -120), (-100, 120), (-887280, 120)] {
+            assert!(build_position(&pool_json(), tick_lower, tick_upper, "1000000").is_err());
+            assert!(build_position_from_amounts(
+                &pool_json(),
+                tick_lower,
+                tick_upper,
+                "1000",
+                "1000"
+            )
+            .is_err());
+        }
+        let position = format!(
+            r#"{{"pool":{},"tickLower":-120,"tickUpper":100,"liquidity":"1000000"}}"#,
+            pool_json()
+        );
+        assert!(build_add_call_parameters(
+            &position,
+            r#"{"slippageToleranceBips":50,"deadline":"123","tokenId":"1"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_swap_call_parameters() {
+        let trade = format!(
+            r#"{{"pools":[{}],"tokenIn":"{}","tokenOut":"{}","amount":"100","tradeType":"exactInput"}}"#,
+            pool_json(),
+            TOKEN0.address,
+            TOKEN1.address
+        );
+        let options = format!(
+            r#"{{"slippageToleranceBips":50,"recipient":"{}"}}"#,
+            Address::ZERO
+        );
+        let mut expected = [Trade::from_route(
+            Route::new(
+                vec![make_pool(TOKEN0.clone(), TOKEN1.clone())],
+                TOKEN0.clone(),
+                TOKEN1.clone(),
+            ),
+            CurrencyAmount::from_raw_amount(TOKEN0.clone(), 100).unwrap(),
+            TradeType::ExactInput,
+        )
+        .unwrap()];
+        let expected = swap_call_parameters(
+            &mut expected,
+            SwapOptions {
+                slippage_tolerance: Percent::new(50, 10000),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            build_swap_call_parameters(&trade, &options).unwrap(),
+            to_json(&CallParameters::from(expected)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_swap_route() {
+        let options = format!(
+            r#"{{"slippageToleranceBips":50,"recipient":"{}"}}"#,
+            Address::ZERO
+        );
+        let trade = |pools: &str, token_out: &Token| {
+            format!(
+                r#"{{"pools":[{pools}],"tokenIn":"{}","tokenOut":"{}","amount":"100","tradeType":"exactInput"}}"#,
+                TOKEN0.address, token_out.address
+            )
+        };
+        let other_chain = pool_json().replace(r#""chainId":1"#, r#""chainId":2"#);
+        for trade in [
+            trade("", &TOKEN1),
+            trade(&format!("{},{other_chain}", pool_json()), &TOKEN0),
+            trade(&pool_json(), &TOKEN2),
+            trade(&format!("{},{}", pool_json(), pool_json()), &TOKEN1),
+        ] {
+            assert!(build_swap_call_parameters(&trade, &options).is_err());
+        }
+    }
+
+    #[test]
+    fn test_add_call_parameters() {
+        let position = format!(
+            r#"{{"pool":{},"tickLower":-120,"tickUpper":120,"liquidity":"1000000"}}"#,
+            pool_json()
+        );
+        let mint = build_add_call_parameters(
+            &position,
+            &format!(
+                r#"{{"slippageToleranceBips":50,"deadline":"123","recipient":"{}"}}"#,
+                Address::ZERO
+            ),
+        )
+        .unwrap();
+        let increase = build_add_call_parameters(
+            &position,
+            r#"{"slippageToleranceBips":50,"deadline":"123","tokenId":"1"}"#,
+        )
+        .unwrap();
+        assert_ne!(mint, increase);
+        assert!(build_add_call_parameters(
+            &position,
+            r#"{"slippageToleranceBips":50,"deadline":"123"}"#
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! Run with `wasm-pack test --node -- --features wasm`
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use uniswap_v3_sdk::wasm::*;
+use wasm_bindgen_test::*;
+
+const POOL: &str = r#"{
+    "chainId": 1,
+    "token0": { "address": "0x0000000000000000000000000000000000000001", "decimals": 18 },
+    "token1": { "address": "0x0000000000000000000000000000000000000002", "decimals": 18 },
+    "fee": 3000,
+    "sqrtPriceX96": "79228162514264337593543950336",
+    "liquidity": "1000000",
+    "ticks": [
+        { "index": -887220, "liquidityNet": "1000000", "liquidityGross": "1000000" },
+        { "index": 887220, "liquidityNet": "-1000000", "liquidityGross": "1000000" }
+    ]
+}"#;
+
+#[wasm_bindgen_test]
+fn test_tick_math() {
+    let sqrt_ratio_x96 = get_sqrt_ratio_at_tick_js(0).unwrap();
+    assert_eq!(sqrt_ratio_x96, "79228162514264337593543950336");
+    assert_eq!(get_tick_at_sqrt_ratio_js(&sqrt_ratio_x96).unwrap(), 0);
+    assert_eq!(
+        get_tick_at_sqrt_ratio_js("0x1000000000000000000000000").unwrap(),
+        0
+    );
+    assert_eq!(nearest_usable_tick_js(-887272, 60).unwrap(), -887220);
+    assert!(nearest_usable_tick_js(0, 0).is_err());
+    assert!(nearest_usable_tick_js(887273, 60).is_err());
+    assert!(get_sqrt_ratio_at_tick_js(1 << 23).is_err());
+    assert_eq!(
+        encode_sqrt_ratio_x96_js("1", "1").unwrap(),
+        "79228162514264337593543950336"
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_pool_quote() {
+    let quote =
+        pool_get_output_amount(POOL, "0x0000000000000000000000000000000000000001", "100").unwrap();
+    assert!(quote.starts_with(r#"{"amount":"98","#));
+    assert!(
+        pool_get_output_amount(POOL, "0x0000000000000000000000000000000000000003", "100").is_err()
+    );
+}
+
+#[wasm_bindgen_test]
+fn test_position_amounts() {
+    let amounts = position_amounts_js(POOL, -120, 120, "1000000").unwrap();
+    assert!(amounts.starts_with(r#"{"liquidity":"1000000","#));
+}
+
+#[wasm_bindgen_test]
+fn test_swap_call_parameters() {
+    let options =
+        r#"{"slippageToleranceBips":50,"recipient":"0x0000000000000000000000000000000000000003"}"#;
+    let trade = format!(
+        r#"{{"pools":[{POOL}],"tokenIn":"0x0000000000000000000000000000000000000001","tokenOut":"0x0000000000000000000000000000000000000002","amount":"100","tradeType":"exactInput"}}"#
+    );
+    let parameters = swap_call_parameters_js(&trade, options).unwrap();
+    assert!(parameters.starts_with(r#"{"calldata":"0x"#));
+    let trade = format!(
+        r#"{{"pools":[{POOL}],"tokenIn":"0x0000000000000000000000000000000000000001","tokenOut":"0x0000000000000000000000000000000000000003","amount":"100","tradeType":"exactInput"}}"#
+    );
+    assert!(swap_call_parameters_js(&trade, options).is_err());
+    let trade = r#"{"pools":[],"tokenIn":"0x0000000000000000000000000000000000000001","tokenOut":"0x0000000000000000000000000000000000000002","amount":"100","tradeType":"exactInput"}"#;
+    assert!(swap_call_parameters_js(trade, options).is_err());
+}
+
+#[wasm_bindgen_test]
+fn test_add_call_parameters() {
+    let position =
+        format!(r#"{{"pool":{POOL},"tickLower":-120,"tickUpper":120,"liquidity":"1000000"}}"#);
+    let parameters = add_call_parameters_js(
+        &position,
+        r#"{"slippageToleranceBips":50,"deadline":"123","recipient":"0x0000000000000000000000000000000000000003"}"#,
+    )
+    .unwrap();
+    assert!(parameters.starts_with(r#"{"calldata":"0x"#));
+    assert!(add_call_parameters_js(
+        &position,
+        r#"{"slippageToleranceBips":50,"deadline":"123"}"#
+    )
+    .is_err());
+}